@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::quote::QuoteError;
+
 #[error_code]
 pub enum AmmError {
     #[msg("Can't get a TWAP before some observations have been stored")]
@@ -44,4 +46,87 @@ pub enum AmmError {
     BuyDisabled,
     #[msg("The pool disabled selling")]
     SellDisabled,
+    #[msg("`fee_bps` can't be more than `MAX_FEE_BPS`")]
+    InvalidFeeBps,
+    #[msg("The market has already been finalized")]
+    MarketAlreadyFinalized,
+    #[msg("The market must be finalized first")]
+    MarketNotFinalized,
+    #[msg("Only a winning market can graduate")]
+    LosingMarketCannotGraduate,
+    #[msg("A winning market must graduate before it can be closed")]
+    MarketNotGraduated,
+    #[msg("The market has already graduated")]
+    MarketAlreadyGraduated,
+    #[msg("The market is closed")]
+    MarketClosed,
+    #[msg("A market can't go back to trading once it has left it")]
+    MarketCannotResumeTrading,
+    #[msg("The swap would take more out of the reserves than they hold")]
+    InsufficientReserves,
+    #[msg("The vault doesn't hold enough tokens to cover the payout")]
+    InsufficientVaultBalance,
+    #[msg("`winner_premium_bps` and `loser_haircut_bps` can't be more than `MAX_BPS`")]
+    InvalidSettlementBps,
+    #[msg("Liquidity can only be added while the market is trading")]
+    MarketNotTrading,
+    #[msg("Liquidity can't be withdrawn once the market has closed")]
+    LiquidityNotWithdrawable,
+    #[msg("LP wouldn't have gotten back `min_base_amount` or `min_quote_amount`")]
+    RemoveLiquiditySlippageExceeded,
+    #[msg("Virtual reserves must be at least one whole token on each side if the amm mints base, and zero otherwise")]
+    InvalidVirtualReserves,
+    #[msg("`graduation_threshold` must be above `initial_v_quote_reserves`")]
+    InvalidGraduationThreshold,
+    #[msg("The market's quote reserves haven't reached `graduation_threshold`")]
+    GraduationThresholdNotReached,
+    #[msg("There is no liquidity to migrate")]
+    NothingToGraduate,
+    #[msg("A `Recipient` graduation target can't be the default pubkey")]
+    InvalidGraduationTarget,
+    #[msg("An account passed to `graduate` doesn't match the market's graduation target")]
+    InvalidGraduationAccount,
+    #[msg("The base mint can't have a Token-2022 transfer fee")]
+    BaseMintTransferFee,
+    #[msg("A user would have spent more than their `input_amount_max`, reverting")]
+    SwapInputMaxExceeded,
+    #[msg("No input can swap into this `output_amount`")]
+    OutputAmountUnreachable,
+    #[msg("The market is in refund-only mode and can only be closed")]
+    MarketRefunding,
+    #[msg("Only a trading market can switch to refund-only mode")]
+    MarketCannotRefund,
+    #[msg("Only the market's settlement authority can settle it")]
+    InvalidSettlementAuthority,
+    #[msg("Markets can only be settled as finalized or refunding")]
+    InvalidSettlementStatus,
+    #[msg("A market can only be closed once its LPs, and the holders of the base it minted, are out")]
+    MarketNotEmpty,
+    #[msg("A base mint that the amm mints must also have the amm as its freeze authority")]
+    InvalidBaseMintAuthority,
+    #[msg("The metadata accounts are required when the amm mints base")]
+    MissingMetadataAccounts,
+    #[msg("Sells can't take the price below the initial price of the virtual reserves")]
+    BelowInitialPrice,
+}
+
+impl From<QuoteError> for AmmError {
+    fn from(err: QuoteError) -> Self {
+        match err {
+            QuoteError::BuyDisabled => AmmError::BuyDisabled,
+            QuoteError::SellDisabled => AmmError::SellDisabled,
+            QuoteError::MarketClosed => AmmError::MarketClosed,
+            QuoteError::InputAmountOverflow => AmmError::InputAmountOverflow,
+            QuoteError::CastingOverflow => AmmError::CastingOverflow,
+            QuoteError::InsufficientReserves => AmmError::InsufficientReserves,
+            QuoteError::OutputAmountUnreachable => AmmError::OutputAmountUnreachable,
+            QuoteError::BelowInitialPrice => AmmError::BelowInitialPrice,
+        }
+    }
+}
+
+impl From<QuoteError> for anchor_lang::error::Error {
+    fn from(err: QuoteError) -> Self {
+        AmmError::from(err).into()
+    }
 }
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{GraduationTarget, SwapType};
+
+/// Prices are quote units per base unit scaled by `PRICE_SCALE`, like the
+/// oracle's. Reserves and prices are as of after the event.
+#[event]
+pub struct CreateAmmEvent {
+    pub user: Pubkey,
+    pub amm: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub proposal: Pubkey,
+    pub proposal_number: u32,
+    pub v_base_reserves: u64,
+    pub v_quote_reserves: u64,
+    pub price: u128,
+    pub graduation_threshold: u64,
+    pub graduation_target: GraduationTarget,
+    pub fee_bps: u16,
+    pub slot: u64,
+}
+
+#[event]
+pub struct SwapEvent {
+    pub user: Pubkey,
+    pub amm: Pubkey,
+    pub swap_type: SwapType,
+    /// What left the user, before any transfer fee
+    pub input_amount: u64,
+    /// What left the vault, before any transfer fee
+    pub output_amount: u64,
+    pub fee_amount: u64,
+    pub v_base_reserves: u64,
+    pub v_quote_reserves: u64,
+    pub price: u128,
+    pub slot: u64,
+}
+
+#[event]
+pub struct GraduationEvent {
+    pub payer: Pubkey,
+    pub amm: Pubkey,
+    pub graduation_target: GraduationTarget,
+    pub graduated_pool: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub v_base_reserves: u64,
+    pub v_quote_reserves: u64,
+    pub price: u128,
+    pub slot: u64,
+}
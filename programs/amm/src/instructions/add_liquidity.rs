@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, *};
+
+use crate::error::AmmError;
+use crate::generate_amm_seeds;
+use crate::instructions::{get_transfer_fee, receive_base, AddOrRemoveLiquidity};
+use crate::state::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddLiquidityArgs {
+    /// How much quote token you will deposit to the pool
+    pub quote_amount: u64,
+    /// The maximum base token you will deposit to the pool. The first deposit
+    /// into an empty pool deposits exactly this much, which sets the price.
+    pub max_base_amount: u64,
+    /// The minimum LP token you will get back
+    pub min_lp_tokens: u64,
+}
+
+impl AddOrRemoveLiquidity<'_> {
+    pub fn handle_add_liquidity(ctx: Context<Self>, args: AddLiquidityArgs) -> Result<()> {
+        let AddLiquidityArgs {
+            quote_amount,
+            max_base_amount,
+            min_lp_tokens,
+        } = args;
+
+        require!(quote_amount > 0, AmmError::ZeroLiquidityToAdd);
+        require!(min_lp_tokens > 0, AmmError::ZeroMinLpTokens);
+
+        let accounts = ctx.accounts;
+
+        // only what reaches the vault after the transfer fee is liquidity
+        let quote_received = get_transfer_fee(&accounts.quote_mint)?.amount_received(quote_amount);
+
+        let (base_amount, lp_tokens) = {
+            let amm = &mut accounts.amm.load_mut()?;
+
+            require!(
+                amm.status == MarketStatus::Trading,
+                AmmError::MarketNotTrading
+            );
+
+            amm.add_liquidity(quote_received, max_base_amount, accounts.lp_mint.supply)?
+        };
+
+        require_gte!(
+            max_base_amount,
+            base_amount,
+            AmmError::AddLiquidityMaxBaseExceeded
+        );
+        require_gte!(
+            lp_tokens,
+            min_lp_tokens,
+            AmmError::AddLiquiditySlippageExceeded
+        );
+        require!(
+            accounts.user_base_account.amount >= base_amount
+                && accounts.user_quote_account.amount >= quote_amount,
+            AmmError::InsufficientBalance
+        );
+
+        let amm = *accounts.amm.load()?;
+        let seeds = generate_amm_seeds!(amm);
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                accounts.quote_token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.user_quote_account.to_account_info(),
+                    mint: accounts.quote_mint.to_account_info(),
+                    to: accounts.vault_ata_quote.to_account_info(),
+                    authority: accounts.user.to_account_info(),
+                },
+            ),
+            quote_amount,
+            accounts.quote_mint.decimals,
+        )?;
+
+        receive_base(
+            &amm,
+            &accounts.base_mint,
+            &accounts.vault_ata_base,
+            &accounts.user_base_account,
+            accounts.user.to_account_info(),
+            &accounts.base_token_program,
+            base_amount,
+        )?;
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                accounts.lp_token_program.to_account_info(),
+                MintTo {
+                    mint: accounts.lp_mint.to_account_info(),
+                    to: accounts.user_lp_account.to_account_info(),
+                    authority: accounts.amm.to_account_info(),
+                },
+                &[seeds],
+            ),
+            lp_tokens,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, *};
+
+use crate::error::AmmError;
+use crate::generate_amm_seeds;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CloseMarket<'info> {
+    #[account(mut)]
+    pub amm: AccountLoader<'info, Amm>,
+    /// CHECK: only receives the rent of the closed accounts
+    #[account(
+        mut,
+        constraint = creator.key() == amm.load()?.creator,
+    )]
+    pub creator: UncheckedAccount<'info>,
+    /// CHECK: only used as the authority of the fee token accounts
+    #[account(
+        constraint = fee_recipient.key() == amm.load()?.fee_recipient,
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = base_mint.key() == amm.load()?.base_mint,
+        mint::token_program = base_token_program,
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        constraint = quote_mint.key() == amm.load()?.quote_mint,
+        mint::token_program = quote_token_program,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = fee_recipient,
+        token::token_program = base_token_program,
+    )]
+    pub fee_recipient_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = fee_recipient,
+        token::token_program = quote_token_program,
+    )]
+    pub fee_recipient_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [AMM_LP_MINT_SEED_PREFIX, amm.key().as_ref()],
+        bump,
+        mint::token_program = lp_token_program,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = base_token_program,
+    )]
+    pub vault_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub vault_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub lp_token_program: Program<'info, Token2022>,
+}
+
+impl CloseMarket<'_> {
+    pub fn validate(&self) -> Result<()> {
+        let amm = self.amm.load()?;
+
+        // once closed, nothing can be withdrawn, so every LP has to be out
+        require_eq!(self.lp_mint.supply, 0, AmmError::MarketNotEmpty);
+
+        // until a market graduates, sellers of base that it minted are paid
+        // out of its virtual liquidity, so they have to be out too. Base
+        // sent to the vault only makes this easier to meet.
+        if amm.base_supply == BaseSupply::Minted && amm.status != MarketStatus::Graduated {
+            require_gte!(
+                self.vault_ata_base.amount,
+                self.base_mint.supply,
+                AmmError::MarketNotEmpty
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Permissionless. Losing, graduated and refunding markets, and winning
+    /// markets without virtual liquidity, can be closed once their LPs and
+    /// the holders of base that they minted are out. Whatever the vaults
+    /// still hold, such as uncollected fees, a losing market's haircut and
+    /// rounding dust, goes to the fee recipient, except for minted base,
+    /// which is burned. The vaults and the LP mint are then closed, returning
+    /// their rent to the market's creator.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let accounts = ctx.accounts;
+
+        let amm = {
+            let amm = &mut accounts.amm.load_mut()?;
+            amm.set_status(MarketStatus::Closed)?;
+            amm.base_fees_accrued = 0;
+            amm.quote_fees_accrued = 0;
+            **amm
+        };
+        let seeds = generate_amm_seeds!(amm);
+
+        let base_left = accounts.vault_ata_base.amount;
+        let quote_left = accounts.vault_ata_quote.amount;
+
+        if base_left > 0 {
+            match amm.base_supply {
+                BaseSupply::Minted => token_interface::burn(
+                    CpiContext::new_with_signer(
+                        accounts.base_token_program.to_account_info(),
+                        Burn {
+                            mint: accounts.base_mint.to_account_info(),
+                            from: accounts.vault_ata_base.to_account_info(),
+                            authority: accounts.amm.to_account_info(),
+                        },
+                        &[seeds],
+                    ),
+                    base_left,
+                )?,
+                BaseSupply::Deposited => token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        accounts.base_token_program.to_account_info(),
+                        TransferChecked {
+                            from: accounts.vault_ata_base.to_account_info(),
+                            mint: accounts.base_mint.to_account_info(),
+                            to: accounts.fee_recipient_base_account.to_account_info(),
+                            authority: accounts.amm.to_account_info(),
+                        },
+                        &[seeds],
+                    ),
+                    base_left,
+                    accounts.base_mint.decimals,
+                )?,
+            }
+        }
+
+        if quote_left > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    accounts.quote_token_program.to_account_info(),
+                    TransferChecked {
+                        from: accounts.vault_ata_quote.to_account_info(),
+                        mint: accounts.quote_mint.to_account_info(),
+                        to: accounts.fee_recipient_quote_account.to_account_info(),
+                        authority: accounts.amm.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                quote_left,
+                accounts.quote_mint.decimals,
+            )?;
+        }
+
+        for (account, token_program) in [
+            (
+                accounts.vault_ata_base.to_account_info(),
+                accounts.base_token_program.to_account_info(),
+            ),
+            (
+                accounts.vault_ata_quote.to_account_info(),
+                accounts.quote_token_program.to_account_info(),
+            ),
+            (
+                accounts.lp_mint.to_account_info(),
+                accounts.lp_token_program.to_account_info(),
+            ),
+        ] {
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program,
+                CloseAccount {
+                    account,
+                    destination: accounts.creator.to_account_info(),
+                    authority: accounts.amm.to_account_info(),
+                },
+                &[seeds],
+            ))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, *};
+
+use crate::generate_amm_seeds;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CollectFees<'info> {
+    #[account(mut)]
+    pub amm: AccountLoader<'info, Amm>,
+    /// CHECK: only used as the authority of the fee token accounts
+    #[account(
+        constraint = fee_recipient.key() == amm.load()?.fee_recipient,
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+    #[account(
+        constraint = base_mint.key() == amm.load()?.base_mint,
+        mint::token_program = base_token_program,
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        constraint = quote_mint.key() == amm.load()?.quote_mint,
+        mint::token_program = quote_token_program,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = fee_recipient,
+        token::token_program = base_token_program,
+    )]
+    pub fee_recipient_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = fee_recipient,
+        token::token_program = quote_token_program,
+    )]
+    pub fee_recipient_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = base_token_program,
+    )]
+    pub vault_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub vault_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+impl CollectFees<'_> {
+    /// Permissionless, since fees can only ever go to the `fee_recipient`.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let accounts = ctx.accounts;
+
+        let amm = *accounts.amm.load()?;
+        let seeds = generate_amm_seeds!(amm);
+
+        let (base_fees, quote_fees) = (amm.base_fees_accrued, amm.quote_fees_accrued);
+
+        {
+            let amm = &mut accounts.amm.load_mut()?;
+            amm.base_fees_accrued = 0;
+            amm.quote_fees_accrued = 0;
+        }
+
+        for (amount, mint, from, to, token_program) in [
+            (
+                base_fees,
+                &accounts.base_mint,
+                &accounts.vault_ata_base,
+                &accounts.fee_recipient_base_account,
+                &accounts.base_token_program,
+            ),
+            (
+                quote_fees,
+                &accounts.quote_mint,
+                &accounts.vault_ata_quote,
+                &accounts.fee_recipient_quote_account,
+                &accounts.quote_token_program,
+            ),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: from.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: accounts.amm.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+                mint.decimals,
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as SplMint;
+use anchor_spl::token_interface::{self, *};
+
+use crate::error::AmmError;
+use crate::generate_amm_seeds;
+use crate::quote::TransferFee;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct AddOrRemoveLiquidity<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(mut)]
+    pub amm: AccountLoader<'info, Amm>,
+    #[account(
+        mut,
+        seeds = [AMM_LP_MINT_SEED_PREFIX, amm.key().as_ref()],
+        bump,
+        mint::token_program = lp_token_program,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = base_mint.key() == amm.load()?.base_mint,
+        mint::token_program = base_token_program,
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        constraint = quote_mint.key() == amm.load()?.quote_mint,
+        mint::token_program = quote_token_program,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = user,
+        token::token_program = lp_token_program,
+    )]
+    pub user_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = user,
+        token::token_program = base_token_program,
+    )]
+    pub user_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = user,
+        token::token_program = quote_token_program,
+    )]
+    pub user_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = base_token_program,
+    )]
+    pub vault_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub vault_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub lp_token_program: Program<'info, Token2022>,
+}
+
+/// Reads the transfer fee that `mint` charges in the current epoch.
+pub fn get_transfer_fee(mint: &InterfaceAccount<Mint>) -> Result<TransferFee> {
+    let mint_info = mint.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<SplMint>::unpack(&mint_data)?;
+
+    let Ok(config) = mint.get_extension::<TransferFeeConfig>() else {
+        return Ok(TransferFee::default());
+    };
+
+    let fee = config.get_epoch_fee(Clock::get()?.epoch);
+
+    Ok(TransferFee {
+        transfer_fee_bps: u16::from(fee.transfer_fee_basis_points),
+        maximum_fee: u64::from(fee.maximum_fee),
+    })
+}
+
+/// Whether `mint` has the Token-2022 transfer fee extension, even if the fee
+/// is currently zero.
+pub fn has_transfer_fee(mint: &InterfaceAccount<Mint>) -> Result<bool> {
+    let mint_info = mint.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<SplMint>::unpack(&mint_data)?;
+
+    Ok(mint.get_extension::<TransferFeeConfig>().is_ok())
+}
+
+/// Pays `amount` of base out of the market to `to`. A `Minted` market mints
+/// it, a `Deposited` one pays it out of its vault, leaving the fees it holds
+/// for the fee recipient.
+pub fn send_base<'info>(
+    amm: &Amm,
+    amm_account: &AccountLoader<'info, Amm>,
+    base_mint: &InterfaceAccount<'info, Mint>,
+    vault_ata_base: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    base_token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let seeds = generate_amm_seeds!(amm);
+
+    match amm.base_supply {
+        BaseSupply::Minted => token_interface::mint_to(
+            CpiContext::new_with_signer(
+                base_token_program.to_account_info(),
+                MintTo {
+                    mint: base_mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: amm_account.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        ),
+        BaseSupply::Deposited => {
+            require_gte!(
+                vault_ata_base.amount.saturating_sub(amm.base_fees_accrued),
+                amount,
+                AmmError::InsufficientVaultBalance
+            );
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    base_token_program.to_account_info(),
+                    TransferChecked {
+                        from: vault_ata_base.to_account_info(),
+                        mint: base_mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: amm_account.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+                base_mint.decimals,
+            )
+        }
+    }
+}
+
+/// Takes `amount` of base from `from`, which `authority` owns, into the
+/// market. A `Minted` market burns it, a `Deposited` one keeps it in its
+/// vault.
+pub fn receive_base<'info>(
+    amm: &Amm,
+    base_mint: &InterfaceAccount<'info, Mint>,
+    vault_ata_base: &InterfaceAccount<'info, TokenAccount>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    base_token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    match amm.base_supply {
+        BaseSupply::Minted => token_interface::burn(
+            CpiContext::new(
+                base_token_program.to_account_info(),
+                Burn {
+                    mint: base_mint.to_account_info(),
+                    from: from.to_account_info(),
+                    authority,
+                },
+            ),
+            amount,
+        ),
+        BaseSupply::Deposited => token_interface::transfer_checked(
+            CpiContext::new(
+                base_token_program.to_account_info(),
+                TransferChecked {
+                    from: from.to_account_info(),
+                    mint: base_mint.to_account_info(),
+                    to: vault_ata_base.to_account_info(),
+                    authority,
+                },
+            ),
+            amount,
+            base_mint.decimals,
+        ),
+    }
+}
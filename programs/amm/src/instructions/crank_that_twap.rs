@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CrankThatTwap<'info> {
+    #[account(mut)]
+    pub amm: AccountLoader<'info, Amm>,
+}
+
+impl CrankThatTwap<'_> {
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let amm = &mut ctx.accounts.amm.load_mut()?;
+
+        amm.update_twap(Clock::get()?.slot)?;
+
+        Ok(())
+    }
+}
@@ -1,57 +1,117 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::*;
 use anchor_spl::metadata::{
     create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
     Metadata
 };
 use crate::error::AmmError;
+use crate::events::CreateAmmEvent;
+use crate::instructions::has_transfer_fee;
 use crate::state::*;
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateAmmArgs {
+    /// `pof`, `uri` and `symbol` name a base mint that the amm mints, and
+    /// are ignored otherwise
+    pub pof: String,
+    pub uri: String,
+    /// The proposal PDA this market is created for, which can be derived
+    /// before the proposal exists
+    pub proposal: Pubkey,
+    pub proposal_number: u32,
+    pub settlement_authority: Pubkey,
+    pub symbol: String,
+    pub twap_initial_observation: u128,
+    pub twap_max_observation_change_per_update: u128,
+    pub fee_bps: u16,
+    pub fee_recipient: Pubkey,
+    pub winner_premium_bps: u16,
+    pub loser_haircut_bps: u16,
+    /// Both in raw token units, and at least one whole token each if the amm
+    /// mints base. Zero if it doesn't, in which case LPs seed the price.
+    pub initial_v_base_reserves: u64,
+    pub initial_v_quote_reserves: u64,
+    /// Must be above `initial_v_quote_reserves`
+    pub graduation_threshold: u64,
+    pub graduation_target: GraduationTarget,
+    pub base_mint_decimals: u8,
+}
+
 #[derive(Accounts)]
+#[instruction(args: CreateAmmArgs)]
 pub struct CreateAmm<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     #[account(
         init,
         payer = user,
-        space = 8 + 144,
+        space = 8 + std::mem::size_of::<Amm>(),
         seeds = [
             AMM_SEED_PREFIX,
             base_mint.key().as_ref(),
-            quote_mint.key().as_ref()
+            quote_mint.key().as_ref(),
+            user.key().as_ref(),
         ],
         bump
     )]
     pub amm: AccountLoader<'info, Amm>,
-    #[account(mut,
-    mint::authority = amm,
-    mint::freeze_authority = amm,
-    mint::decimals = 6,
+    /// Either a fresh mint whose mint and freeze authority is the amm, or an
+    /// existing mint such as one of a conditional vault's. See `BaseSupply`.
+    #[account(
+        mut,
+        mint::decimals = args.base_mint_decimals,
+        mint::token_program = base_token_program,
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = quote_token_program,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = user,
+        seeds = [AMM_LP_MINT_SEED_PREFIX, amm.key().as_ref()],
+        bump,
+        mint::authority = amm,
+        mint::freeze_authority = amm,
+        mint::decimals = quote_mint.decimals,
+        mint::token_program = lp_token_program,
+        extensions::close_authority::authority = amm,
     )]
-    pub base_mint: Box<Account<'info, Mint>>,
-    pub quote_mint: Box<Account<'info, Mint>>,
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         init_if_needed,
         payer = user,
         associated_token::authority = amm,
-        associated_token::mint = base_mint
+        associated_token::mint = base_mint,
+        associated_token::token_program = base_token_program,
     )]
-    pub vault_ata_base: Box<Account<'info, TokenAccount>>,
+    pub vault_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         init_if_needed,
         payer = user,
         associated_token::authority = amm,
-        associated_token::mint = quote_mint
+        associated_token::mint = quote_mint,
+        associated_token::token_program = quote_token_program,
     )]
-    pub vault_ata_quote: Box<Account<'info, TokenAccount>>,
+    pub vault_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    /// The base mint's program, which can be either SPL Token or Token-2022
+    pub base_token_program: Interface<'info, TokenInterface>,
+    /// The quote mint's program, which can be either SPL Token or Token-2022
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    /// LP mints always live under Token-2022, so that `close_market` can
+    /// close them
+    pub lp_token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
-    /// CHECK: verified via cpi into token metadata
+    /// CHECK: verified via cpi into token metadata. Only needed if the amm
+    /// mints base.
     #[account(mut)]
-    pub base_token_metadata: AccountInfo<'info>,
-    pub metadata_program: Program<'info, Metadata>,
+    pub base_token_metadata: Option<UncheckedAccount<'info>>,
+    pub metadata_program: Option<Program<'info, Metadata>>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -63,37 +123,129 @@ impl CreateAmm<'_> {
             AmmError::SameTokenMints
         );
 
-        require_eq!(self.base_mint.supply, 0, AmmError::InvalidSupply);
+        if self.base_supply() == BaseSupply::Minted {
+            require!(
+                self.base_mint.freeze_authority == COption::Some(self.amm.key()),
+                AmmError::InvalidBaseMintAuthority
+            );
+
+            require_eq!(self.base_mint.supply, 0, AmmError::InvalidSupply);
+        }
+
+        // the curve swaps all of the base it's sent, which only keeps the
+        // books straight if base transfers are fee-free
+        require!(
+            !has_transfer_fee(&self.base_mint)?,
+            AmmError::BaseMintTransferFee
+        );
 
         Ok(())
     }
 
-    pub fn handle(ctx: Context<Self>, pof: String, uri: String, proposal_number: u16, osymbol: String, _: u8) -> Result<()> {
+    /// The amm mints its base if it's the base mint's authority.
+    fn base_supply(&self) -> BaseSupply {
+        if self.base_mint.mint_authority == COption::Some(self.amm.key()) {
+            BaseSupply::Minted
+        } else {
+            BaseSupply::Deposited
+        }
+    }
+
+    pub fn handle(ctx: Context<Self>, args: CreateAmmArgs) -> Result<()> {
+        let base_supply = ctx.accounts.base_supply();
+
+        require_gte!(MAX_FEE_BPS, args.fee_bps, AmmError::InvalidFeeBps);
+        require_gte!(MAX_BPS, args.winner_premium_bps, AmmError::InvalidSettlementBps);
+        require_gte!(MAX_BPS, args.loser_haircut_bps, AmmError::InvalidSettlementBps);
+        require_gte!(
+            MAX_BASE_MINT_DECIMALS,
+            args.base_mint_decimals,
+            AmmError::DecimalScaleError
+        );
+        let virtual_reserves_valid = match base_supply {
+            BaseSupply::Minted => {
+                let one_base = 10_u64
+                    .checked_pow(args.base_mint_decimals as u32)
+                    .ok_or(AmmError::DecimalScaleError)?;
+                let one_quote = 10_u64
+                    .checked_pow(ctx.accounts.quote_mint.decimals as u32)
+                    .ok_or(AmmError::DecimalScaleError)?;
+
+                args.initial_v_base_reserves >= one_base
+                    && args.initial_v_quote_reserves >= one_quote
+            }
+            // virtual base that the amm can't mint could never be paid out
+            BaseSupply::Deposited => {
+                args.initial_v_base_reserves == 0 && args.initial_v_quote_reserves == 0
+            }
+        };
+        require!(virtual_reserves_valid, AmmError::InvalidVirtualReserves);
+        require_gt!(
+            args.graduation_threshold,
+            args.initial_v_quote_reserves,
+            AmmError::InvalidGraduationThreshold
+        );
+        if let GraduationTarget::Recipient(recipient) = args.graduation_target {
+            require_keys_neq!(
+                recipient,
+                Pubkey::default(),
+                AmmError::InvalidGraduationTarget
+            );
+        }
+
+        let CreateAmmArgs {
+            pof,
+            uri,
+            proposal,
+            proposal_number,
+            settlement_authority,
+            symbol: osymbol,
+            twap_initial_observation,
+            twap_max_observation_change_per_update,
+            fee_bps,
+            fee_recipient,
+            winner_premium_bps,
+            loser_haircut_bps,
+            initial_v_base_reserves,
+            initial_v_quote_reserves,
+            graduation_threshold,
+            graduation_target,
+            base_mint_decimals: _,
+        } = args;
+
         let CreateAmm {
             user,
             amm: _,
             base_mint,
             quote_mint,
             base_token_metadata,
+            metadata_program,
             ..
         } = ctx.accounts;
         let current_slot = Clock::get()?.slot;
 
-        // there are null bytes we must trim from string, otherwise string value is longer than we want
-        let quote_token_symbol_raw = osymbol.clone();
-        let quote_token_symbol = quote_token_symbol_raw.trim_matches(char::from(0));
+        if base_supply == BaseSupply::Minted {
+            let (Some(base_token_metadata), Some(metadata_program)) =
+                (base_token_metadata, metadata_program)
+            else {
+                return err!(AmmError::MissingMetadataAccounts);
+            };
 
-        let base_symbol = format!("{}{}", pof, quote_token_symbol);
-       
+            // there are null bytes we must trim from string, otherwise string value is longer than we want
+            let quote_token_symbol_raw = osymbol.clone();
+            let quote_token_symbol = quote_token_symbol_raw.trim_matches(char::from(0));
 
-        let signer_seeds: &[&[u8]; 4] = &[
-            AMM_SEED_PREFIX,
-            base_mint.to_account_info().key.as_ref(),
-            quote_mint.to_account_info().key.as_ref(),
-            &[ctx.bumps.amm],
-        ];
-            
-            let cpi_program = ctx.accounts.metadata_program.to_account_info();
+            let base_symbol = format!("{}{}", pof, quote_token_symbol);
+
+            let signer_seeds: &[&[u8]; 5] = &[
+                AMM_SEED_PREFIX,
+                base_mint.to_account_info().key.as_ref(),
+                quote_mint.to_account_info().key.as_ref(),
+                user.key.as_ref(),
+                &[ctx.bumps.amm],
+            ];
+
+            let cpi_program = metadata_program.to_account_info();
 
             let cpi_accounts = CreateMetadataAccountsV3 {
                 metadata: base_token_metadata.to_account_info(),
@@ -120,22 +272,69 @@ impl CreateAmm<'_> {
                 true,
                 None,
             )?;
-        
+        }
+
         let amm = &mut ctx.accounts.amm.load_init()?;
 
         amm.bump = ctx.bumps.amm;
 
         amm.created_at_slot = current_slot;
+        amm.creator = user.key();
+
+        amm.proposal = proposal;
+        amm.proposal_number = proposal_number;
+        amm.settlement_authority = settlement_authority;
 
         amm.base_mint = base_mint.key();
         amm.quote_mint = quote_mint.key();
+        amm.base_supply = base_supply;
+        amm.lp_mint = ctx.accounts.lp_mint.key();
 
         amm.base_mint_decimals = base_mint.decimals;
         amm.quote_mint_decimals = quote_mint.decimals;
 
-        amm.v_base_reserves = (1_000_000_000_u128 * 10_u128.pow(base_mint.decimals as u32)) as u64;
-        amm.v_quote_reserves = (10_u128 * 10_u128.pow(quote_mint.decimals as u32)) as u64;
-      amm.vault_status = 0;
+        amm.v_base_reserves = initial_v_base_reserves;
+        amm.v_quote_reserves = initial_v_quote_reserves;
+        amm.initial_v_base_reserves = initial_v_base_reserves;
+        amm.initial_v_quote_reserves = initial_v_quote_reserves;
+        amm.graduation_threshold = graduation_threshold;
+        amm.graduation_target = graduation_target;
+        amm.graduated_pool = Pubkey::default();
+        amm.status = MarketStatus::Trading;
+
+        // the virtual reserves own the pool until someone adds liquidity
+        amm.virtual_lp_supply = amm.v_quote_reserves;
+
+        amm.oracle = TwapOracle::new(
+            current_slot,
+            twap_initial_observation,
+            twap_max_observation_change_per_update,
+        );
+
+        amm.fee_bps = fee_bps;
+        amm.fee_recipient = fee_recipient;
+        amm.base_fees_accrued = 0;
+        amm.quote_fees_accrued = 0;
+
+        amm.winner_premium_bps = winner_premium_bps;
+        amm.loser_haircut_bps = loser_haircut_bps;
+
+        emit!(CreateAmmEvent {
+            user: user.key(),
+            amm: ctx.accounts.amm.key(),
+            base_mint: amm.base_mint,
+            quote_mint: amm.quote_mint,
+            lp_mint: amm.lp_mint,
+            proposal,
+            proposal_number,
+            v_base_reserves: amm.v_base_reserves,
+            v_quote_reserves: amm.v_quote_reserves,
+            price: amm.spot_price().unwrap_or(0),
+            graduation_threshold,
+            graduation_target,
+            fee_bps,
+            slot: current_slot,
+        });
 
         Ok(())
     }
@@ -0,0 +1,391 @@
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Burn, Token};
+use anchor_spl::token_interface::{self, *};
+use raydium_cp_swap::cpi::accounts::Initialize;
+use raydium_cp_swap::program::RaydiumCpSwap;
+
+use crate::error::AmmError;
+use crate::events::GraduationEvent;
+use crate::generate_amm_seeds;
+use crate::instructions::get_transfer_fee;
+use crate::state::*;
+
+/// The accounts every graduation needs. The accounts of the market's
+/// `graduation_target` follow in `remaining_accounts`, in the order of
+/// `GraduateToRaydiumCpSwap` or `GraduateToRecipient`.
+#[derive(Accounts)]
+pub struct Graduate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub amm: AccountLoader<'info, Amm>,
+    #[account(
+        mut,
+        constraint = base_mint.key() == amm.load()?.base_mint,
+        mint::token_program = base_token_program,
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        constraint = quote_mint.key() == amm.load()?.quote_mint,
+        mint::token_program = quote_token_program,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [AMM_LP_MINT_SEED_PREFIX, amm.key().as_ref()],
+        bump,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = base_token_program,
+    )]
+    pub vault_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = amm,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub vault_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Raydium takes the initial deposit from the pool creator, so the payer
+/// briefly holds the migrated liquidity in their own token accounts.
+#[derive(Accounts)]
+pub struct GraduateToRaydiumCpSwap<'info> {
+    #[account(mut)]
+    pub payer_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub payer_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub raydium_cp_swap_program: Program<'info, RaydiumCpSwap>,
+    /// CHECK: verified via cpi into token ray
+    pub amm_config: UncheckedAccount<'info>,
+    /// CHECK: verified via cpi into token ray
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: verified via cpi into token ray
+    #[account(mut)]
+    pub pool_state: UncheckedAccount<'info>,
+    /// CHECK: verified via cpi into token ray
+    #[account(mut)]
+    pub lp_mint: UncheckedAccount<'info>,
+    /// CHECK: created by token ray, the LP tokens it receives are burned
+    #[account(mut)]
+    pub creator_lp_token: UncheckedAccount<'info>,
+    /// CHECK: verified via cpi into token ray
+    #[account(mut)]
+    pub token_0_vault: UncheckedAccount<'info>,
+    /// CHECK: verified via cpi into token ray
+    #[account(mut)]
+    pub token_1_vault: UncheckedAccount<'info>,
+    /// CHECK: verified via cpi into token ray
+    #[account(mut)]
+    pub create_pool_fee: UncheckedAccount<'info>,
+    /// CHECK: verified via cpi into token ray
+    #[account(mut)]
+    pub observation_state: UncheckedAccount<'info>,
+    /// Token ray's LP mint is always an SPL Token mint
+    pub lp_token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct GraduateToRecipient<'info> {
+    #[account(mut)]
+    pub recipient_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub recipient_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+impl<'info> Graduate<'info> {
+    /// Permissionless. Migrates the liquidity of a winning market that has
+    /// reached its `graduation_threshold` to the market's
+    /// `graduation_target`, at the curve price. Only the virtual liquidity's
+    /// share moves: the LPs' share stays in the market for them to withdraw.
+    pub fn handle(ctx: Context<'_, '_, 'info, 'info, Self>) -> Result<()> {
+        let accounts = ctx.accounts;
+        let mut remaining_accounts = ctx.remaining_accounts;
+
+        let amm = *accounts.amm.load()?;
+
+        require!(
+            amm.graduation_reached(),
+            AmmError::GraduationThresholdNotReached
+        );
+
+        let (lp_base_amount, lp_quote_amount) = accounts
+            .amm
+            .load_mut()?
+            .retain_lp_liquidity(accounts.lp_mint.supply)?;
+
+        // uncollected fees stay behind for the fee recipient
+        let quote_amount = accounts
+            .vault_ata_quote
+            .amount
+            .saturating_sub(amm.quote_fees_accrued)
+            .saturating_sub(lp_quote_amount);
+        let vault_base_amount = accounts
+            .vault_ata_base
+            .amount
+            .saturating_sub(amm.base_fees_accrued);
+
+        let base_amount: u64 = (quote_amount as u128 * amm.v_base_reserves as u128
+            / amm.v_quote_reserves as u128)
+            .try_into()
+            .map_err(|_| error!(AmmError::CastingOverflow))?;
+
+        // a market that doesn't mint its base can only hand over what it holds
+        let (base_amount, vault_base_amount) = match amm.base_supply {
+            BaseSupply::Minted => (base_amount, vault_base_amount),
+            BaseSupply::Deposited => {
+                let vault_base_amount = vault_base_amount.saturating_sub(lp_base_amount);
+
+                (std::cmp::min(base_amount, vault_base_amount), vault_base_amount)
+            }
+        };
+
+        require!(
+            quote_amount > 0 && base_amount > 0,
+            AmmError::NothingToGraduate
+        );
+
+        accounts.amm.load_mut()?.set_status(MarketStatus::Graduated)?;
+
+        let graduated_pool = match amm.graduation_target {
+            GraduationTarget::RaydiumCpSwap => {
+                let target = GraduateToRaydiumCpSwap::try_accounts(
+                    ctx.program_id,
+                    &mut remaining_accounts,
+                    &[],
+                    &mut Default::default(),
+                    &mut BTreeSet::new(),
+                )?;
+
+                accounts.release_liquidity(
+                    &amm,
+                    base_amount,
+                    vault_base_amount,
+                    quote_amount,
+                    &target.payer_base_account,
+                    &target.payer_quote_account,
+                    accounts.payer.key(),
+                )?;
+
+                // the payer only receives what's left after the quote
+                // transfer fee, so that's all they can deposit
+                let quote_received = get_transfer_fee(&accounts.quote_mint)?
+                    .amount_received(quote_amount);
+
+                accounts.seed_raydium_cp_swap_pool(&target, base_amount, quote_received)?;
+
+                target.pool_state.key()
+            }
+            GraduationTarget::Recipient(recipient) => {
+                let target = GraduateToRecipient::try_accounts(
+                    ctx.program_id,
+                    &mut remaining_accounts,
+                    &[],
+                    &mut Default::default(),
+                    &mut BTreeSet::new(),
+                )?;
+
+                accounts.release_liquidity(
+                    &amm,
+                    base_amount,
+                    vault_base_amount,
+                    quote_amount,
+                    &target.recipient_base_account,
+                    &target.recipient_quote_account,
+                    recipient,
+                )?;
+
+                recipient
+            }
+        };
+
+        accounts.amm.load_mut()?.graduated_pool = graduated_pool;
+
+        emit!(GraduationEvent {
+            payer: accounts.payer.key(),
+            amm: accounts.amm.key(),
+            graduation_target: amm.graduation_target,
+            graduated_pool,
+            base_amount,
+            quote_amount,
+            v_base_reserves: amm.v_base_reserves,
+            v_quote_reserves: amm.v_quote_reserves,
+            price: amm.spot_price().unwrap_or(0),
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    /// Moves `quote_amount` and `base_amount` out of the market into token
+    /// accounts of `owner`. The base side of a `Minted` market mostly only
+    /// exists on the curve, so whatever the vault doesn't hold is minted.
+    #[allow(clippy::too_many_arguments)]
+    fn release_liquidity(
+        &self,
+        amm: &Amm,
+        base_amount: u64,
+        vault_base_amount: u64,
+        quote_amount: u64,
+        base_to: &InterfaceAccount<'info, TokenAccount>,
+        quote_to: &InterfaceAccount<'info, TokenAccount>,
+        owner: Pubkey,
+    ) -> Result<()> {
+        require!(
+            base_to.mint == amm.base_mint
+                && quote_to.mint == amm.quote_mint
+                && base_to.owner == owner
+                && quote_to.owner == owner,
+            AmmError::InvalidGraduationAccount
+        );
+
+        let seeds = generate_amm_seeds!(amm);
+
+        let base_from_vault = std::cmp::min(vault_base_amount, base_amount);
+        let base_to_mint = base_amount - base_from_vault;
+
+        for (amount, mint, from, to, token_program) in [
+            (
+                base_from_vault,
+                &self.base_mint,
+                &self.vault_ata_base,
+                base_to,
+                &self.base_token_program,
+            ),
+            (
+                quote_amount,
+                &self.quote_mint,
+                &self.vault_ata_quote,
+                quote_to,
+                &self.quote_token_program,
+            ),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: from.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: self.amm.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+                mint.decimals,
+            )?;
+        }
+
+        if base_to_mint > 0 {
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    self.base_token_program.to_account_info(),
+                    MintTo {
+                        mint: self.base_mint.to_account_info(),
+                        to: base_to.to_account_info(),
+                        authority: self.amm.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                base_to_mint,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates the pool from the payer's token accounts and burns the LP
+    /// tokens it hands back, so the liquidity can't be pulled.
+    fn seed_raydium_cp_swap_pool(
+        &self,
+        target: &GraduateToRaydiumCpSwap<'info>,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<()> {
+        // token ray wants its mints sorted
+        let base = (
+            self.base_mint.to_account_info(),
+            target.payer_base_account.to_account_info(),
+            self.base_token_program.to_account_info(),
+            base_amount,
+        );
+        let quote = (
+            self.quote_mint.to_account_info(),
+            target.payer_quote_account.to_account_info(),
+            self.quote_token_program.to_account_info(),
+            quote_amount,
+        );
+        let (
+            (token_0_mint, creator_token_0, token_0_program, init_amount_0),
+            (token_1_mint, creator_token_1, token_1_program, init_amount_1),
+        ) = if self.base_mint.key() < self.quote_mint.key() {
+            (base, quote)
+        } else {
+            (quote, base)
+        };
+
+        raydium_cp_swap::cpi::initialize(
+            CpiContext::new(
+                target.raydium_cp_swap_program.to_account_info(),
+                Initialize {
+                    creator: self.payer.to_account_info(),
+                    amm_config: target.amm_config.to_account_info(),
+                    authority: target.authority.to_account_info(),
+                    pool_state: target.pool_state.to_account_info(),
+                    token_0_mint,
+                    token_1_mint,
+                    lp_mint: target.lp_mint.to_account_info(),
+                    creator_token_0,
+                    creator_token_1,
+                    creator_lp_token: target.creator_lp_token.to_account_info(),
+                    token_0_vault: target.token_0_vault.to_account_info(),
+                    token_1_vault: target.token_1_vault.to_account_info(),
+                    create_pool_fee: target.create_pool_fee.to_account_info(),
+                    observation_state: target.observation_state.to_account_info(),
+                    token_program: target.lp_token_program.to_account_info(),
+                    token_0_program,
+                    token_1_program,
+                    associated_token_program: target.associated_token_program.to_account_info(),
+                    system_program: target.system_program.to_account_info(),
+                    rent: target.rent.to_account_info(),
+                },
+            ),
+            init_amount_0,
+            init_amount_1,
+            0,
+        )?;
+
+        let lp_tokens = {
+            let data = target.creator_lp_token.try_borrow_data()?;
+            token::TokenAccount::try_deserialize(&mut &data[..])?.amount
+        };
+
+        token::burn(
+            CpiContext::new(
+                target.lp_token_program.to_account_info(),
+                Burn {
+                    from: target.creator_lp_token.to_account_info(),
+                    mint: target.lp_mint.to_account_info(),
+                    authority: self.payer.to_account_info(),
+                },
+            ),
+            lp_tokens,
+        )
+    }
+}
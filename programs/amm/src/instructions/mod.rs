@@ -1,5 +1,21 @@
+pub use add_liquidity::*;
+pub use close_market::*;
+pub use collect_fees::*;
+pub use common::*;
+pub use crank_that_twap::*;
 pub use create_amm::*;
+pub use graduate::*;
+pub use remove_liquidity::*;
+pub use settle_market::*;
 pub use swap::*;
 
+pub mod add_liquidity;
+pub mod close_market;
+pub mod collect_fees;
+pub mod common;
+pub mod crank_that_twap;
 pub mod create_amm;
+pub mod graduate;
+pub mod remove_liquidity;
+pub mod settle_market;
 pub mod swap;
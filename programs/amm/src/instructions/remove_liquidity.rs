@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, *};
+
+use crate::error::AmmError;
+use crate::generate_amm_seeds;
+use crate::instructions::{get_transfer_fee, send_base, AddOrRemoveLiquidity};
+use crate::state::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoveLiquidityArgs {
+    pub lp_tokens_to_burn: u64,
+    pub min_quote_amount: u64,
+    pub min_base_amount: u64,
+}
+
+impl AddOrRemoveLiquidity<'_> {
+    pub fn handle_remove_liquidity(ctx: Context<Self>, args: RemoveLiquidityArgs) -> Result<()> {
+        let RemoveLiquidityArgs {
+            lp_tokens_to_burn,
+            min_quote_amount,
+            min_base_amount,
+        } = args;
+
+        require!(lp_tokens_to_burn > 0, AmmError::ZeroLiquidityRemove);
+
+        let accounts = ctx.accounts;
+
+        require_gte!(
+            accounts.user_lp_account.amount,
+            lp_tokens_to_burn,
+            AmmError::InsufficientBalance
+        );
+
+        let (base_amount, quote_amount, quote_fees_accrued) = {
+            let amm = &mut accounts.amm.load_mut()?;
+
+            require!(
+                matches!(
+                    amm.status,
+                    MarketStatus::Trading
+                        | MarketStatus::Finalized(_)
+                        | MarketStatus::Refunding
+                        | MarketStatus::Graduated
+                ),
+                AmmError::LiquidityNotWithdrawable
+            );
+
+            let (base_amount, quote_amount) =
+                amm.remove_liquidity(lp_tokens_to_burn, accounts.lp_mint.supply)?;
+
+            (base_amount, quote_amount, amm.quote_fees_accrued)
+        };
+
+        // the slippage check is on what the LP actually receives
+        let quote_received = get_transfer_fee(&accounts.quote_mint)?.amount_received(quote_amount);
+
+        require!(
+            base_amount >= min_base_amount && quote_received >= min_quote_amount,
+            AmmError::RemoveLiquiditySlippageExceeded
+        );
+        require_gte!(
+            accounts
+                .vault_ata_quote
+                .amount
+                .saturating_sub(quote_fees_accrued),
+            quote_amount,
+            AmmError::InsufficientVaultBalance
+        );
+
+        let amm = *accounts.amm.load()?;
+        let seeds = generate_amm_seeds!(amm);
+
+        token_interface::burn(
+            CpiContext::new(
+                accounts.lp_token_program.to_account_info(),
+                Burn {
+                    from: accounts.user_lp_account.to_account_info(),
+                    mint: accounts.lp_mint.to_account_info(),
+                    authority: accounts.user.to_account_info(),
+                },
+            ),
+            lp_tokens_to_burn,
+        )?;
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.quote_token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.vault_ata_quote.to_account_info(),
+                    mint: accounts.quote_mint.to_account_info(),
+                    to: accounts.user_quote_account.to_account_info(),
+                    authority: accounts.amm.to_account_info(),
+                },
+                &[seeds],
+            ),
+            quote_amount,
+            accounts.quote_mint.decimals,
+        )?;
+
+        send_base(
+            &amm,
+            &accounts.amm,
+            &accounts.base_mint,
+            &accounts.vault_ata_base,
+            &accounts.user_base_account,
+            &accounts.base_token_program,
+            base_amount,
+        )?;
+
+        Ok(())
+    }
+}
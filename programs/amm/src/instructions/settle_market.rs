@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AmmError;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SettleMarket<'info> {
+    pub settlement_authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = amm.load()?.settlement_authority == settlement_authority.key()
+            @ AmmError::InvalidSettlementAuthority,
+    )]
+    pub amm: AccountLoader<'info, Amm>,
+}
+
+impl SettleMarket<'_> {
+    pub fn handle(ctx: Context<Self>, new_status: MarketStatus) -> Result<()> {
+        ctx.accounts.amm.load_mut()?.settle(new_status)
+    }
+}
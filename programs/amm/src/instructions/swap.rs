@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, *};
-use anchor_spl::token_2022::Token2022;
-use raydium_cp_swap::cpi::accounts::Initialize;
-use raydium_cp_swap::program::RaydiumCpSwap;
-use crate::{AMM_SEED_PREFIX};
+use anchor_spl::token_interface::{self, *};
+use crate::generate_amm_seeds;
+use crate::instructions::{get_transfer_fee, receive_base, send_base};
+use crate::state::AMM_SEED_PREFIX;
 use crate::error::AmmError;
+use crate::events::SwapEvent;
+use crate::quote::SwapQuote;
 use crate::{Amm, SwapType};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -15,6 +15,14 @@ pub struct SwapArgs {
     pub output_amount_min: u64,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SwapExactOutputArgs {
+    pub swap_type: SwapType,
+    /// What the user receives, after any transfer fee
+    pub output_amount: u64,
+    pub input_amount_max: u64,
+}
+
 #[derive(Accounts)]
 pub struct Swap<'info> {
     #[account(mut)]
@@ -23,73 +31,48 @@ pub struct Swap<'info> {
     pub amm: AccountLoader<'info, Amm>,
     #[account(
         mut,
-        token::mint = amm.load()?.base_mint,
+        token::mint = base_mint,
         token::authority = user,
+        token::token_program = base_token_program,
     )]
-    pub user_base_account: Box<Account<'info, TokenAccount>>,
+    pub user_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
-        token::mint = amm.load()?.quote_mint,
+        token::mint = quote_mint,
         token::authority = user,
+        token::token_program = quote_token_program,
     )]
-    pub user_quote_account: Box<Account<'info, TokenAccount>>,
+    pub user_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
-        associated_token::mint = amm.load()?.base_mint,
+        associated_token::mint = base_mint,
         associated_token::authority = amm,
+        associated_token::token_program = base_token_program,
     )]
-    pub vault_ata_base: Box<Account<'info, TokenAccount>>,
+    pub vault_ata_base: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
-        associated_token::mint = amm.load()?.quote_mint,
+        associated_token::mint = quote_mint,
         associated_token::authority = amm,
+        associated_token::token_program = quote_token_program,
     )]
-    pub vault_ata_quote: Box<Account<'info, TokenAccount>>,
-    pub token_program: Program<'info, Token>,
-    pub token_2022_program: Program<'info, Token2022>,
+    pub vault_ata_quote: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         constraint = base_mint.key() == amm.load()?.base_mint,
+        mint::token_program = base_token_program,
     )]
-    pub base_mint: Box<Account<'info, Mint>>,
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
-        mut,
         constraint = quote_mint.key() == amm.load()?.quote_mint,
+        mint::token_program = quote_token_program,
     )]
-    pub quote_mint: Box<Account<'info, Mint>>,
-    pub raydium_cp_swap_program: Program<'info, RaydiumCpSwap>,
-    ///  CHECK: verified via cpi into token ray
-    pub amm_config: AccountInfo<'info>,
-    /// CHECK: verified via cpi into token ray
-    pub authority: AccountInfo<'info>,
-    /// CHECK: verified via cpi into token ray
-    #[account(mut)]
-    pub pool_account: AccountInfo<'info>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-    /// CHECK: verified via cpi into token ray
-    #[account(mut)]
-    pub token_0_vault: AccountInfo<'info>,
-    /// CHECK: verified via cpi into token ray
-    #[account(mut)]
-    pub token_1_vault: AccountInfo<'info>,
-    /// CHECK: verified via cpi into token ray
-    #[account(mut)]
-    pub create_lp_account: AccountInfo<'info>,
-    /// CHECK: verified via cpi into token ray
-    #[account(mut)]
-    pub create_pool_fee: AccountInfo<'info>,
-    /// CHECK: verified via cpi into token ray
-    #[account(mut)]
-    pub observation_key: AccountInfo<'info>,
-    /// CHECK: verified via cpi into token ray
-    #[account(mut)]
-    pub lp_mint: AccountInfo<'info>,
-
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
 }
 
-impl Swap<'_> {
+impl<'info> Swap<'info> {
     pub fn handle(ctx: Context<Swap>, args: SwapArgs) -> Result<()> {
         let accounts = ctx.accounts;
         let SwapArgs {
@@ -98,189 +81,198 @@ impl Swap<'_> {
             output_amount_min,
         } = args;
 
+        accounts.check_balance(swap_type, input_amount)?;
+
+        require!(input_amount > 0, AmmError::ZeroSwapAmount);
+
+        let quote_transfer_fee = get_transfer_fee(&accounts.quote_mint)?;
+
+        let swap_quote = {
+            let amm = &mut accounts.amm.load_mut()?;
+            amm.update_twap(Clock::get()?.slot)?;
+            amm.swap(input_amount, swap_type, quote_transfer_fee)?
+        };
+        let output_amount = swap_quote.output_amount;
+
+        accounts.transfer_swapped_tokens(swap_type, input_amount, &swap_quote)?;
+
+        // sellers are paid in quote, which may charge a transfer fee on the way out
+        let output_amount_received = match swap_type {
+            SwapType::Buy => output_amount,
+            SwapType::Sell => quote_transfer_fee.amount_received(output_amount),
+        };
+
+        require_gte!(
+            output_amount_received,
+            output_amount_min,
+            AmmError::SwapSlippageExceeded
+        );
+
+        Ok(())
+    }
+
+    pub fn handle_exact_output(ctx: Context<Swap>, args: SwapExactOutputArgs) -> Result<()> {
+        let accounts = ctx.accounts;
+        let SwapExactOutputArgs {
+            swap_type,
+            output_amount,
+            input_amount_max,
+        } = args;
+
+        require!(output_amount > 0, AmmError::ZeroSwapAmount);
+
+        let quote_transfer_fee = get_transfer_fee(&accounts.quote_mint)?;
+
+        let (input_amount, swap_quote) = {
+            let amm = &mut accounts.amm.load_mut()?;
+            amm.update_twap(Clock::get()?.slot)?;
+            amm.swap_exact_output(output_amount, swap_type, quote_transfer_fee)?
+        };
+
+        require_gte!(
+            input_amount_max,
+            input_amount,
+            AmmError::SwapInputMaxExceeded
+        );
+
+        accounts.check_balance(swap_type, input_amount)?;
+
+        accounts.transfer_swapped_tokens(swap_type, input_amount, &swap_quote)
+    }
+
+    fn check_balance(&self, swap_type: SwapType, input_amount: u64) -> Result<()> {
         match swap_type {
             SwapType::Buy => require_gte!(
-                accounts.user_quote_account.amount,
+                self.user_quote_account.amount,
                 input_amount,
                 AmmError::InsufficientBalance
             ),
-            SwapType::Sell => require_gte!( 
-                accounts.user_base_account.amount,
+            SwapType::Sell => require_gte!(
+                self.user_base_account.amount,
                 input_amount,
                 AmmError::InsufficientBalance
             ),
         };
 
-        require!(input_amount > 0, AmmError::ZeroSwapAmount);
+        Ok(())
+    }
 
-        let signer_seeds = {
-            &[
-                AMM_SEED_PREFIX,
-                accounts.base_mint.to_account_info().key.as_ref(),
-                accounts.quote_mint.to_account_info().key.as_ref(),
-                &[accounts.amm.load()?.bump],
-            ]
-        };
-        let output_amount = {
-            accounts.amm.load_mut()?.swap(input_amount, swap_type)?
-        };
-        let (user_from, vault_to, vault_from, user_to) = match swap_type {
-            SwapType::Buy => (
-                accounts.user_quote_account.clone(),
-                accounts.vault_ata_quote.clone(),
-                accounts.vault_ata_base.clone(),
-                accounts.user_base_account.clone(),
-            ),
-            SwapType::Sell => (
-                accounts.user_base_account.clone(),
-                accounts.vault_ata_base.clone(),
-                accounts.vault_ata_quote.clone(),
-                accounts.user_quote_account.clone(),
-            ),
-        };
-        {
-        match swap_type {
-            SwapType::Buy => 
-            {
-                
-                if accounts.amm.load()?.v_base_reserves as i64 -  output_amount as i64 <= 0 {
-                  { 
-                    let amm = &mut accounts.amm.load_mut()?;
-                    amm.v_base_reserves = (1_000_000_000_u128 * 10_u128.pow(accounts.base_mint.decimals as u32)) as u64;
-                    amm.v_quote_reserves = (10_u128 * 10_u128.pow(accounts.quote_mint.decimals as u32)) as u64;
-                  
-                  }
-                    let mint0 = accounts.base_mint.clone().key();
-                    let mint1 = accounts.quote_mint.key();
-                    let init_amount_0 = accounts.vault_ata_base.amount;
-                    let init_amount_1 = accounts.vault_ata_quote.amount;
-                    let (mint0, mint1, init_amount_0, init_amount_1, user_token_0_account, user_token_1_account, token_0_program, token_1_program) = if mint0 > mint1 {
-                        let token_0_program = if accounts.quote_mint.to_account_info().owner == &accounts.token_program.key() {
-                            accounts.token_program.to_account_info()
-                        } else {
-                            accounts.token_2022_program.to_account_info()
-                        };
-                        let token_1_program = if accounts.base_mint.clone().to_account_info().owner == &accounts.token_program.key() {
-                            accounts.token_program.to_account_info()
-                        } else {
-                            accounts.token_2022_program.to_account_info()
-                        };
-                        (accounts.quote_mint.clone(), accounts.base_mint.clone(), init_amount_1, init_amount_0, accounts.user_quote_account.clone(), accounts.user_base_account.clone(), token_0_program, token_1_program)
-                    } else {
-                        let token_0_program = if accounts.base_mint.clone().to_account_info().owner == &accounts.token_program.key() {
-                            accounts.token_program.to_account_info()
-                        } else {
-                            accounts.token_2022_program.to_account_info()
-                        };
-                        let token_1_program = if accounts.quote_mint.to_account_info().owner == &accounts.token_program.key() {
-                            accounts.token_program.to_account_info()
-                        } else {
-                            accounts.token_2022_program.to_account_info()
-                        };
-                        (accounts.base_mint.clone(), accounts.quote_mint.clone(), init_amount_0, init_amount_1, accounts.user_base_account.clone(), accounts.user_quote_account.clone(), token_0_program, token_1_program)
-                    };
-                    raydium_cp_swap::cpi::initialize(
-                        CpiContext::new(
-                            accounts.raydium_cp_swap_program.to_account_info(),
-                            Initialize {
-                                creator: accounts.user.to_account_info(),
-                                token_program: accounts.token_program.to_account_info(),
-                                amm_config: accounts.amm_config.to_account_info(),
+    /// Takes `input_amount` from the user and pays out the swap's
+    /// `output_amount`, after `Amm::swap` or `Amm::swap_exact_output` has done
+    /// the accounting.
+    fn transfer_swapped_tokens(
+        &self,
+        swap_type: SwapType,
+        input_amount: u64,
+        swap_quote: &SwapQuote,
+    ) -> Result<()> {
+        let amm = *self.amm.load()?;
+        let signer_seeds = generate_amm_seeds!(amm);
 
-                                authority: accounts.authority.to_account_info(),
-                                pool_state: accounts.pool_account.to_account_info(),
-                                token_0_mint: mint0.to_account_info(),
-                                token_1_mint: mint1.to_account_info(),
-                                lp_mint: accounts.lp_mint.to_account_info(),
-                                creator_token_0: user_token_0_account.to_account_info(),
-                                creator_token_1: user_token_1_account.to_account_info(),
-                                creator_lp_token: accounts.create_lp_account.to_account_info(),
-                                token_0_vault: accounts.token_0_vault.to_account_info(),
-                                token_1_vault: accounts.token_1_vault.to_account_info(),
-                                create_pool_fee: accounts.create_pool_fee.to_account_info(),
-                                observation_state: accounts.observation_key.to_account_info(),
-                                token_0_program: token_0_program,
-                                token_1_program: token_1_program,
-                                associated_token_program: accounts.associated_token_program.to_account_info(),
-                                system_program: accounts.system_program.to_account_info(),
-                                rent: accounts.rent.to_account_info()
-                            },                
-                        ),
-                            init_amount_0,
-                            init_amount_1,
-                            0,
-                        
+        let SwapQuote {
+            output_amount,
+            fee_amount,
+            ..
+        } = *swap_quote;
 
-                    ).unwrap();
-                }
+        // uncollected fees belong to the fee recipient, so they can't be used
+        // to pay out sellers
+        if swap_type == SwapType::Sell {
+            require_gte!(
+                self
+                    .vault_ata_quote
+                    .amount
+                    .saturating_sub(amm.quote_fees_accrued),
+                output_amount,
+                AmmError::InsufficientVaultBalance
+            );
+        }
 
-                token::transfer(
+        match swap_type {
+            SwapType::Buy => {
+                token_interface::transfer_checked(
                     CpiContext::new(
-                        accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: user_from.to_account_info(),
-                            to: vault_to.to_account_info(),
-                            authority: accounts.user.to_account_info(),
+                        self.quote_token_program.to_account_info(),
+                        TransferChecked {
+                            from: self.user_quote_account.to_account_info(),
+                            mint: self.quote_mint.to_account_info(),
+                            to: self.vault_ata_quote.to_account_info(),
+                            authority: self.user.to_account_info(),
                         },
                     ),
                     input_amount,
+                    self.quote_mint.decimals,
                 )?;
 
-                token::mint_to(
-                    CpiContext::new_with_signer(
-                        accounts.token_program.to_account_info(),
-                        MintTo {
-                            to: user_to.to_account_info(),
-                            mint: accounts.base_mint.to_account_info(),
-                            authority: accounts.amm.to_account_info(),
-                        },
-                        &[signer_seeds]
-                    ),
+                send_base(
+                    &amm,
+                    &self.amm,
+                    &self.base_mint,
+                    &self.vault_ata_base,
+                    &self.user_base_account,
+                    &self.base_token_program,
                     output_amount,
                 )?;
-
             }
             SwapType::Sell => {
-                {
-                token::transfer(
+                token_interface::transfer_checked(
                     CpiContext::new_with_signer(
-                        accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: vault_from.to_account_info(),
-                            to: user_to.to_account_info(),
-                            authority: accounts.amm.to_account_info(),
+                        self.quote_token_program.to_account_info(),
+                        TransferChecked {
+                            from: self.vault_ata_quote.to_account_info(),
+                            mint: self.quote_mint.to_account_info(),
+                            to: self.user_quote_account.to_account_info(),
+                            authority: self.amm.to_account_info(),
                         },
                         &[signer_seeds]
-                                   ),
-                    output_amount,
-                )?;
-
-                token::burn(
-                    CpiContext::new(
-                        accounts.token_program.to_account_info(),
-                        Burn {
-                            from: user_from.to_account_info(),
-                            mint: accounts.base_mint.to_account_info(),
-                            authority: accounts.user.to_account_info(),
-                        },
-                        
-                        
                     ),
-                    input_amount,
+                    output_amount,
+                    self.quote_mint.decimals,
                 )?;
 
+                // the fee is kept in the vault until it's collected, the rest
+                // goes back into the curve
+                if fee_amount > 0 {
+                    token_interface::transfer_checked(
+                        CpiContext::new(
+                            self.base_token_program.to_account_info(),
+                            TransferChecked {
+                                from: self.user_base_account.to_account_info(),
+                                mint: self.base_mint.to_account_info(),
+                                to: self.vault_ata_base.to_account_info(),
+                                authority: self.user.to_account_info(),
+                            },
+                        ),
+                        fee_amount,
+                        self.base_mint.decimals,
+                    )?;
+                }
 
+                receive_base(
+                    &amm,
+                    &self.base_mint,
+                    &self.vault_ata_base,
+                    &self.user_base_account,
+                    self.user.to_account_info(),
+                    &self.base_token_program,
+                    input_amount - fee_amount,
+                )?;
             }
         };
-    }
 
-        require_gte!(
+        emit!(SwapEvent {
+            user: self.user.key(),
+            amm: self.amm.key(),
+            swap_type,
+            input_amount,
             output_amount,
-            output_amount_min,
-            AmmError::SwapSlippageExceeded
-        );
+            fee_amount,
+            v_base_reserves: amm.v_base_reserves,
+            v_quote_reserves: amm.v_quote_reserves,
+            price: amm.spot_price().unwrap_or(0),
+            slot: Clock::get()?.slot,
+        });
 
         Ok(())
     }
 }
-
@@ -16,7 +16,9 @@ security_txt! {
 }
 
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod quote;
 pub mod state;
 
 use crate::instructions::*;
@@ -30,11 +32,47 @@ pub mod amm {
     use super::*;
 
     #[access_control(ctx.accounts.validate())]
-    pub fn create_amm(ctx: Context<CreateAmm>, pof: String, uri: String, proposal_number: u16, symbol: String, a: u8) -> Result<()> {
-        CreateAmm::handle(ctx, pof, uri, proposal_number, symbol, a)
+    pub fn create_amm(ctx: Context<CreateAmm>, args: CreateAmmArgs) -> Result<()> {
+        CreateAmm::handle(ctx, args)
     }
 
     pub fn swap(ctx: Context<Swap>, args: SwapArgs) -> Result<()> {
         Swap::handle(ctx, args)
     }
+
+    pub fn swap_exact_output(ctx: Context<Swap>, args: SwapExactOutputArgs) -> Result<()> {
+        Swap::handle_exact_output(ctx, args)
+    }
+
+    pub fn add_liquidity(ctx: Context<AddOrRemoveLiquidity>, args: AddLiquidityArgs) -> Result<()> {
+        AddOrRemoveLiquidity::handle_add_liquidity(ctx, args)
+    }
+
+    pub fn remove_liquidity(
+        ctx: Context<AddOrRemoveLiquidity>,
+        args: RemoveLiquidityArgs,
+    ) -> Result<()> {
+        AddOrRemoveLiquidity::handle_remove_liquidity(ctx, args)
+    }
+
+    pub fn graduate<'info>(ctx: Context<'_, '_, 'info, 'info, Graduate<'info>>) -> Result<()> {
+        Graduate::handle(ctx)
+    }
+
+    pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+        CollectFees::handle(ctx)
+    }
+
+    pub fn settle_market(ctx: Context<SettleMarket>, new_status: MarketStatus) -> Result<()> {
+        SettleMarket::handle(ctx, new_status)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
+        CloseMarket::handle(ctx)
+    }
+
+    pub fn crank_that_twap(ctx: Context<CrankThatTwap>) -> Result<()> {
+        CrankThatTwap::handle(ctx)
+    }
 }
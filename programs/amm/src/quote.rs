@@ -0,0 +1,572 @@
+//! Pure swap math, shared by the on-chain `Amm::swap` and anyone who wants to
+//! simulate a swap off-chain, e.g. against `Amm::pool` of a deserialized
+//! `Amm`. This module only depends on `core`, not on the rest of the crate or
+//! on anchor, so the same numbers come out on and off chain.
+use core::cmp;
+
+pub const MAX_BPS: u16 = 10_000;
+
+/// Which way a swap goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    /// Quote in, base out
+    Buy,
+    /// Base in, quote out
+    Sell,
+}
+
+/// What a market's status means for swapping through it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// Buys and sells at the curve price
+    Trading,
+    /// Sells only, at the curve price
+    Refunding,
+    /// Sells only, at the curve price plus `premium_bps`
+    Winning { premium_bps: u16 },
+    /// Sells only, at the curve price minus `haircut_bps`
+    Losing { haircut_bps: u16 },
+    /// No swaps, the liquidity lives in another pool
+    Graduated,
+    /// No swaps
+    Closed,
+}
+
+/// Everything about a market that the swap math needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pool {
+    pub v_base_reserves: u64,
+    pub v_quote_reserves: u64,
+    /// The reserves the curve started at, which set the price floor, or 0 for
+    /// a plain constant-product pool
+    pub initial_v_base_reserves: u64,
+    pub initial_v_quote_reserves: u64,
+    pub fee_bps: u16,
+    pub phase: Phase,
+}
+
+/// Why a swap can't be quoted. Converts into the matching `AmmError`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuoteError {
+    BuyDisabled,
+    SellDisabled,
+    MarketClosed,
+    InputAmountOverflow,
+    CastingOverflow,
+    InsufficientReserves,
+    OutputAmountUnreachable,
+    BelowInitialPrice,
+}
+
+/// A Token-2022 transfer fee, as configured for the current epoch. Mints
+/// without the extension, including all legacy mints, have no fee.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferFee {
+    pub transfer_fee_bps: u16,
+    pub maximum_fee: u64,
+}
+
+impl TransferFee {
+    /// The fee withheld when transferring `amount`, rounded up like Token-2022
+    /// does.
+    pub fn calculate(&self, amount: u64) -> u64 {
+        if self.transfer_fee_bps == 0 || amount == 0 {
+            return 0;
+        }
+
+        let fee = (amount as u128 * self.transfer_fee_bps as u128).div_ceil(MAX_BPS as u128);
+
+        cmp::min(fee, self.maximum_fee as u128) as u64
+    }
+
+    /// What arrives on the other side of a transfer of `amount`.
+    pub fn amount_received(&self, amount: u64) -> u64 {
+        amount - self.calculate(amount)
+    }
+
+    /// The smallest transfer that gets at least `amount_received` to the
+    /// other side.
+    pub fn amount_to_send(&self, amount_received: u64) -> Result<u64, QuoteError> {
+        if self.transfer_fee_bps == 0 || amount_received == 0 {
+            return Ok(amount_received);
+        }
+
+        // either the fee is capped at `maximum_fee`, or it's proportional
+        let capped = amount_received as u128 + self.maximum_fee as u128;
+        let amount = if self.transfer_fee_bps < MAX_BPS {
+            let proportional = (amount_received as u128 * MAX_BPS as u128)
+                .div_ceil((MAX_BPS - self.transfer_fee_bps) as u128);
+
+            cmp::min(capped, proportional)
+        } else {
+            capped
+        };
+
+        amount.try_into().map_err(|_| QuoteError::CastingOverflow)
+    }
+}
+
+/// The outcome of swapping some input through a `Pool`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// What the vault pays out, before any quote transfer fee on the way to
+    /// the trader
+    pub output_amount: u64,
+    /// The swap fee, in the input token
+    pub fee_amount: u64,
+    /// The part of the input that goes into the curve, after the quote
+    /// transfer fee on buys and the swap fee
+    pub input_amount_after_fee: u64,
+    /// How much worse the curve's execution price is than the spot price
+    /// before the trade, fees and settlement aside
+    pub price_impact_bps: u64,
+    pub v_base_reserves_after: u64,
+    pub v_quote_reserves_after: u64,
+}
+
+/// Whether `pool`'s phase allows a `side` swap.
+pub fn check_swap_allowed(pool: &Pool, side: Side) -> Result<(), QuoteError> {
+    match (pool.phase, side) {
+        (Phase::Trading, _)
+        | (Phase::Winning { .. } | Phase::Losing { .. } | Phase::Refunding, Side::Sell) => Ok(()),
+        (Phase::Winning { .. } | Phase::Losing { .. } | Phase::Refunding, Side::Buy) => {
+            Err(QuoteError::BuyDisabled)
+        }
+        // the liquidity now lives in the pool the market graduated to
+        (Phase::Graduated, Side::Buy) => Err(QuoteError::BuyDisabled),
+        (Phase::Graduated, Side::Sell) => Err(QuoteError::SellDisabled),
+        (Phase::Closed, _) => Err(QuoteError::MarketClosed),
+    }
+}
+
+/// The part of `input_amount` that is taken as a fee, rounded up.
+pub fn calculate_fee(fee_bps: u16, input_amount: u64) -> Result<u64, QuoteError> {
+    let fee = (input_amount as u128 * fee_bps as u128).div_ceil(MAX_BPS as u128);
+
+    fee.try_into().map_err(|_| QuoteError::CastingOverflow)
+}
+
+/// Quotes swapping `input_amount` through `pool`.
+///
+/// Quote that's bought with only reaches the vault after
+/// `quote_transfer_fee`, so only that part is swapped. Base mints can't have
+/// a transfer fee, so all of the sold base is swapped.
+pub fn quote_swap(
+    pool: &Pool,
+    input_amount: u64,
+    side: Side,
+    quote_transfer_fee: TransferFee,
+) -> Result<SwapQuote, QuoteError> {
+    check_swap_allowed(pool, side)?;
+
+    let (input_reserve, output_reserve) = match side {
+        Side::Buy => (pool.v_quote_reserves as u128, pool.v_base_reserves as u128),
+        Side::Sell => (pool.v_base_reserves as u128, pool.v_quote_reserves as u128),
+    };
+
+    let input_amount = match side {
+        Side::Buy => quote_transfer_fee.amount_received(input_amount),
+        Side::Sell => input_amount,
+    };
+
+    let fee_amount = calculate_fee(pool.fee_bps, input_amount)?;
+    let input_amount_after_fee = input_amount - fee_amount;
+
+    let numerator = (input_amount_after_fee as u128)
+        .checked_mul(output_reserve)
+        .ok_or(QuoteError::InputAmountOverflow)?;
+
+    // only zero when both are, e.g. when an empty pool's fee takes all of a
+    // tiny input
+    let denominator = input_reserve
+        .checked_add(input_amount_after_fee as u128)
+        .filter(|denominator| *denominator != 0)
+        .ok_or(QuoteError::InsufficientReserves)?;
+
+    let curve_output: u64 = (numerator / denominator)
+        .try_into()
+        .map_err(|_| QuoteError::CastingOverflow)?;
+
+    // sells of a finalized market settle at a premium or a haircut to the
+    // curve, always rounding down against the seller
+    let output_amount = match pool.phase {
+        Phase::Winning { premium_bps } => {
+            scale_by_bps(curve_output, MAX_BPS as u128 + premium_bps as u128)?
+        }
+        Phase::Losing { haircut_bps } => {
+            scale_by_bps(curve_output, (MAX_BPS - haircut_bps) as u128)?
+        }
+        _ => curve_output,
+    };
+
+    // on a constant product curve, execution price / spot price comes down to
+    // `input_reserve / (input_reserve + input)` for sells and
+    // `(input_reserve + input) / input_reserve` for buys
+    let price_impact_bps = match side {
+        Side::Buy => (input_amount_after_fee as u128 * MAX_BPS as u128)
+            .checked_div(input_reserve)
+            .unwrap_or(u64::MAX as u128),
+        Side::Sell => (input_amount_after_fee as u128 * MAX_BPS as u128)
+            .checked_div(denominator)
+            .unwrap_or(0),
+    };
+    let price_impact_bps = cmp::min(price_impact_bps, u64::MAX as u128) as u64;
+
+    let (v_base_reserves_after, v_quote_reserves_after) = match side {
+        Side::Buy => (
+            pool.v_base_reserves
+                .checked_sub(output_amount)
+                .ok_or(QuoteError::InsufficientReserves)?,
+            pool.v_quote_reserves
+                .checked_add(input_amount_after_fee)
+                .ok_or(QuoteError::InputAmountOverflow)?,
+        ),
+        Side::Sell => (
+            pool.v_base_reserves
+                .checked_add(input_amount_after_fee)
+                .ok_or(QuoteError::InputAmountOverflow)?,
+            pool.v_quote_reserves
+                .checked_sub(output_amount)
+                .ok_or(QuoteError::InsufficientReserves)?,
+        ),
+    };
+
+    if side == Side::Sell {
+        check_price_floor(
+            pool,
+            v_base_reserves_after as u128,
+            v_quote_reserves_after as u128,
+        )?;
+    }
+
+    Ok(SwapQuote {
+        output_amount,
+        fee_amount,
+        input_amount_after_fee,
+        price_impact_bps,
+        v_base_reserves_after,
+        v_quote_reserves_after,
+    })
+}
+
+/// Returns the smallest input that gets the trader `output_amount`, after the
+/// swap fee, any settlement premium or haircut and `quote_transfer_fee`.
+/// Every step rounds up, against the trader.
+pub fn quote_exact_output(
+    pool: &Pool,
+    output_amount: u64,
+    side: Side,
+    quote_transfer_fee: TransferFee,
+) -> Result<u64, QuoteError> {
+    check_swap_allowed(pool, side)?;
+
+    let (input_reserve, output_reserve) = match side {
+        Side::Buy => (pool.v_quote_reserves as u128, pool.v_base_reserves as u128),
+        Side::Sell => (pool.v_base_reserves as u128, pool.v_quote_reserves as u128),
+    };
+
+    let vault_output = match side {
+        Side::Buy => output_amount,
+        Side::Sell => quote_transfer_fee.amount_to_send(output_amount)?,
+    };
+
+    let curve_output = match pool.phase {
+        Phase::Winning { premium_bps } => {
+            unscale_by_bps(vault_output, MAX_BPS as u128 + premium_bps as u128)?
+        }
+        Phase::Losing { haircut_bps } => {
+            unscale_by_bps(vault_output, (MAX_BPS - haircut_bps) as u128)?
+        }
+        _ => vault_output as u128,
+    };
+
+    if curve_output >= output_reserve {
+        return Err(QuoteError::InsufficientReserves);
+    }
+
+    // the inverse of `output = input * output_reserve / (input_reserve + input)`
+    let input_after_fee = curve_output
+        .checked_mul(input_reserve)
+        .ok_or(QuoteError::InputAmountOverflow)?
+        .div_ceil(output_reserve - curve_output);
+
+    // `calculate_fee` rounds up, so this is the smallest input that leaves
+    // `input_after_fee` after the fee
+    let input_amount: u64 = input_after_fee
+        .checked_mul(MAX_BPS as u128)
+        .ok_or(QuoteError::InputAmountOverflow)?
+        .div_ceil((MAX_BPS - pool.fee_bps) as u128)
+        .try_into()
+        .map_err(|_| QuoteError::CastingOverflow)?;
+
+    match side {
+        Side::Buy => quote_transfer_fee.amount_to_send(input_amount),
+        Side::Sell => {
+            check_price_floor(
+                pool,
+                pool.v_base_reserves as u128 + input_after_fee,
+                (pool.v_quote_reserves as u128)
+                    .checked_sub(vault_output as u128)
+                    .ok_or(QuoteError::InsufficientReserves)?,
+            )?;
+
+            Ok(input_amount)
+        }
+    }
+}
+
+/// Checks that a sell leaves the price at or above the one that the curve
+/// started at.
+///
+/// The virtual reserves act like an LP that can never withdraw. Its quote
+/// only stays fully backed by what traders paid in while the price is at or
+/// above the initial price, so that's as low as sells can go. Above it, every
+/// real LP can withdraw its share and every sell can be paid out of the vault.
+/// Markets without virtual reserves are plain constant-product pools.
+fn check_price_floor(
+    pool: &Pool,
+    v_base_reserves_after: u128,
+    v_quote_reserves_after: u128,
+) -> Result<(), QuoteError> {
+    if pool.initial_v_quote_reserves == 0 {
+        return Ok(());
+    }
+
+    // v_quote / v_base >= initial_v_quote / initial_v_base
+    let quote_side = v_quote_reserves_after
+        .checked_mul(pool.initial_v_base_reserves as u128)
+        .ok_or(QuoteError::InputAmountOverflow)?;
+    let base_side = v_base_reserves_after
+        .checked_mul(pool.initial_v_quote_reserves as u128)
+        .ok_or(QuoteError::InputAmountOverflow)?;
+
+    if quote_side < base_side {
+        return Err(QuoteError::BelowInitialPrice);
+    }
+
+    Ok(())
+}
+
+/// Returns `amount * bps / MAX_BPS`, rounded down.
+fn scale_by_bps(amount: u64, bps: u128) -> Result<u64, QuoteError> {
+    let scaled = (amount as u128)
+        .checked_mul(bps)
+        .ok_or(QuoteError::InputAmountOverflow)?
+        / MAX_BPS as u128;
+
+    scaled.try_into().map_err(|_| QuoteError::CastingOverflow)
+}
+
+/// Returns the smallest amount that `scale_by_bps` turns into at least
+/// `amount`.
+fn unscale_by_bps(amount: u64, bps: u128) -> Result<u128, QuoteError> {
+    if bps == 0 {
+        return Err(QuoteError::OutputAmountUnreachable);
+    }
+
+    Ok((amount as u128 * MAX_BPS as u128).div_ceil(bps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(v_base_reserves: u64, v_quote_reserves: u64, fee_bps: u16) -> Pool {
+        Pool {
+            v_base_reserves,
+            v_quote_reserves,
+            initial_v_base_reserves: 0,
+            initial_v_quote_reserves: 0,
+            fee_bps,
+            phase: Phase::Trading,
+        }
+    }
+
+    fn no_fee() -> TransferFee {
+        TransferFee::default()
+    }
+
+    #[test]
+    fn buy_and_sell_round_down_against_the_trader() {
+        let pool = pool(1_000, 1_000, 0);
+
+        // 100 * 1_000 / 1_100 = 90.9
+        let buy = quote_swap(&pool, 100, Side::Buy, no_fee()).unwrap();
+        assert_eq!(buy.output_amount, 90);
+        assert_eq!(buy.v_base_reserves_after, 910);
+        assert_eq!(buy.v_quote_reserves_after, 1_100);
+
+        let sell = quote_swap(&pool, 100, Side::Sell, no_fee()).unwrap();
+        assert_eq!(sell.output_amount, 90);
+        assert_eq!(sell.v_base_reserves_after, 1_100);
+        assert_eq!(sell.v_quote_reserves_after, 910);
+
+        for quote in [buy, sell] {
+            let k = quote.v_base_reserves_after as u128 * quote.v_quote_reserves_after as u128;
+            assert!(k >= 1_000 * 1_000);
+        }
+    }
+
+    #[test]
+    fn swap_fee_rounds_up() {
+        let pool = pool(1_000_000, 1_000_000, 100);
+
+        // 1% of 150 is 1.5
+        let quote = quote_swap(&pool, 150, Side::Buy, no_fee()).unwrap();
+        assert_eq!(quote.fee_amount, 2);
+        assert_eq!(quote.input_amount_after_fee, 148);
+
+        // any non-zero input pays at least one unit of fee
+        let quote = quote_swap(&pool, 1, Side::Sell, no_fee()).unwrap();
+        assert_eq!(quote.fee_amount, 1);
+        assert_eq!(quote.input_amount_after_fee, 0);
+        assert_eq!(quote.output_amount, 0);
+    }
+
+    #[test]
+    fn zero_fee_takes_nothing() {
+        let pool = pool(1_000_000, 1_000_000, 0);
+
+        let quote = quote_swap(&pool, 1, Side::Buy, no_fee()).unwrap();
+        assert_eq!(quote.fee_amount, 0);
+        assert_eq!(quote.input_amount_after_fee, 1);
+    }
+
+    #[test]
+    fn empty_pool_that_takes_the_whole_input_as_fee_is_an_error() {
+        let pool = pool(0, 0, 100);
+
+        assert_eq!(
+            quote_swap(&pool, 1, Side::Buy, no_fee()),
+            Err(QuoteError::InsufficientReserves)
+        );
+    }
+
+    #[test]
+    fn buys_only_swap_what_reaches_the_vault() {
+        let pool = pool(1_000_000, 1_000_000, 0);
+        let transfer_fee = TransferFee {
+            transfer_fee_bps: 100,
+            maximum_fee: 5,
+        };
+
+        // 1% of 100 is 1, under the cap
+        let quote = quote_swap(&pool, 100, Side::Buy, transfer_fee).unwrap();
+        assert_eq!(quote.input_amount_after_fee, 99);
+
+        // 1% of 10_000 is 100, capped at 5
+        let quote = quote_swap(&pool, 10_000, Side::Buy, transfer_fee).unwrap();
+        assert_eq!(quote.input_amount_after_fee, 9_995);
+
+        // sells aren't charged a quote transfer fee on the way in
+        let quote = quote_swap(&pool, 10_000, Side::Sell, transfer_fee).unwrap();
+        assert_eq!(quote.input_amount_after_fee, 10_000);
+    }
+
+    #[test]
+    fn finalized_sells_settle_at_a_premium_or_haircut() {
+        let mut pool = pool(1_000, 1_000, 0);
+
+        // the curve pays 90
+        pool.phase = Phase::Winning { premium_bps: 1_000 };
+        let quote = quote_swap(&pool, 100, Side::Sell, no_fee()).unwrap();
+        assert_eq!(quote.output_amount, 99);
+        assert_eq!(quote.v_quote_reserves_after, 901);
+
+        pool.phase = Phase::Losing { haircut_bps: 1_000 };
+        let quote = quote_swap(&pool, 100, Side::Sell, no_fee()).unwrap();
+        assert_eq!(quote.output_amount, 81);
+        assert_eq!(quote.v_quote_reserves_after, 919);
+
+        assert_eq!(
+            quote_swap(&pool, 100, Side::Buy, no_fee()),
+            Err(QuoteError::BuyDisabled)
+        );
+    }
+
+    #[test]
+    fn exact_output_is_the_smallest_sufficient_input() {
+        let transfer_fees = [
+            no_fee(),
+            TransferFee {
+                transfer_fee_bps: 250,
+                maximum_fee: 1_000,
+            },
+            TransferFee {
+                transfer_fee_bps: 250,
+                maximum_fee: 3,
+            },
+        ];
+        let phases = [
+            Phase::Trading,
+            Phase::Refunding,
+            Phase::Winning { premium_bps: 500 },
+            Phase::Losing { haircut_bps: 700 },
+        ];
+
+        for fee_bps in [0, 30, 1_000] {
+            for transfer_fee in transfer_fees {
+                for phase in phases {
+                    let mut pool = pool(1_000_000_000, 3_000_000_000, fee_bps);
+                    pool.phase = phase;
+
+                    let sides: &[Side] = match phase {
+                        Phase::Trading => &[Side::Buy, Side::Sell],
+                        _ => &[Side::Sell],
+                    };
+
+                    for &side in sides {
+                        for output_amount in [1, 7, 12_345, 50_000_000] {
+                            let input_amount =
+                                quote_exact_output(&pool, output_amount, side, transfer_fee)
+                                    .unwrap();
+
+                            let received = |input_amount| {
+                                let output_amount =
+                                    quote_swap(&pool, input_amount, side, transfer_fee)
+                                        .unwrap()
+                                        .output_amount;
+
+                                match side {
+                                    Side::Buy => output_amount,
+                                    Side::Sell => transfer_fee.amount_received(output_amount),
+                                }
+                            };
+
+                            assert!(received(input_amount) >= output_amount);
+                            assert!(received(input_amount - 1) < output_amount);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn exact_output_rejects_unreachable_outputs() {
+        let mut pool = pool(1_000, 1_000, 0);
+
+        assert_eq!(
+            quote_exact_output(&pool, 1_000, Side::Buy, no_fee()),
+            Err(QuoteError::InsufficientReserves)
+        );
+
+        pool.phase = Phase::Losing {
+            haircut_bps: MAX_BPS,
+        };
+        assert_eq!(
+            quote_exact_output(&pool, 1, Side::Sell, no_fee()),
+            Err(QuoteError::OutputAmountUnreachable)
+        );
+    }
+
+    #[test]
+    fn exact_output_overflow_is_an_error() {
+        let pool = pool(u64::MAX, u64::MAX, 1_000);
+
+        assert_eq!(
+            quote_exact_output(&pool, u64::MAX - 1, Side::Buy, no_fee()),
+            Err(QuoteError::InputAmountOverflow)
+        );
+    }
+}
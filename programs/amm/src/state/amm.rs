@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 
 use crate::error::AmmError;
+use crate::quote::{self, SwapQuote, TransferFee};
+use crate::state::{MAX_PRICE, ONE_MINUTE_IN_SLOTS, PRICE_SCALE};
 
 #[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
 pub enum SwapType {
@@ -10,14 +12,153 @@ pub enum SwapType {
     Sell,
 }
 
+impl From<SwapType> for quote::Side {
+    fn from(swap_type: SwapType) -> Self {
+        match swap_type {
+            SwapType::Buy => quote::Side::Buy,
+            SwapType::Sell => quote::Side::Sell,
+        }
+    }
+}
+
+/// Which side of the proposal a market ended up on.
+#[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MarketOutcome {
+    /// The market's side of the proposal happened, e.g. the pass market of a
+    /// proposal that passed
+    Winning,
+    /// The market's side of the proposal didn't happen
+    Losing,
+}
+
+/// The lifecycle of a market. The only legal transitions are:
+/// - Trading -> Finalized
+/// - Trading -> Refunding
+/// - Finalized(Winning) -> Graduated
+/// - Finalized(Winning) -> Closed, if there's no virtual liquidity to graduate
+/// - Finalized(Losing) -> Closed
+/// - Graduated -> Closed
+/// - Refunding -> Closed
+///
+/// `repr(u8)` pins the layout so that it can live inside the zero-copy `Amm`.
+/// A zeroed status is `Trading`.
+#[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MarketStatus {
+    /// Anyone can buy and sell
+    Trading,
+    /// The proposal has been decided, so buys are disabled and sells settle
+    Finalized(MarketOutcome),
+    /// The virtual liquidity's share of the market has been migrated to
+    /// another pool. LPs can still withdraw theirs.
+    Graduated,
+    /// The market is done and can't be traded
+    Closed,
+    /// The proposal was cancelled, so buys are disabled and sells and
+    /// withdrawals settle at the plain curve price
+    Refunding,
+}
+
+/// Where a market's liquidity goes when it graduates. Each target has its own
+/// set of accounts that `graduate` expects after the common ones.
+#[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+#[repr(u8)]
+pub enum GraduationTarget {
+    /// Seed a Raydium CP-swap pool at the curve price and burn the LP tokens
+    /// it hands back
+    RaydiumCpSwap,
+    /// Send the liquidity to the token accounts of this owner, e.g. a DAO
+    /// treasury that seeds another constant-product pool or an order book
+    Recipient(Pubkey),
+}
+
+/// Where the base that a market sells comes from.
+#[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+#[repr(u8)]
+pub enum BaseSupply {
+    /// The amm is the base mint's authority. It mints the base it sells and
+    /// burns the base it buys back, so its base reserves can be virtual.
+    Minted,
+    /// The base mint belongs to someone else, e.g. it's one of a conditional
+    /// vault's mints. Base moves in and out of the amm's vault like quote
+    /// does, so there are no virtual reserves and LPs seed the price.
+    Deposited,
+}
+
+#[zero_copy(unsafe)]
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct TwapOracle {
+    pub last_updated_slot: u64,
+    /// A price is the number of quote units per base unit multiplied by 1e12.
+    /// You cannot simply divide by 1e12 to get a price you can display in the UI
+    /// because the base and quote decimals may be different. Instead, do:
+    /// ui_price = (price * (10**(base_decimals - quote_decimals))) / 1e12
+    pub last_price: u128,
+    /// If we did a raw TWAP over prices, someone could push the TWAP heavily with
+    /// a few extremely large outliers. So we use observations, which can only move
+    /// by `max_observation_change_per_update` per update.
+    pub last_observation: u128,
+    /// Running sum of slots_per_last_update * last_observation.
+    ///
+    /// Assuming latest observations are as big as possible (u64::MAX * 1e12),
+    /// we can store (2**128 / (2**64 * 1e12)) / (60 * 60 * 24 * 365 * 2.5) = 589
+    /// years of slots before this value overflows. It's wrapping anyway, so
+    /// consumers should only ever look at the difference between two aggregators.
+    pub aggregator: u128,
+    /// The most that an observation can change per update.
+    pub max_observation_change_per_update: u128,
+    /// What the initial `last_observation` is set to.
+    pub initial_observation: u128,
+}
+
+impl TwapOracle {
+    pub fn new(
+        current_slot: u64,
+        initial_observation: u128,
+        max_observation_change_per_update: u128,
+    ) -> Self {
+        Self {
+            last_updated_slot: current_slot,
+            last_price: 0,
+            last_observation: initial_observation,
+            aggregator: 0,
+            max_observation_change_per_update,
+            initial_observation,
+        }
+    }
+}
+
 #[account(zero_copy(unsafe))]
 pub struct Amm {
     pub bump: u8,
 
     pub created_at_slot: u64,
+    /// Who created the market. It's part of the amm's address, so nobody can
+    /// squat on a mint pair that someone else needs a market for.
+    pub creator: Pubkey,
+
+    /// The futarchy proposal this market trades, and its number. Autocrat
+    /// only accepts markets created for the proposal being initialized.
+    pub proposal: Pubkey,
+    pub proposal_number: u32,
+    /// The only account that can finalize the market or switch it to
+    /// refund-only mode, through `settle_market`. For proposals, this is the
+    /// DAO's treasury.
+    pub settlement_authority: Pubkey,
 
     pub base_mint: Pubkey,
     pub quote_mint: Pubkey,
+    pub base_supply: BaseSupply,
+    /// PDA of `[AMM_LP_MINT_SEED_PREFIX, amm]`, owned by the amm
+    pub lp_mint: Pubkey,
+    /// LP tokens that represent the virtual reserves the curve starts with.
+    /// They are never minted, so nobody can withdraw that liquidity, but they
+    /// count towards the total LP supply. Real LPs own what they deposited
+    /// plus their share of what traders paid in or took out since, and sells
+    /// can't take the price below the initial price, which keeps the virtual
+    /// share of the quote backed by what traders paid in.
+    pub virtual_lp_supply: u64,
 
     pub base_mint_decimals: u8,
     pub quote_mint_decimals: u8,
@@ -25,112 +166,385 @@ pub struct Amm {
     pub v_quote_reserves: u64,
     pub v_base_reserves: u64,
 
-    pub vault_status: u8,
+    /// The virtual reserves the curve starts with, which also set the lowest
+    /// price that sells can reach
+    pub initial_v_quote_reserves: u64,
+    pub initial_v_base_reserves: u64,
+    /// The market can graduate once `v_quote_reserves` reach this amount
+    pub graduation_threshold: u64,
+    pub graduation_target: GraduationTarget,
+    /// The pool or recipient that `graduate` migrated the liquidity to, or the
+    /// default pubkey if the market hasn't graduated
+    pub graduated_pool: Pubkey,
+
+    pub status: MarketStatus,
+
+    pub oracle: TwapOracle,
+
+    /// The swap fee, taken out of the input amount
+    pub fee_bps: u16,
+    /// Who `collect_fees` pays out to
+    pub fee_recipient: Pubkey,
+    /// Fees that have been charged but not yet collected. They sit in the
+    /// vaults but are not part of the reserves.
+    pub base_fees_accrued: u64,
+    pub quote_fees_accrued: u64,
+
+    /// How much more than the curve price sellers of a winning market get
+    pub winner_premium_bps: u16,
+    /// How much less than the curve price sellers of a losing market get
+    pub loser_haircut_bps: u16,
 }
 
 impl Amm {
-    pub fn buy_quote(&self, amount: u128) -> u64 {
-       
-        let v_quote_reserves = self.v_quote_reserves as u128;
-        let v_base_reserves = self.v_base_reserves as u128;
-        let cost: u64 =
-            ((amount * v_quote_reserves) / (v_base_reserves - amount)) as u64;
+    /// Returns the time-weighted average observation since market creation.
+    pub fn get_twap(&self) -> Result<u128> {
+        let oracle = self.oracle;
+        let slots_passed = (oracle.last_updated_slot - self.created_at_slot) as u128;
 
-        return cost + 1; // always round up
+        require_neq!(slots_passed, 0, AmmError::NoSlotsPassed);
+
+        Ok(oracle.aggregator / slots_passed)
     }
 
-    pub fn sell_quote(&self, amount: u128) -> u64 {
-        let v_quote_reserves = self.v_quote_reserves as u128;
-        let v_base_reserves = self.v_base_reserves as u128;
-        let output: u64 =
-            ((amount * v_base_reserves) / (v_quote_reserves + amount)) as u64; // Corrected formula to use base reserves in numerator
+    /// Quote units per base unit scaled by `PRICE_SCALE`, capped at
+    /// `MAX_PRICE`. `None` if either reserve is empty.
+    pub fn spot_price(&self) -> Option<u128> {
+        if self.v_base_reserves == 0 || self.v_quote_reserves == 0 {
+            return None;
+        }
+
+        let price = self.v_quote_reserves as u128 * PRICE_SCALE / self.v_base_reserves as u128;
 
-        return output;
+        Some(std::cmp::min(price, MAX_PRICE))
     }
 
-    pub fn calculate_price(&self) -> Result<u128> {
-        let v_quote_reserves = self.v_quote_reserves as u128;
+    /// Records a new observation if at least a minute has passed since the last
+    /// one. Returns the spot price that was observed, if any.
+    pub fn update_twap(&mut self, current_slot: u64) -> Result<Option<u128>> {
+        let oracle = self.oracle;
+
+        // observations are rate-limited so that a manipulator has to hold the
+        // price for a while to move the TWAP
+        if current_slot < oracle.last_updated_slot + ONE_MINUTE_IN_SLOTS {
+            return Ok(None);
+        }
+
+        let Some(price) = self.spot_price() else {
+            return Ok(None);
+        };
+
+        let last_observation = oracle.last_observation;
+
+        let new_observation = if price > last_observation {
+            let max_observation =
+                last_observation.saturating_add(oracle.max_observation_change_per_update);
+
+            std::cmp::min(price, max_observation)
+        } else {
+            let min_observation =
+                last_observation.saturating_sub(oracle.max_observation_change_per_update);
+
+            std::cmp::max(price, min_observation)
+        };
+
+        let slot_difference = (current_slot - oracle.last_updated_slot) as u128;
+
+        // if this wraps, consumers who diff two aggregators still get the right
+        // answer, so we just can't panic
+        let weighted_observation = new_observation.wrapping_mul(slot_difference);
+        let new_aggregator = oracle.aggregator.wrapping_add(weighted_observation);
+
+        self.oracle = TwapOracle {
+            last_updated_slot: current_slot,
+            last_price: price,
+            last_observation: new_observation,
+            aggregator: new_aggregator,
+            ..oracle
+        };
+
+        Ok(Some(price))
+    }
+
+    /// Moves the market to `new_status`, failing with a transition-specific
+    /// error if the move isn't legal.
+    pub fn set_status(&mut self, new_status: MarketStatus) -> Result<()> {
+        use MarketOutcome::*;
+        use MarketStatus::*;
+
+        match (self.status, new_status) {
+            (Trading, Finalized(_) | Refunding)
+            | (Finalized(Winning), Graduated)
+            | (Finalized(Losing), Closed)
+            | (Graduated, Closed)
+            | (Refunding, Closed) => {}
+            // a market without virtual reserves belongs entirely to its LPs,
+            // so there's nothing for graduation to migrate
+            (Finalized(Winning), Closed) if self.virtual_lp_supply == 0 => {}
+            (_, Trading) => return err!(AmmError::MarketCannotResumeTrading),
+            (Closed, _) => return err!(AmmError::MarketClosed),
+            (Refunding, _) => return err!(AmmError::MarketRefunding),
+            (_, Refunding) => return err!(AmmError::MarketCannotRefund),
+            (_, Finalized(_)) => return err!(AmmError::MarketAlreadyFinalized),
+            (Trading, Graduated | Closed) => return err!(AmmError::MarketNotFinalized),
+            (Finalized(Losing), Graduated) => return err!(AmmError::LosingMarketCannotGraduate),
+            (Finalized(Winning), Closed) => return err!(AmmError::MarketNotGraduated),
+            (Graduated, Graduated) => return err!(AmmError::MarketAlreadyGraduated),
+        }
+
+        self.status = new_status;
+
+        Ok(())
+    }
+
+    /// Decides the market, or switches it to refund-only mode, on behalf of
+    /// its settlement authority. Graduating and closing are up to the amm
+    /// itself.
+    pub fn settle(&mut self, new_status: MarketStatus) -> Result<()> {
+        require!(
+            matches!(
+                new_status,
+                MarketStatus::Finalized(_) | MarketStatus::Refunding
+            ),
+            AmmError::InvalidSettlementStatus
+        );
+
+        self.set_status(new_status)
+    }
+
+    /// The part of `input_amount` that is taken as a fee, rounded up.
+    pub fn calculate_fee(&self, input_amount: u64) -> Result<u64> {
+        Ok(quote::calculate_fee(self.fee_bps, input_amount)?)
+    }
+
+    /// Returns the base and quote reserves that `lp_tokens` have a claim on,
+    /// rounded down. `lp_mint_supply` is the supply of the LP mint, which
+    /// doesn't include `virtual_lp_supply`. Because of the price floor on
+    /// sells, the vault can pay these out on top of everything the virtual
+    /// share owes sellers.
+    pub fn get_lp_token_liquidity(&self, lp_tokens: u64, lp_mint_supply: u64) -> Result<(u64, u64)> {
+        let lp_total_supply = lp_mint_supply as u128 + self.virtual_lp_supply as u128;
+
+        require_neq!(lp_total_supply, 0, AmmError::InvalidSupply);
+
+        let base = (lp_tokens as u128 * self.v_base_reserves as u128) / lp_total_supply;
+        let quote = (lp_tokens as u128 * self.v_quote_reserves as u128) / lp_total_supply;
+
+        Ok((
+            base.try_into().map_err(|_| error!(AmmError::CastingOverflow))?,
+            quote.try_into().map_err(|_| error!(AmmError::CastingOverflow))?,
+        ))
+    }
+
+    /// Adds `quote_amount` and a proportional amount of base to the reserves.
+    /// Returns the base that needs to be deposited, rounded up, and the LP
+    /// tokens to mint, rounded down. The first deposit into an empty market,
+    /// which only a `Deposited` market can be, sets the price instead, with
+    /// `max_base_amount` of base.
+    pub fn add_liquidity(
+        &mut self,
+        quote_amount: u64,
+        max_base_amount: u64,
+        lp_mint_supply: u64,
+    ) -> Result<(u64, u64)> {
         let v_base_reserves = self.v_base_reserves as u128;
-        let price = (v_quote_reserves * 100) / v_base_reserves;
-        Ok(price)
+        let v_quote_reserves = self.v_quote_reserves as u128;
+        let lp_total_supply = lp_mint_supply as u128 + self.virtual_lp_supply as u128;
+
+        let (base_amount, lp_tokens) = if v_base_reserves == 0 && v_quote_reserves == 0 {
+            require_neq!(max_base_amount, 0, AmmError::ZeroLiquidityToAdd);
+
+            (max_base_amount as u128, quote_amount as u128)
+        } else {
+            require!(
+                v_base_reserves > 0 && v_quote_reserves > 0,
+                AmmError::NoReserves
+            );
+
+            (
+                (quote_amount as u128 * v_base_reserves).div_ceil(v_quote_reserves),
+                (quote_amount as u128 * lp_total_supply) / v_quote_reserves,
+            )
+        };
+
+        let base_amount: u64 = base_amount
+            .try_into()
+            .map_err(|_| error!(AmmError::AddLiquidityCalculationError))?;
+        let lp_tokens: u64 = lp_tokens
+            .try_into()
+            .map_err(|_| error!(AmmError::AddLiquidityCalculationError))?;
+
+        self.v_base_reserves = self
+            .v_base_reserves
+            .checked_add(base_amount)
+            .ok_or(error!(AmmError::AddLiquidityCalculationError))?;
+        self.v_quote_reserves = self
+            .v_quote_reserves
+            .checked_add(quote_amount)
+            .ok_or(error!(AmmError::AddLiquidityCalculationError))?;
+
+        Ok((base_amount, lp_tokens))
+    }
+
+    /// Takes the reserves that `lp_tokens` have a claim on out of the pool and
+    /// returns them as `(base_amount, quote_amount)`.
+    pub fn remove_liquidity(&mut self, lp_tokens: u64, lp_mint_supply: u64) -> Result<(u64, u64)> {
+        let (base_amount, quote_amount) = self.get_lp_token_liquidity(lp_tokens, lp_mint_supply)?;
+
+        self.v_base_reserves -= base_amount;
+        self.v_quote_reserves -= quote_amount;
+
+        Ok((base_amount, quote_amount))
+    }
+
+    /// Scales the reserves down to the LPs' share and drops the virtual LP
+    /// supply, so that graduation can migrate the rest while LPs keep what
+    /// they own. Returns the base and quote reserves that stay behind for the
+    /// LPs, rounded up so that they can all withdraw.
+    pub fn retain_lp_liquidity(&mut self, lp_mint_supply: u64) -> Result<(u64, u64)> {
+        let lp_total_supply = lp_mint_supply as u128 + self.virtual_lp_supply as u128;
+
+        require_neq!(lp_total_supply, 0, AmmError::InvalidSupply);
+
+        let base = (lp_mint_supply as u128 * self.v_base_reserves as u128).div_ceil(lp_total_supply);
+        let quote =
+            (lp_mint_supply as u128 * self.v_quote_reserves as u128).div_ceil(lp_total_supply);
+
+        // both are at most the current reserves
+        self.v_base_reserves = base as u64;
+        self.v_quote_reserves = quote as u64;
+        self.virtual_lp_supply = 0;
+
+        Ok((self.v_base_reserves, self.v_quote_reserves))
+    }
+
+    /// Whether the curve has accumulated enough quote to graduate.
+    pub fn graduation_reached(&self) -> bool {
+        self.v_quote_reserves >= self.graduation_threshold
+    }
+
+    /// What the swap math in `quote` needs to know about this market.
+    pub fn pool(&self) -> quote::Pool {
+        let phase = match self.status {
+            MarketStatus::Trading => quote::Phase::Trading,
+            MarketStatus::Refunding => quote::Phase::Refunding,
+            MarketStatus::Finalized(MarketOutcome::Winning) => quote::Phase::Winning {
+                premium_bps: self.winner_premium_bps,
+            },
+            MarketStatus::Finalized(MarketOutcome::Losing) => quote::Phase::Losing {
+                haircut_bps: self.loser_haircut_bps,
+            },
+            MarketStatus::Graduated => quote::Phase::Graduated,
+            MarketStatus::Closed => quote::Phase::Closed,
+        };
+
+        quote::Pool {
+            v_base_reserves: self.v_base_reserves,
+            v_quote_reserves: self.v_quote_reserves,
+            initial_v_base_reserves: self.initial_v_base_reserves,
+            initial_v_quote_reserves: self.initial_v_quote_reserves,
+            fee_bps: self.fee_bps,
+            phase,
+        }
     }
 
     pub fn k(&self) -> u128 {
         self.v_base_reserves as u128 * self.v_quote_reserves as u128
     }
 
-    /// Does the internal accounting to swap `input_amount` into the returned
-    /// output amount so that output amount can be transferred to the user.
-    pub fn swap(&mut self, input_amount: u64, swap_type: SwapType) -> Result<u64> {
-        let base_amount_start = self.v_base_reserves as u128;
-        let quote_amount_start = self.v_quote_reserves as u128;
+    /// Returns the smallest input that gets the trader `output_amount`. See
+    /// `quote::quote_exact_output`.
+    pub fn quote_exact_output(
+        &self,
+        output_amount: u64,
+        swap_type: SwapType,
+        quote_transfer_fee: TransferFee,
+    ) -> Result<u64> {
+        Ok(quote::quote_exact_output(
+            &self.pool(),
+            output_amount,
+            swap_type.into(),
+            quote_transfer_fee,
+        )?)
+    }
 
-        let k = self.k();
+    /// Does the internal accounting to swap the smallest possible input into
+    /// exactly `output_amount` for the trader. Returns that input and the
+    /// swap, whose `output_amount` is what the vault has to pay out, which
+    /// includes the trader's quote transfer fee on sells. Rounding dust stays
+    /// in the reserves.
+    pub fn swap_exact_output(
+        &mut self,
+        output_amount: u64,
+        swap_type: SwapType,
+        quote_transfer_fee: TransferFee,
+    ) -> Result<(u64, SwapQuote)> {
+        let input_amount = self.quote_exact_output(output_amount, swap_type, quote_transfer_fee)?;
 
-        let (input_reserve, output_reserve) = match swap_type {
-            SwapType::Buy => (quote_amount_start, base_amount_start),
-            SwapType::Sell => (base_amount_start, quote_amount_start),
+        let vault_output = match swap_type {
+            SwapType::Buy => output_amount,
+            SwapType::Sell => quote_transfer_fee.amount_to_send(output_amount)?,
         };
 
-        let input_amount_with_fee = match swap_type {
-            SwapType::Buy => self.buy_quote(input_amount as u128) * 99,
-            SwapType::Sell => self.sell_quote(input_amount as u128) * 99,
-        } as u128;
+        let swap_quote = self.swap(input_amount, swap_type, quote_transfer_fee)?;
 
-        let numerator = input_amount_with_fee
-            .checked_mul(output_reserve)
-            .ok_or(error!(AmmError::InputAmountOverflow))?;
+        let dust = swap_quote
+            .output_amount
+            .checked_sub(vault_output)
+            .ok_or(error!(AmmError::OutputAmountUnreachable))?;
 
-        let denominator = (input_reserve * 100) + input_amount_with_fee;
+        match swap_type {
+            SwapType::Buy => self.v_base_reserves += dust,
+            SwapType::Sell => self.v_quote_reserves += dust,
+        }
 
-        let output_amount = (numerator / denominator)
-            .try_into()
-            .map_err(|_| AmmError::CastingOverflow)?;
-
-        let new_k = self.k();
-        match self.vault_status {
-            1 => {
-                if swap_type == SwapType::Buy {
-                    return Err(AmmError::BuyDisabled.into());
-                } else if swap_type == SwapType::Sell {
-                    // Boost the output amount by 10%
-                    let boosted_output_amount = (output_amount as f64 * 1.10) as u64;
-                    self.v_base_reserves += input_amount;
-                    self.v_quote_reserves -= boosted_output_amount;
-                }
-            }
-            2 => {
-                if swap_type == SwapType::Buy {
-                    return Err(AmmError::BuyDisabled.into());
-                } else if swap_type == SwapType::Sell {
-                    // Decrease the output amount inversely by 10%
-                    let decreased_output_amount = (output_amount as f64 * 0.90) as u64;
-                    self.v_base_reserves += input_amount;
-                    self.v_quote_reserves -= decreased_output_amount;
-                }
-            }
-            _ => {
-                match swap_type {
-                    SwapType::Buy => {
-                        self.v_quote_reserves += input_amount;
-                        self.v_base_reserves -= output_amount;
-                    }
-                    SwapType::Sell => {
-                        self.v_base_reserves += input_amount;
-                        self.v_quote_reserves -= output_amount;
-                    }
-                }
-            }
+        Ok((
+            input_amount,
+            SwapQuote {
+                output_amount: vault_output,
+                v_base_reserves_after: self.v_base_reserves,
+                v_quote_reserves_after: self.v_quote_reserves,
+                ..swap_quote
+            },
+        ))
+    }
+
+    /// Does the internal accounting to swap `input_amount` so that the
+    /// returned `output_amount` can be transferred to the user. The math lives
+    /// in `quote::quote_swap`, so off-chain simulations get exactly the same
+    /// result.
+    pub fn swap(
+        &mut self,
+        input_amount: u64,
+        swap_type: SwapType,
+        quote_transfer_fee: TransferFee,
+    ) -> Result<SwapQuote> {
+        let status = self.status;
+        let k = self.k();
+
+        let swap_quote = quote::quote_swap(
+            &self.pool(),
+            input_amount,
+            swap_type.into(),
+            quote_transfer_fee,
+        )?;
+
+        match swap_type {
+            SwapType::Buy => self.quote_fees_accrued += swap_quote.fee_amount,
+            SwapType::Sell => self.base_fees_accrued += swap_quote.fee_amount,
         }
 
-    msg!("Input Amount: {}", input_amount);
-    msg!("Input Amount with Fee: {}", input_amount_with_fee);
-    msg!("Numerator: {}", numerator);
-    msg!("Denominator: {}", denominator);
-    msg!("Output Amount: {}", output_amount);
-        require_gte!(new_k, k, AmmError::ConstantProductInvariantFailed);
+        self.v_base_reserves = swap_quote.v_base_reserves_after;
+        self.v_quote_reserves = swap_quote.v_quote_reserves_after;
+
+        // the winner premium is paid on top of the curve, so it's the one case
+        // where k is allowed to shrink. `Swap` checks that the vault can cover it
+        if status != MarketStatus::Finalized(MarketOutcome::Winning) {
+            let new_k = self.k();
+            require_gte!(new_k, k, AmmError::ConstantProductInvariantFailed);
+        }
 
-        Ok(output_amount)
+        Ok(swap_quote)
     }
 }
 
@@ -141,7 +555,245 @@ macro_rules! generate_amm_seeds {
             AMM_SEED_PREFIX,
             $amm.base_mint.as_ref(),
             $amm.quote_mint.as_ref(),
+            $amm.creator.as_ref(),
             &[$amm.bump],
         ]
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::quote::QuoteError;
+
+    const ONE: u64 = 1_000_000;
+
+    fn no_fee() -> TransferFee {
+        TransferFee::default()
+    }
+
+    /// A market that mints its base, with 10 virtual base and 10 virtual quote
+    fn minted_amm() -> Amm {
+        let mut amm: Amm = bytemuck::Zeroable::zeroed();
+        amm.base_supply = BaseSupply::Minted;
+        amm.v_base_reserves = 10 * ONE;
+        amm.v_quote_reserves = 10 * ONE;
+        amm.initial_v_base_reserves = 10 * ONE;
+        amm.initial_v_quote_reserves = 10 * ONE;
+        amm.virtual_lp_supply = 10 * ONE;
+        amm
+    }
+
+    #[test]
+    fn lp_withdrawals_leave_sellers_backed() {
+        let mut amm = minted_amm();
+        let mut vault_quote = 0;
+
+        // an LP matches the virtual liquidity
+        let (_, lp_tokens) = amm.add_liquidity(10 * ONE, 10 * ONE, 0).unwrap();
+        assert_eq!(lp_tokens, 10 * ONE);
+        vault_quote += 10 * ONE;
+
+        // a trader buys with 10
+        let buy = amm.swap(10 * ONE, SwapType::Buy, no_fee()).unwrap();
+        let trader_base = buy.output_amount;
+        vault_quote += 10 * ONE;
+
+        // the LP takes its half of the pool, which includes half of the
+        // trader's quote
+        let (_, quote_amount) = amm.remove_liquidity(lp_tokens, lp_tokens).unwrap();
+        assert_eq!(quote_amount, 15 * ONE);
+        vault_quote -= quote_amount;
+        assert_eq!(vault_quote, 5 * ONE);
+
+        // the curve would quote 7.5 for all of the trader's base, more than
+        // the vault holds, so the sell is rejected instead of the transfer
+        assert_eq!(
+            quote::quote_swap(&amm.pool(), trader_base, quote::Side::Sell, no_fee()),
+            Err(QuoteError::BelowInitialPrice)
+        );
+
+        // the 5 that the virtual liquidity got from the trader can be sold
+        // back, and is all there is
+        let (_, sell) = amm
+            .swap_exact_output(5 * ONE - 1, SwapType::Sell, no_fee())
+            .unwrap();
+        assert!(sell.output_amount <= vault_quote);
+        assert!(amm.v_quote_reserves >= amm.initial_v_quote_reserves);
+        assert_eq!(
+            amm.quote_exact_output(2, SwapType::Sell, no_fee()),
+            Err(AmmError::BelowInitialPrice.into())
+        );
+    }
+
+    #[test]
+    fn graduation_leaves_the_lps_share_behind() {
+        let mut amm = minted_amm();
+
+        let (_, lp_tokens) = amm.add_liquidity(10 * ONE, 10 * ONE, 0).unwrap();
+        amm.swap(30 * ONE, SwapType::Buy, no_fee()).unwrap();
+        let vault_quote = 40 * ONE;
+
+        // the LP owns half of the 50 quote reserves, and 10 of them are virtual
+        let price = amm.spot_price();
+        let (lp_base, lp_quote) = amm.retain_lp_liquidity(lp_tokens).unwrap();
+        assert_eq!(lp_quote, 25 * ONE);
+        assert_eq!(amm.spot_price(), price);
+
+        // graduation migrates the virtual half's 25, less the 10 that never
+        // existed
+        let migrated = vault_quote - lp_quote;
+        assert_eq!(migrated, 15 * ONE);
+
+        let (base_amount, quote_amount) = amm.remove_liquidity(lp_tokens, lp_tokens).unwrap();
+        assert_eq!((base_amount, quote_amount), (lp_base, lp_quote));
+    }
+
+    #[test]
+    fn only_markets_without_virtual_liquidity_close_without_graduating() {
+        let mut amm = minted_amm();
+        amm.status = MarketStatus::Finalized(MarketOutcome::Winning);
+        assert_eq!(
+            amm.set_status(MarketStatus::Closed),
+            Err(AmmError::MarketNotGraduated.into())
+        );
+
+        amm.virtual_lp_supply = 0;
+        amm.set_status(MarketStatus::Closed).unwrap();
+    }
+
+    #[test]
+    fn settlement_authorities_can_only_decide_or_refund_trading_markets() {
+        use MarketOutcome::*;
+        use MarketStatus::*;
+
+        for new_status in [Trading, Graduated, Closed] {
+            assert_eq!(
+                minted_amm().settle(new_status),
+                Err(AmmError::InvalidSettlementStatus.into())
+            );
+        }
+
+        for new_status in [Finalized(Winning), Finalized(Losing), Refunding] {
+            let mut amm = minted_amm();
+            amm.settle(new_status).unwrap();
+            assert_eq!(amm.status, new_status);
+        }
+
+        // settlement is final
+        let mut amm = minted_amm();
+        amm.settle(Finalized(Winning)).unwrap();
+        assert_eq!(
+            amm.settle(Finalized(Losing)),
+            Err(AmmError::MarketAlreadyFinalized.into())
+        );
+        assert_eq!(
+            amm.settle(Refunding),
+            Err(AmmError::MarketCannotRefund.into())
+        );
+
+        let mut amm = minted_amm();
+        amm.settle(Refunding).unwrap();
+        assert_eq!(
+            amm.settle(Finalized(Winning)),
+            Err(AmmError::MarketRefunding.into())
+        );
+    }
+
+    #[test]
+    fn markets_only_move_forward() {
+        use MarketOutcome::*;
+        use MarketStatus::*;
+
+        let mut amm = minted_amm();
+        assert_eq!(
+            amm.set_status(Graduated),
+            Err(AmmError::MarketNotFinalized.into())
+        );
+        assert_eq!(
+            amm.set_status(Closed),
+            Err(AmmError::MarketNotFinalized.into())
+        );
+
+        amm.set_status(Finalized(Losing)).unwrap();
+        assert_eq!(
+            amm.set_status(Graduated),
+            Err(AmmError::LosingMarketCannotGraduate.into())
+        );
+        amm.set_status(Closed).unwrap();
+        assert_eq!(amm.set_status(Closed), Err(AmmError::MarketClosed.into()));
+        assert_eq!(
+            amm.set_status(Trading),
+            Err(AmmError::MarketCannotResumeTrading.into())
+        );
+
+        let mut amm = minted_amm();
+        amm.set_status(Finalized(Winning)).unwrap();
+        amm.set_status(Graduated).unwrap();
+        assert_eq!(
+            amm.set_status(Graduated),
+            Err(AmmError::MarketAlreadyGraduated.into())
+        );
+        amm.set_status(Closed).unwrap();
+
+        let mut amm = minted_amm();
+        amm.set_status(Refunding).unwrap();
+        assert_eq!(
+            amm.set_status(Graduated),
+            Err(AmmError::MarketRefunding.into())
+        );
+        amm.set_status(Closed).unwrap();
+    }
+
+    #[test]
+    fn every_lp_and_seller_can_exit() {
+        let mut amm = minted_amm();
+        amm.fee_bps = 30;
+        let mut vault_quote: u64 = 0;
+        let mut lp_supply = 0;
+
+        // interleave buys and deposits, then sell and withdraw everything
+        let mut traders_base = 0;
+        let mut lp_tokens = vec![];
+        for quote_amount in [3 * ONE, 7 * ONE, 20 * ONE] {
+            let buy = amm.swap(quote_amount, SwapType::Buy, no_fee()).unwrap();
+            traders_base += buy.output_amount;
+            vault_quote += quote_amount;
+
+            let (_, minted) = amm.add_liquidity(quote_amount, u64::MAX, lp_supply).unwrap();
+            lp_supply += minted;
+            lp_tokens.push(minted);
+            vault_quote += quote_amount;
+        }
+
+        for lp in lp_tokens {
+            let (_, quote_amount) = amm.remove_liquidity(lp, lp_supply).unwrap();
+            lp_supply -= lp;
+            vault_quote = vault_quote.checked_sub(quote_amount).unwrap();
+        }
+
+        // whatever the floor lets traders sell, the vault can pay
+        loop {
+            let amount = traders_base.min(ONE / 10);
+            if amount == 0 {
+                break;
+            }
+            match amm.swap(amount, SwapType::Sell, no_fee()) {
+                Ok(sell) => {
+                    vault_quote = vault_quote.checked_sub(sell.output_amount).unwrap();
+                    traders_base -= amount;
+                }
+                Err(_) => break,
+            }
+        }
+
+        // the vault still holds the real quote reserves and the fees
+        assert!(amm.v_quote_reserves >= amm.initial_v_quote_reserves);
+        assert!(
+            vault_quote
+                >= amm.v_quote_reserves - amm.initial_v_quote_reserves + amm.quote_fees_accrued
+        );
+    }
+}
+
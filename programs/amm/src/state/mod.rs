@@ -7,5 +7,11 @@ pub const ONE_MINUTE_IN_SLOTS: u64 = TEN_SECONDS_IN_SLOTS * 6;
 pub const PRICE_SCALE: u128 = 1_000_000_000_000;
 pub const MAX_PRICE: u128 = u64::MAX as u128 * PRICE_SCALE;
 
+pub use crate::quote::MAX_BPS;
+// swap fees can be at most 10%
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+pub const MAX_BASE_MINT_DECIMALS: u8 = 9;
+
 pub const AMM_SEED_PREFIX: &[u8] = b"amm__";
 pub const AMM_LP_MINT_SEED_PREFIX: &[u8] = b"amm_lp_mint";
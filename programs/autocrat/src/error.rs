@@ -26,4 +26,86 @@ pub enum AutocratError {
     InsufficientLpTokenBalance,
     #[msg("The LP tokens passed in have less liquidity than the DAO's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`")]
     InsufficientLpTokenLock,
+    #[msg("An amm has a `fee_recipient` that isn't the `dao`'s treasury")]
+    InvalidFeeRecipient,
+    #[msg("An amm has a `winner_premium_bps` that doesn't match the `dao`'s config, or it's more than `MAX_BPS`")]
+    InvalidWinnerPremium,
+    #[msg("An amm has a `loser_haircut_bps` that doesn't match the `dao`'s config, or it's more than `MAX_BPS`")]
+    InvalidLoserHaircut,
+    #[msg("This proposal is still pending, so its LP tokens are still locked")]
+    ProposalStillPending,
+    #[msg("A proposal's `description_url` can be at most `MAX_DESCRIPTION_URL_LEN` bytes")]
+    DescriptionUrlTooLong,
+    #[msg("A proposal can have at most `MAX_PROPOSAL_INSTRUCTIONS` instructions")]
+    TooManyProposalInstructions,
+    #[msg("A proposal's instructions can take up at most `MAX_PROPOSAL_INSTRUCTIONS_LEN` bytes")]
+    ProposalInstructionsTooLarge,
+    #[msg("Not enough `remaining_accounts` were passed to execute every proposal instruction")]
+    MissingInstructionAccounts,
+    #[msg("`remaining_accounts` hold more accounts than the proposal's instructions use")]
+    UnexpectedInstructionAccounts,
+    #[msg("An instruction's account in `remaining_accounts` isn't the one the proposal specifies")]
+    InstructionAccountMismatch,
+    #[msg("An instruction's program in `remaining_accounts` isn't the one the proposal specifies")]
+    InstructionProgramMismatch,
+    #[msg("An account's writability doesn't match the proposal instruction's account meta")]
+    InstructionAccountWritableMismatch,
+    #[msg("An account the proposal needs a signature from, other than the treasury, didn't sign")]
+    InstructionSignerMissing,
+    #[msg("Only executed, failed, expired, vetoed or cancelled proposals can be closed")]
+    ProposalNotClosable,
+    #[msg("The proposal's LP tokens need to be unlocked before it can be closed")]
+    LpTokensStillLocked,
+    #[msg("The DAO's `cancellation_grace_slots` have passed, so the proposal can't be cancelled")]
+    CancellationWindowElapsed,
+    #[msg("The DAO's `execution_delay_slots` haven't passed since the proposal was finalized")]
+    ProposalTimelocked,
+    #[msg("The proposal's execution window has passed, so it can only be expired")]
+    ProposalExpired,
+    #[msg("The proposal's execution window hasn't passed yet")]
+    ProposalNotExpired,
+    #[msg("`execution_expiry_slots` must be non-zero, or no proposal could ever be executed")]
+    InvalidExecutionExpiry,
+    #[msg("This DAO doesn't have a guardian")]
+    NoGuardian,
+    #[msg("Only the DAO's guardian can veto proposals")]
+    InvalidGuardian,
+    #[msg("`pass_amm` and `fail_amm` must be different markets")]
+    DuplicateAmm,
+    #[msg("The market wasn't created for this proposal")]
+    InvalidAmmProposal,
+    #[msg("The market wasn't created for this proposal number")]
+    InvalidAmmProposalNumber,
+    #[msg("The pass and fail markets must trade the vaults' pass and fail tokens respectively")]
+    InvalidAmmMints,
+    #[msg("The market has already been settled")]
+    AmmNotTrading,
+    #[msg("The DAO already has `max_pending_proposals` pending proposals")]
+    TooManyPendingProposals,
+    #[msg("The proposer created a proposal within the DAO's `proposer_cooldown_slots`")]
+    ProposerCooldown,
+    #[msg("`max_pending_proposals` must be non-zero, or no proposal could ever be created")]
+    InvalidMaxPendingProposals,
+    #[msg("`bond_slash_threshold_bps` must be at most 10,000")]
+    InvalidBondSlashThreshold,
+    #[msg("The proposal's bond has already been settled")]
+    BondAlreadySettled,
+    #[msg("The bond must go to the treasury if it was slashed, and to the proposer otherwise")]
+    InvalidBondRecipient,
+    #[msg("The proposal's bond must be settled before it can be closed")]
+    BondNotSettled,
+    #[msg("The bond token accounts must all be passed when there is a bond")]
+    MissingBondAccounts,
+    #[msg("The market must graduate to the DAO's `graduation_target`")]
+    InvalidGraduationTarget,
+    #[msg("The market's fee must be the DAO's `amm_fee_bps`, which is at most `amm::state::MAX_FEE_BPS`")]
+    InvalidAmmFee,
+    #[msg("The market must graduate at the DAO's `amm_graduation_threshold`")]
+    InvalidAmmGraduationThreshold,
+    #[msg("Proposal markets can't have virtual reserves")]
+    InvalidAmmVirtualReserves,
+    #[msg("The proposal's vaults have already been settled")]
+    VaultNotActive,
+    #[msg("The proposal's timelock is over, so it can no longer be vetoed")]
+    VetoWindowElapsed,
 }
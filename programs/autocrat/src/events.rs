@@ -0,0 +1,65 @@
+use super::*;
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub proposer: Pubkey,
+    pub number: u32,
+    pub pass_amm: Pubkey,
+    pub fail_amm: Pubkey,
+    pub description_url: String,
+    pub slot: u64,
+}
+
+/// TWAPs are quote units per base unit scaled by `amm::state::PRICE_SCALE`.
+#[event]
+pub struct ProposalFinalized {
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub state: ProposalState,
+    pub pass_market_twap: u128,
+    pub fail_market_twap: u128,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub instructions_executed: u32,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ProposalCancelled {
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub proposer: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ProposalExpired {
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ProposalVetoed {
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub guardian: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ProposalBondSettled {
+    pub proposal: Pubkey,
+    pub dao: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub slashed: bool,
+    pub slot: u64,
+}
@@ -0,0 +1,76 @@
+use super::*;
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(
+        mut,
+        has_one = proposer,
+        has_one = pass_amm,
+        has_one = fail_amm,
+        has_one = dao,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub proposer: Signer<'info>,
+    #[account(mut)]
+    pub pass_amm: AccountLoader<'info, Amm>,
+    #[account(mut)]
+    pub fail_amm: AccountLoader<'info, Amm>,
+    #[account(mut, has_one = treasury)]
+    pub dao: Box<Account<'info, Dao>>,
+    /// CHECK: never read, signs as the markets' settlement authority
+    pub treasury: UncheckedAccount<'info>,
+    pub amm_program: Program<'info, AmmProgram>,
+}
+
+impl CancelProposal<'_> {
+    pub fn validate(&self) -> Result<()> {
+        self.proposal
+            .check_cancellable(&self.dao, Clock::get()?.slot)
+    }
+
+    /// Both markets switch to refund-only mode, where traders can only sell
+    /// back and LPs can only withdraw, at the plain curve price. The vaults
+    /// stay active, so conditional tokens can be merged back into underlying
+    /// tokens, and the proposer can unlock their LP tokens.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let CancelProposal {
+            proposal,
+            proposer,
+            pass_amm,
+            fail_amm,
+            dao,
+            treasury,
+            amm_program,
+        } = ctx.accounts;
+
+        let dao_key = dao.key();
+        let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
+        let signer = &[&treasury_seeds[..]];
+
+        for amm in [pass_amm, fail_amm] {
+            amm::cpi::settle_market(
+                CpiContext::new_with_signer(
+                    amm_program.to_account_info(),
+                    amm::cpi::accounts::SettleMarket {
+                        settlement_authority: treasury.to_account_info(),
+                        amm: amm.to_account_info(),
+                    },
+                    signer,
+                ),
+                MarketStatus::Refunding,
+            )?;
+        }
+
+        proposal.state = ProposalState::Cancelled;
+        dao.pending_proposal_count = dao.pending_proposal_count.saturating_sub(1);
+
+        emit!(ProposalCancelled {
+            proposal: proposal.key(),
+            dao: dao.key(),
+            proposer: proposer.key(),
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use super::*;
+
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    #[account(
+        mut,
+        has_one = proposer,
+        has_one = dao,
+        has_one = pass_amm,
+        has_one = fail_amm,
+        close = proposer,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+    #[account(
+        mut,
+        has_one = proposal,
+        seeds = [
+            PROPOSAL_INDEX_SEED_PREFIX,
+            dao.key().as_ref(),
+            &proposal.number.to_le_bytes(),
+        ],
+        bump = proposal_index.pda_bump,
+        close = proposer,
+    )]
+    pub proposal_index: Box<Account<'info, ProposalIndex>>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub dao: Box<Account<'info, Dao>>,
+    pub pass_amm: AccountLoader<'info, Amm>,
+    pub fail_amm: AccountLoader<'info, Amm>,
+    #[account(
+        mut,
+        address = pass_amm.load()?.lp_mint,
+        mint::token_program = token_program,
+    )]
+    pub pass_lp_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        mut,
+        address = fail_amm.load()?.lp_mint,
+        mint::token_program = token_program,
+    )]
+    pub fail_lp_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = pass_lp_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = token_program,
+    )]
+    pub pass_lp_vault_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = fail_lp_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = token_program,
+    )]
+    pub fail_lp_vault_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    /// Only needed if the proposal has a bond vault
+    #[account(
+        mut,
+        address = dao.token_mint,
+        mint::token_program = bond_token_program,
+    )]
+    pub token_mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
+    /// Only needed if the proposal has a bond vault
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = bond_token_program,
+    )]
+    pub bond_vault_account: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub bond_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+impl CloseProposal<'_> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            matches!(
+                self.proposal.state,
+                ProposalState::Executed
+                    | ProposalState::Failed
+                    | ProposalState::Expired
+                    | ProposalState::Vetoed
+                    | ProposalState::Cancelled
+            ),
+            AutocratError::ProposalNotClosable
+        );
+
+        require!(
+            self.proposal.pass_lp_tokens_locked == 0 && self.proposal.fail_lp_tokens_locked == 0,
+            AutocratError::LpTokensStillLocked
+        );
+
+        require_eq!(self.proposal.bond_amount, 0, AutocratError::BondNotSettled);
+
+        Ok(())
+    }
+
+    /// Closes the token accounts the proposal owns, then the proposal and its
+    /// index through their `close` constraints, returning all of their rent to the
+    /// proposer. Anything sent to those accounts after the LP was unlocked or
+    /// the bond settled is burned, so that nobody can keep them open.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let CloseProposal {
+            proposal,
+            proposer,
+            pass_lp_mint,
+            fail_lp_mint,
+            pass_lp_vault_account,
+            fail_lp_vault_account,
+            token_mint,
+            bond_vault_account,
+            token_program,
+            bond_token_program,
+            ..
+        } = ctx.accounts;
+
+        let proposer_key = proposal.proposer;
+        let nonce_bytes = proposal.nonce.to_le_bytes();
+        let proposal_seeds = &[
+            b"proposal",
+            proposer_key.as_ref(),
+            nonce_bytes.as_ref(),
+            &[proposal.pda_bump],
+        ];
+        let signer = &[&proposal_seeds[..]];
+
+        let mut accounts_to_close = vec![
+            (pass_lp_mint, pass_lp_vault_account, &*token_program),
+            (fail_lp_mint, fail_lp_vault_account, &*token_program),
+        ];
+
+        if proposal.has_bond_vault {
+            let (Some(token_mint), Some(bond_vault_account), Some(bond_token_program)) =
+                (token_mint, bond_vault_account, bond_token_program)
+            else {
+                return err!(AutocratError::MissingBondAccounts);
+            };
+
+            accounts_to_close.push((token_mint, bond_vault_account, bond_token_program));
+        }
+
+        for (mint, account, token_program) in accounts_to_close {
+            if account.amount > 0 {
+                token_interface::burn(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        token_interface::Burn {
+                            mint: mint.to_account_info(),
+                            from: account.to_account_info(),
+                            authority: proposal.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    account.amount,
+                )?;
+            }
+
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token_interface::CloseAccount {
+                    account: account.to_account_info(),
+                    destination: proposer.to_account_info(),
+                    authority: proposal.to_account_info(),
+                },
+                signer,
+            ))?;
+        }
+
+        Ok(())
+    }
+}
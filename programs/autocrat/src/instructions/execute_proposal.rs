@@ -9,31 +9,49 @@ pub struct ExecuteProposal<'info> {
 
 impl ExecuteProposal<'_> {
     pub fn validate(&self) -> Result<()> {
-        require!(
-            self.proposal.state == ProposalState::Passed,
-            AutocratError::ProposalNotPassed
-        );
-
-        Ok(())
+        self.proposal
+            .check_executable(&self.dao, Clock::get()?.slot)
     }
 
+    /// `remaining_accounts` must hold, for each instruction in order, that
+    /// instruction's accounts followed by its program, and nothing else.
     pub fn handle(ctx: Context<Self>) -> Result<()> {
         let ExecuteProposal { proposal, dao } = ctx.accounts;
 
+        // check every instruction before invoking any, so that a bad account
+        // list fails with a clear error instead of halfway through a CPI
+        let instructions_accounts =
+            proposal.instructions_accounts(ctx.remaining_accounts, &dao.treasury)?;
+
         proposal.state = ProposalState::Executed;
 
         let dao_key = dao.key();
         let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
         let signer = &[&treasury_seeds[..]];
 
-        let mut svm_instruction: Instruction = proposal.instruction.borrow().into();
-        for acc in svm_instruction.accounts.iter_mut() {
-            if acc.pubkey == dao.treasury.key() {
-                acc.is_signer = true;
+        for (instruction, instruction_accounts) in
+            proposal.instructions.iter().zip(instructions_accounts)
+        {
+            let mut svm_instruction: Instruction = instruction.into();
+            for acc in svm_instruction.accounts.iter_mut() {
+                if acc.pubkey == dao.treasury {
+                    acc.is_signer = true;
+                }
             }
+
+            solana_program::program::invoke_signed(
+                &svm_instruction,
+                instruction_accounts,
+                signer,
+            )?;
         }
 
-        solana_program::program::invoke_signed(&svm_instruction, ctx.remaining_accounts, signer)?;
+        emit!(ProposalExecuted {
+            proposal: proposal.key(),
+            dao: dao.key(),
+            instructions_executed: proposal.instructions.len() as u32,
+            slot: Clock::get()?.slot,
+        });
 
         Ok(())
     }
@@ -0,0 +1,30 @@
+use super::*;
+
+#[derive(Accounts)]
+pub struct ExpireProposal<'info> {
+    #[account(mut, has_one = dao)]
+    pub proposal: Account<'info, Proposal>,
+    pub dao: Box<Account<'info, Dao>>,
+}
+
+impl ExpireProposal<'_> {
+    pub fn validate(&self) -> Result<()> {
+        self.proposal
+            .check_expirable(&self.dao, Clock::get()?.slot)
+    }
+
+    /// Permissionless, so that stale proposals can be cleaned up by anyone.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+
+        proposal.state = ProposalState::Expired;
+
+        emit!(ProposalExpired {
+            proposal: proposal.key(),
+            dao: proposal.dao,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+}
@@ -1,21 +1,32 @@
-
 use super::*;
 
+use amm::state::ONE_MINUTE_IN_SLOTS;
+
 #[derive(Accounts)]
 pub struct FinalizeProposal<'info> {
     #[account(mut,
 
         has_one = pass_amm,
         has_one = fail_amm,
+        has_one = base_vault,
+        has_one = quote_vault,
         has_one = dao,
     )]
     pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
     pub pass_amm: AccountLoader<'info, Amm>,
+    #[account(mut)]
     pub fail_amm: AccountLoader<'info, Amm>,
-    #[account(has_one = treasury)]
+    #[account(mut)]
+    pub base_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    #[account(mut)]
+    pub quote_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    #[account(mut, has_one = treasury)]
     pub dao: Box<Account<'info, Dao>>,
-    /// CHECK: never read
+    /// CHECK: never read, signs as the vaults' and markets' settlement authority
     pub treasury: UncheckedAccount<'info>,
+    pub vault_program: Program<'info, ConditionalVaultProgram>,
+    pub amm_program: Program<'info, AmmProgram>,
 }
 
 impl FinalizeProposal<'_> {
@@ -32,6 +43,19 @@ impl FinalizeProposal<'_> {
             AutocratError::ProposalAlreadyFinalized
         );
 
+        // the TWAP only covers observations up to `last_updated_slot`, so make
+        // sure someone cranked both markets near the end of the proposal
+        let proposal_end_slot = self.proposal.slot_enqueued + self.dao.slots_per_proposal;
+
+        for amm in [&self.pass_amm, &self.fail_amm] {
+            let last_updated_slot = amm.load()?.oracle.last_updated_slot;
+
+            require!(
+                last_updated_slot + ONE_MINUTE_IN_SLOTS >= proposal_end_slot,
+                AutocratError::MarketsTooYoung
+            );
+        }
+
         Ok(())
     }
 
@@ -40,13 +64,16 @@ impl FinalizeProposal<'_> {
             proposal,
             pass_amm,
             fail_amm,
+            base_vault,
+            quote_vault,
             dao,
-            ..
-
+            treasury,
+            vault_program,
+            amm_program,
         } = ctx.accounts;
 
-        let pass_market_twap = pass_amm.load()?.calculate_price()?;
-        let fail_market_twap = fail_amm.load()?.calculate_price()?;
+        let pass_market_twap = pass_amm.load()?.get_twap()?;
+        let fail_market_twap = fail_amm.load()?.get_twap()?;
 
         // this can't overflow because each twap can only be MAX_PRICE (~1e31),
         // MAX_BPS + pass_threshold_bps is at most 1e5, and a u128 can hold
@@ -55,30 +82,64 @@ impl FinalizeProposal<'_> {
             .saturating_mul(MAX_BPS.saturating_add(dao.pass_threshold_bps).into())
             / MAX_BPS as u128;
 
-        let new_proposal_state = if pass_market_twap > threshold {
-            pass_amm.load_mut()?.vault_status = 1;
-            fail_amm.load_mut()?.vault_status = 2;
-            ProposalState::Passed
+        let (new_proposal_state, pass_outcome, fail_outcome) = if pass_market_twap > threshold {
+            (ProposalState::Passed, MarketOutcome::Winning, MarketOutcome::Losing)
         } else {
-            pass_amm.load_mut()?.vault_status = 2;
-            fail_amm.load_mut()?.vault_status = 1;
-            ProposalState::Failed
+            (ProposalState::Failed, MarketOutcome::Losing, MarketOutcome::Winning)
         };
 
+        proposal.bond_slashed = dao.bond_slashed(pass_market_twap, fail_market_twap);
+
         proposal.state = new_proposal_state;
+        dao.pending_proposal_count = dao.pending_proposal_count.saturating_sub(1);
+        proposal.slot_finalized = Clock::get()?.slot;
+
+        let new_vault_status = match new_proposal_state {
+            ProposalState::Passed => VaultStatus::Finalized,
+            _ => VaultStatus::Reverted,
+        };
+
+        let dao_key = dao.key();
+        let treasury_seeds = &[dao_key.as_ref(), &[dao.treasury_pda_bump]];
+        let signer = &[&treasury_seeds[..]];
 
-        match new_proposal_state {
-            ProposalState::Passed => {
-                assert!(pass_amm.load()?.vault_status == 1);
-                assert!(fail_amm.load()?.vault_status == 2);
-            }
-            ProposalState::Failed => {
-                assert!(pass_amm.load()?.vault_status == 2);
-                assert!(fail_amm.load()?.vault_status == 1);
-            }
-            _ => unreachable!("Encountered an unexpected proposal state"),
+        for (amm, outcome) in [(pass_amm, pass_outcome), (fail_amm, fail_outcome)] {
+            amm::cpi::settle_market(
+                CpiContext::new_with_signer(
+                    amm_program.to_account_info(),
+                    amm::cpi::accounts::SettleMarket {
+                        settlement_authority: treasury.to_account_info(),
+                        amm: amm.to_account_info(),
+                    },
+                    signer,
+                ),
+                MarketStatus::Finalized(outcome),
+            )?;
         }
 
+        for vault in [base_vault, quote_vault] {
+            conditional_vault::cpi::settle_conditional_vault(
+                CpiContext::new_with_signer(
+                    vault_program.to_account_info(),
+                    conditional_vault::cpi::accounts::SettleConditionalVault {
+                        settlement_authority: treasury.to_account_info(),
+                        vault: vault.to_account_info(),
+                    },
+                    signer,
+                ),
+                new_vault_status,
+            )?;
+        }
+
+        emit!(ProposalFinalized {
+            proposal: proposal.key(),
+            dao: dao.key(),
+            state: new_proposal_state,
+            pass_market_twap,
+            fail_market_twap,
+            slot: Clock::get()?.slot,
+        });
+
         Ok(())
     }
 }
@@ -8,6 +8,18 @@ pub struct InitializeDaoParams {
     pub min_base_futarchic_liquidity: u64,
     pub pass_threshold_bps: Option<u16>,
     pub slots_per_proposal: Option<u64>,
+    pub winner_premium_bps: Option<u16>,
+    pub loser_haircut_bps: Option<u16>,
+    pub cancellation_grace_slots: Option<u64>,
+    pub execution_delay_slots: Option<u64>,
+    pub execution_expiry_slots: Option<u64>,
+    pub max_pending_proposals: Option<u32>,
+    pub proposer_cooldown_slots: Option<u64>,
+    pub proposal_bond: Option<u64>,
+    pub bond_slash_threshold_bps: Option<u16>,
+    pub graduation_target: Option<GraduationTarget>,
+    pub amm_fee_bps: Option<u16>,
+    pub amm_graduation_threshold: Option<u64>,
 }
 
 #[derive(Accounts)]
@@ -21,10 +33,12 @@ pub struct InitializeDAO<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
-    pub token_mint: Account<'info, Mint>,
+    /// SPL Token or Token-2022
+    pub token_mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// SPL Token or Token-2022
     // todo: statically check that this is USDC given a feature flag
     #[account(mint::decimals = 6)]
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: InterfaceAccount<'info, token_interface::Mint>,
 }
 
 impl InitializeDAO<'_> {
@@ -36,8 +50,44 @@ impl InitializeDAO<'_> {
             min_quote_futarchic_liquidity,
             pass_threshold_bps,
             slots_per_proposal,
+            winner_premium_bps,
+            loser_haircut_bps,
+            cancellation_grace_slots,
+            execution_delay_slots,
+            execution_expiry_slots,
+            max_pending_proposals,
+            proposer_cooldown_slots,
+            proposal_bond,
+            bond_slash_threshold_bps,
+            graduation_target,
+            amm_fee_bps,
+            amm_graduation_threshold,
         } = params;
 
+        let winner_premium_bps = winner_premium_bps.unwrap_or(DEFAULT_WINNER_PREMIUM_BPS);
+        require_gte!(MAX_BPS, winner_premium_bps, AutocratError::InvalidWinnerPremium);
+        let loser_haircut_bps = loser_haircut_bps.unwrap_or(DEFAULT_LOSER_HAIRCUT_BPS);
+        require_gte!(MAX_BPS, loser_haircut_bps, AutocratError::InvalidLoserHaircut);
+
+        let execution_expiry_slots =
+            execution_expiry_slots.unwrap_or(DEFAULT_EXECUTION_EXPIRY_SLOTS);
+        require_neq!(execution_expiry_slots, 0, AutocratError::InvalidExecutionExpiry);
+
+        let max_pending_proposals =
+            max_pending_proposals.unwrap_or(DEFAULT_MAX_PENDING_PROPOSALS);
+        require_neq!(max_pending_proposals, 0, AutocratError::InvalidMaxPendingProposals);
+
+        let bond_slash_threshold_bps =
+            bond_slash_threshold_bps.unwrap_or(DEFAULT_BOND_SLASH_THRESHOLD_BPS);
+        require_gte!(
+            MAX_BPS,
+            bond_slash_threshold_bps,
+            AutocratError::InvalidBondSlashThreshold
+        );
+
+        let amm_fee_bps = amm_fee_bps.unwrap_or(DEFAULT_AMM_FEE_BPS);
+        require_gte!(MAX_FEE_BPS, amm_fee_bps, AutocratError::InvalidAmmFee);
+
         let dao = &mut ctx.accounts.dao;
 
         let (treasury, treasury_pda_bump) =
@@ -55,6 +105,23 @@ impl InitializeDAO<'_> {
             twap_max_observation_change_per_update,
             min_base_futarchic_liquidity,
             min_quote_futarchic_liquidity,
+            winner_premium_bps,
+            loser_haircut_bps,
+            cancellation_grace_slots: cancellation_grace_slots
+                .unwrap_or(DEFAULT_CANCELLATION_GRACE_SLOTS),
+            execution_delay_slots: execution_delay_slots.unwrap_or(DEFAULT_EXECUTION_DELAY_SLOTS),
+            execution_expiry_slots,
+            guardian: None,
+            max_pending_proposals,
+            pending_proposal_count: 0,
+            proposer_cooldown_slots: proposer_cooldown_slots
+                .unwrap_or(DEFAULT_PROPOSER_COOLDOWN_SLOTS),
+            proposal_bond: proposal_bond.unwrap_or(DEFAULT_PROPOSAL_BOND),
+            bond_slash_threshold_bps,
+            graduation_target: graduation_target.unwrap_or(GraduationTarget::Recipient(treasury)),
+            amm_fee_bps,
+            amm_graduation_threshold: amm_graduation_threshold
+                .unwrap_or(DEFAULT_AMM_GRADUATION_THRESHOLD),
         });
 
         Ok(())
@@ -1,11 +1,9 @@
 use super::*;
 
-use amm::state::ONE_MINUTE_IN_SLOTS;
-
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct InitializeProposalParams {
     pub description_url: String,
-    pub instruction: ProposalInstruction,
+    pub instructions: Vec<ProposalInstruction>,
     pub pass_lp_tokens_to_lock: u64,
     pub fail_lp_tokens_to_lock: u64,
     pub nonce: u64,
@@ -17,7 +15,10 @@ pub struct InitializeProposal<'info> {
     #[account(
         init,
         payer = proposer,
-        space = 2000,
+        // oversized params are rejected in `handle` with a clear error, so
+        // don't let them fail the allocation first
+        space = Proposal::space(&args.description_url, &args.instructions)
+            .min(Proposal::MAX_SPACE),
         seeds = [b"proposal", proposer.key().as_ref(), &args.nonce.to_le_bytes()],
         bump
     )]
@@ -25,15 +26,104 @@ pub struct InitializeProposal<'info> {
     #[account(mut)]
     pub dao: Box<Account<'info, Dao>>,
     #[account(
-        constraint = fail_amm.load()?.quote_mint == dao.token_mint,
+        init,
+        payer = proposer,
+        space = 8 + ProposalIndex::INIT_SPACE,
+        seeds = [
+            PROPOSAL_INDEX_SEED_PREFIX,
+            dao.key().as_ref(),
+            &(dao.proposal_count + 1).to_le_bytes(),
+        ],
+        bump
     )]
+    pub proposal_index: Box<Account<'info, ProposalIndex>>,
     pub fail_amm: AccountLoader<'info, Amm>,
+    pub pass_amm: AccountLoader<'info, Amm>,
     #[account(
-        constraint = pass_amm.load()?.quote_mint == dao.token_mint,
+        constraint = base_vault.underlying_token_mint == dao.token_mint,
     )]
-    pub pass_amm: AccountLoader<'info, Amm>,
+    pub base_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    #[account(
+        constraint = quote_vault.underlying_token_mint == dao.usdc_mint,
+    )]
+    pub quote_vault: Box<Account<'info, ConditionalVaultAccount>>,
+    #[account(
+        constraint = pass_lp_mint.key() == pass_amm.load()?.lp_mint,
+        mint::token_program = token_program,
+    )]
+    pub pass_lp_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        constraint = fail_lp_mint.key() == fail_amm.load()?.lp_mint,
+        mint::token_program = token_program,
+    )]
+    pub fail_lp_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        mut,
+        token::mint = pass_lp_mint,
+        token::authority = proposer,
+        token::token_program = token_program,
+    )]
+    pub pass_lp_user_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = fail_lp_mint,
+        token::authority = proposer,
+        token::token_program = token_program,
+    )]
+    pub fail_lp_user_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        init,
+        payer = proposer,
+        associated_token::mint = pass_lp_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = token_program,
+    )]
+    pub pass_lp_vault_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        init,
+        payer = proposer,
+        associated_token::mint = fail_lp_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = token_program,
+    )]
+    pub fail_lp_vault_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = 8 + ProposerRecord::INIT_SPACE,
+        seeds = [b"proposer_record", dao.key().as_ref(), proposer.key().as_ref()],
+        bump
+    )]
+    pub proposer_record: Box<Account<'info, ProposerRecord>>,
+    #[account(
+        address = dao.token_mint,
+        mint::token_program = bond_token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    /// Only needed if the DAO has a `proposal_bond`
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = proposer,
+        token::token_program = bond_token_program,
+    )]
+    pub bond_user_account: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+    /// Only needed if the DAO has a `proposal_bond`
+    #[account(
+        init,
+        payer = proposer,
+        associated_token::mint = token_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = bond_token_program,
+    )]
+    pub bond_vault_account: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
     #[account(mut)]
     pub proposer: Signer<'info>,
+    /// The markets' token program, which owns their LP mints
+    pub token_program: Interface<'info, TokenInterface>,
+    /// `dao.token_mint` can be an SPL Token or a Token-2022 mint
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
@@ -41,12 +131,43 @@ impl InitializeProposal<'_> {
     pub fn validate(&self) -> Result<()> {
         let clock = Clock::get()?;
 
-        for amm in [&self.pass_amm, &self.fail_amm] {
-            // an attacker is able to crank 5 observations before a proposal starts
-            require!(
-                clock.slot < amm.load()?.created_at_slot + (50 * ONE_MINUTE_IN_SLOTS),
-                AutocratError::AmmTooOld
-            );
+        let proposal_number = self.dao.proposal_count + 1;
+
+        self.dao.check_rate_limit(&self.proposer_record, clock.slot)?;
+
+        for vault in [&self.base_vault, &self.quote_vault] {
+            self.dao.check_proposal_vault(vault, proposal_number)?;
+        }
+
+        require_keys_neq!(
+            self.pass_amm.key(),
+            self.fail_amm.key(),
+            AutocratError::DuplicateAmm
+        );
+
+        // the pass market trades pTOKEN/pUSDC and the fail market trades
+        // fTOKEN/fUSDC, so that each one prices the DAO in the world where
+        // its side of the proposal happens
+        for (amm, base_mint, quote_mint) in [
+            (
+                &self.pass_amm,
+                self.base_vault.conditional_on_finalize_token_mint,
+                self.quote_vault.conditional_on_finalize_token_mint,
+            ),
+            (
+                &self.fail_amm,
+                self.base_vault.conditional_on_revert_token_mint,
+                self.quote_vault.conditional_on_revert_token_mint,
+            ),
+        ] {
+            self.dao.check_proposal_amm(
+                &*amm.load()?,
+                &self.proposal.key(),
+                proposal_number,
+                &base_mint,
+                &quote_mint,
+                clock.slot,
+            )?;
         }
 
         Ok(())
@@ -56,23 +177,139 @@ impl InitializeProposal<'_> {
         let Self {
             proposal,
             dao,
+            proposal_index,
             pass_amm,
             fail_amm,
+            base_vault,
+            quote_vault,
+            pass_lp_mint,
+            fail_lp_mint,
+            pass_lp_user_account,
+            fail_lp_user_account,
+            pass_lp_vault_account,
+            fail_lp_vault_account,
+            proposer_record,
+            token_mint,
+            bond_user_account,
+            bond_vault_account,
             proposer,
+            token_program,
+            bond_token_program,
+            associated_token_program: _,
             system_program: _,
         } = ctx.accounts;
 
         let InitializeProposalParams {
             description_url,
-            instruction,
+            instructions,
             pass_lp_tokens_to_lock,
             fail_lp_tokens_to_lock,
             nonce,
         } = params;
 
+        require_gte!(
+            MAX_DESCRIPTION_URL_LEN,
+            description_url.len(),
+            AutocratError::DescriptionUrlTooLong
+        );
+
+        require_gte!(
+            MAX_PROPOSAL_INSTRUCTIONS,
+            instructions.len(),
+            AutocratError::TooManyProposalInstructions
+        );
+
+        require_gte!(
+            MAX_PROPOSAL_INSTRUCTIONS_LEN,
+            Proposal::instructions_len(&instructions),
+            AutocratError::ProposalInstructionsTooLarge
+        );
+
+        for (amm, lp_mint, lp_user_account, lp_vault_account, amount) in [
+            (
+                &pass_amm,
+                &pass_lp_mint,
+                &pass_lp_user_account,
+                &pass_lp_vault_account,
+                pass_lp_tokens_to_lock,
+            ),
+            (
+                &fail_amm,
+                &fail_lp_mint,
+                &fail_lp_user_account,
+                &fail_lp_vault_account,
+                fail_lp_tokens_to_lock,
+            ),
+        ] {
+            require_gte!(
+                lp_user_account.amount,
+                amount,
+                AutocratError::InsufficientLpTokenBalance
+            );
+
+            let (base_liquidity, quote_liquidity) = if amount == 0 {
+                (0, 0)
+            } else {
+                amm.load()?
+                    .get_lp_token_liquidity(amount, lp_mint.supply)?
+            };
+
+            require!(
+                base_liquidity >= dao.min_base_futarchic_liquidity
+                    && quote_liquidity >= dao.min_quote_futarchic_liquidity,
+                AutocratError::InsufficientLpTokenLock
+            );
+
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: lp_user_account.to_account_info(),
+                        mint: lp_mint.to_account_info(),
+                        to: lp_vault_account.to_account_info(),
+                        authority: proposer.to_account_info(),
+                    },
+                ),
+                amount,
+                lp_mint.decimals,
+            )?;
+        }
+
+        let has_bond_vault = bond_vault_account.is_some();
+
+        if dao.proposal_bond > 0 {
+            let (Some(bond_user_account), Some(bond_vault_account)) =
+                (bond_user_account, bond_vault_account)
+            else {
+                return err!(AutocratError::MissingBondAccounts);
+            };
+
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    bond_token_program.to_account_info(),
+                    TransferChecked {
+                        from: bond_user_account.to_account_info(),
+                        mint: token_mint.to_account_info(),
+                        to: bond_vault_account.to_account_info(),
+                        authority: proposer.to_account_info(),
+                    },
+                ),
+                dao.proposal_bond,
+                token_mint.decimals,
+            )?;
+        }
+
         let clock = Clock::get()?;
 
+        proposer_record.set_inner(ProposerRecord {
+            dao: dao.key(),
+            proposer: proposer.key(),
+            last_proposal_slot: clock.slot,
+            pda_bump: ctx.bumps.proposer_record,
+        });
+
         dao.proposal_count += 1;
+        dao.pending_proposal_count += 1;
 
         proposal.set_inner(Proposal {
             number: dao.proposal_count,
@@ -80,16 +317,40 @@ impl InitializeProposal<'_> {
             description_url,
             slot_enqueued: clock.slot,
             state: ProposalState::Pending,
-            instruction,
+            slot_finalized: 0,
+            instructions,
             pass_amm: pass_amm.key(),
             fail_amm: fail_amm.key(),
+            base_vault: base_vault.key(),
+            quote_vault: quote_vault.key(),
             dao: dao.key(),
             pass_lp_tokens_locked: pass_lp_tokens_to_lock,
             fail_lp_tokens_locked: fail_lp_tokens_to_lock,
+            bond_amount: dao.proposal_bond,
+            bond_slashed: false,
+            has_bond_vault,
             nonce,
             pda_bump: ctx.bumps.proposal,
         });
 
+        proposal_index.set_inner(ProposalIndex {
+            dao: dao.key(),
+            number: proposal.number,
+            proposal: proposal.key(),
+            pda_bump: ctx.bumps.proposal_index,
+        });
+
+        emit!(ProposalCreated {
+            proposal: proposal.key(),
+            dao: dao.key(),
+            proposer: proposer.key(),
+            number: proposal.number,
+            pass_amm: proposal.pass_amm,
+            fail_amm: proposal.fail_amm,
+            description_url: proposal.description_url.clone(),
+            slot: clock.slot,
+        });
+
         Ok(())
     }
 }
@@ -1,14 +1,26 @@
 use super::*;
 
+pub mod cancel_proposal;
+pub mod close_proposal;
 pub mod execute_proposal;
+pub mod expire_proposal;
 pub mod finalize_proposal;
 pub mod initialize_dao;
 pub mod initialize_proposal;
+pub mod settle_proposal_bond;
+pub mod unlock_proposal_lp;
 pub mod update_dao;
+pub mod veto_proposal;
 
+pub use cancel_proposal::*;
+pub use close_proposal::*;
 pub use execute_proposal::*;
+pub use expire_proposal::*;
 pub use finalize_proposal::*;
 pub use initialize_dao::*;
 pub use initialize_proposal::*;
+pub use settle_proposal_bond::*;
+pub use unlock_proposal_lp::*;
 pub use update_dao::*;
+pub use veto_proposal::*;
 
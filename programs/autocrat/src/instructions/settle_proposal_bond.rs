@@ -0,0 +1,98 @@
+use super::*;
+
+#[derive(Accounts)]
+pub struct SettleProposalBond<'info> {
+    #[account(mut, has_one = dao)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    pub dao: Box<Account<'info, Dao>>,
+    #[account(
+        address = dao.token_mint,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = token_program,
+    )]
+    pub bond_vault_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    /// The treasury's token account if the bond was slashed, and the
+    /// proposer's otherwise
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub recipient_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl SettleProposalBond<'_> {
+    pub fn validate(&self) -> Result<()> {
+        let recipient = self.proposal.bond_recipient(&self.dao)?;
+
+        require_keys_eq!(
+            self.recipient_account.owner,
+            recipient,
+            AutocratError::InvalidBondRecipient
+        );
+
+        Ok(())
+    }
+
+    /// Permissionless, since the bond can only go to one place. Pays out the
+    /// whole vault, which is the bond minus any transfer fee on the way in.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let SettleProposalBond {
+            proposal,
+            dao,
+            token_mint,
+            bond_vault_account,
+            recipient_account,
+            token_program,
+        } = ctx.accounts;
+
+        let proposer_key = proposal.proposer;
+        let nonce_bytes = proposal.nonce.to_le_bytes();
+        let proposal_seeds = &[
+            b"proposal",
+            proposer_key.as_ref(),
+            nonce_bytes.as_ref(),
+            &[proposal.pda_bump],
+        ];
+        let signer = &[&proposal_seeds[..]];
+
+        let amount = bond_vault_account.amount;
+
+        if amount > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: bond_vault_account.to_account_info(),
+                        mint: token_mint.to_account_info(),
+                        to: recipient_account.to_account_info(),
+                        authority: proposal.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+                token_mint.decimals,
+            )?;
+        }
+
+        proposal.bond_amount = 0;
+
+        emit!(ProposalBondSettled {
+            proposal: proposal.key(),
+            dao: dao.key(),
+            recipient: recipient_account.owner,
+            amount,
+            slashed: proposal.bond_slashed,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+}
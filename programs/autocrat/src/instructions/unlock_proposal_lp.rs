@@ -0,0 +1,128 @@
+use super::*;
+
+#[derive(Accounts)]
+pub struct UnlockProposalLp<'info> {
+    #[account(
+        mut,
+        has_one = proposer,
+        has_one = pass_amm,
+        has_one = fail_amm,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+    pub proposer: Signer<'info>,
+    pub pass_amm: AccountLoader<'info, Amm>,
+    pub fail_amm: AccountLoader<'info, Amm>,
+    #[account(
+        address = pass_amm.load()?.lp_mint,
+        mint::token_program = token_program,
+    )]
+    pub pass_lp_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        address = fail_amm.load()?.lp_mint,
+        mint::token_program = token_program,
+    )]
+    pub fail_lp_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = pass_lp_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = token_program,
+    )]
+    pub pass_lp_vault_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = fail_lp_mint,
+        associated_token::authority = proposal,
+        associated_token::token_program = token_program,
+    )]
+    pub fail_lp_vault_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = pass_lp_mint,
+        token::authority = proposer,
+        token::token_program = token_program,
+    )]
+    pub pass_lp_user_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = fail_lp_mint,
+        token::authority = proposer,
+        token::token_program = token_program,
+    )]
+    pub fail_lp_user_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl UnlockProposalLp<'_> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.proposal.state != ProposalState::Pending,
+            AutocratError::ProposalStillPending
+        );
+
+        Ok(())
+    }
+
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let UnlockProposalLp {
+            proposal,
+            pass_lp_mint,
+            fail_lp_mint,
+            pass_lp_vault_account,
+            fail_lp_vault_account,
+            pass_lp_user_account,
+            fail_lp_user_account,
+            token_program,
+            ..
+        } = ctx.accounts;
+
+        let proposer_key = proposal.proposer;
+        let nonce_bytes = proposal.nonce.to_le_bytes();
+        let proposal_seeds = &[
+            b"proposal",
+            proposer_key.as_ref(),
+            nonce_bytes.as_ref(),
+            &[proposal.pda_bump],
+        ];
+        let signer = &[&proposal_seeds[..]];
+
+        for (mint, from, to, amount) in [
+            (
+                &pass_lp_mint,
+                &pass_lp_vault_account,
+                &pass_lp_user_account,
+                proposal.pass_lp_tokens_locked,
+            ),
+            (
+                &fail_lp_mint,
+                &fail_lp_vault_account,
+                &fail_lp_user_account,
+                proposal.fail_lp_tokens_locked,
+            ),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: from.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: proposal.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+                mint.decimals,
+            )?;
+        }
+
+        proposal.pass_lp_tokens_locked = 0;
+        proposal.fail_lp_tokens_locked = 0;
+
+        Ok(())
+    }
+}
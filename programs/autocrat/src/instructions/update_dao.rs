@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
 pub struct UpdateDaoParams {
     pub pass_threshold_bps: Option<u16>,
     pub slots_per_proposal: Option<u64>,
@@ -8,6 +8,20 @@ pub struct UpdateDaoParams {
     pub twap_max_observation_change_per_update: Option<u128>,
     pub min_quote_futarchic_liquidity: Option<u64>,
     pub min_base_futarchic_liquidity: Option<u64>,
+    pub winner_premium_bps: Option<u16>,
+    pub loser_haircut_bps: Option<u16>,
+    pub cancellation_grace_slots: Option<u64>,
+    pub execution_delay_slots: Option<u64>,
+    pub execution_expiry_slots: Option<u64>,
+    pub max_pending_proposals: Option<u32>,
+    pub proposer_cooldown_slots: Option<u64>,
+    pub proposal_bond: Option<u64>,
+    pub bond_slash_threshold_bps: Option<u16>,
+    /// `Some(None)` removes the guardian
+    pub guardian: Option<Option<Pubkey>>,
+    pub graduation_target: Option<GraduationTarget>,
+    pub amm_fee_bps: Option<u16>,
+    pub amm_graduation_threshold: Option<u64>,
 }
 
 #[derive(Accounts)]
@@ -35,6 +49,30 @@ impl UpdateDao<'_> {
         update_dao_if_passed!(twap_max_observation_change_per_update);
         update_dao_if_passed!(min_quote_futarchic_liquidity);
         update_dao_if_passed!(min_base_futarchic_liquidity);
+        update_dao_if_passed!(winner_premium_bps);
+        update_dao_if_passed!(loser_haircut_bps);
+        update_dao_if_passed!(cancellation_grace_slots);
+        update_dao_if_passed!(execution_delay_slots);
+        update_dao_if_passed!(execution_expiry_slots);
+        update_dao_if_passed!(guardian);
+        update_dao_if_passed!(max_pending_proposals);
+        update_dao_if_passed!(proposer_cooldown_slots);
+        update_dao_if_passed!(proposal_bond);
+        update_dao_if_passed!(bond_slash_threshold_bps);
+        update_dao_if_passed!(graduation_target);
+        update_dao_if_passed!(amm_fee_bps);
+        update_dao_if_passed!(amm_graduation_threshold);
+
+        require_gte!(MAX_BPS, dao.winner_premium_bps, AutocratError::InvalidWinnerPremium);
+        require_gte!(MAX_BPS, dao.loser_haircut_bps, AutocratError::InvalidLoserHaircut);
+        require_neq!(dao.execution_expiry_slots, 0, AutocratError::InvalidExecutionExpiry);
+        require_neq!(dao.max_pending_proposals, 0, AutocratError::InvalidMaxPendingProposals);
+        require_gte!(
+            MAX_BPS,
+            dao.bond_slash_threshold_bps,
+            AutocratError::InvalidBondSlashThreshold
+        );
+        require_gte!(MAX_FEE_BPS, dao.amm_fee_bps, AutocratError::InvalidAmmFee);
 
         Ok(())
     }
@@ -0,0 +1,39 @@
+use super::*;
+
+#[derive(Accounts)]
+pub struct VetoProposal<'info> {
+    #[account(mut, has_one = dao)]
+    pub proposal: Account<'info, Proposal>,
+    pub dao: Box<Account<'info, Dao>>,
+    pub guardian: Signer<'info>,
+}
+
+impl VetoProposal<'_> {
+    pub fn validate(&self) -> Result<()> {
+        self.proposal
+            .check_vetoable(&self.dao, &self.guardian.key(), Clock::get()?.slot)
+    }
+
+    /// The markets and vaults have already settled as passed, so this only
+    /// stops the proposal's instructions from ever being executed. Once the
+    /// timelock is over, token holders have had their time to react and the
+    /// proposal can be executed at any moment, so it can't be vetoed anymore.
+    pub fn handle(ctx: Context<Self>) -> Result<()> {
+        let VetoProposal {
+            proposal,
+            dao,
+            guardian,
+        } = ctx.accounts;
+
+        proposal.state = ProposalState::Vetoed;
+
+        emit!(ProposalVetoed {
+            proposal: proposal.key(),
+            dao: dao.key(),
+            guardian: guardian.key(),
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+}
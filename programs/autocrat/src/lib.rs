@@ -3,17 +3,23 @@
 //!
 //! Autocrat has two types of accounts: DAOs and proposals. Every DAO has its
 //! own token, its own treasury account, and list of configs. Proposals are
-//! created for a specific DAO, and contain an SVM instruction and a URL that
-//! should point to a description and justification of that instruction.
+//! created for a specific DAO, and contain a list of SVM instructions and a URL
+//! that should point to a description and justification of those instructions.
 //!
 //! Proposals pass through various states in their lifecycle. Here's a description
 //! of these states:
 //! - Pre-creation: this is when you initialize the accounts needed for a proposal,
-//!   including the vaults and the AMM accounts. The proposer will also deposit to
-//!   create their LP during this time.
+//!   including the vaults and the AMM accounts. The base vault splits TOKEN into
+//!   pTOKEN and fTOKEN and the quote vault splits USDC into pUSDC and fUSDC. The
+//!   pass market trades pTOKEN/pUSDC and the fail market trades fTOKEN/fUSDC,
+//!   both with the conditional tokens as deposited base (see `amm::state::BaseSupply`).
+//!   The proposer will also mint conditional tokens and deposit them to create
+//!   their LP during this time.
 //! - Trading: to create a proposal, the proposer must call
 //!   `initialize_proposal`, which requires them to lock up some LP tokens in each
-//!   of the markets. Once a proposal is created, anyone can trade its markets.
+//!   of the markets, and to post the DAO's `proposal_bond`, if it has one. A DAO
+//!   also caps its pending proposals and how often each proposer can propose.
+//!   Once a proposal is created, anyone can trade its markets.
 //!   Prices of these markets are aggregated into a time-weighted average price
 //!   oracle.
 //! - Pass or fail: if the TWAP of the pass market is sufficiently higher than the
@@ -22,25 +28,40 @@
 //!   redeem. If it fails, both vaults will be reverted, allowing fTOKEN holders to
 //!   redeem.
 //! - Executed: if a proposal passes, anyone can make autocrat execute its SVM
-//!   instruction by calling `execute_proposal`.
+//!   instructions, atomically and in order, by calling `execute_proposal`. This
+//!   opens `execution_delay_slots` after the proposal is finalized and stays
+//!   open for `execution_expiry_slots`.
+//! - Expired: a passed proposal that wasn't executed in time can be marked as
+//!   expired by anyone, with `expire_proposal`.
+//! - Vetoed: if the DAO has a guardian, it can veto a passed proposal before
+//!   it's executed, with `veto_proposal`.
+//! - Cancelled: within the DAO's `cancellation_grace_slots` of creating a
+//!   proposal, its proposer can withdraw it with `cancel_proposal`. Its markets
+//!   then only refund: traders can sell back and LPs can withdraw.
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
-use anchor_spl::token::Mint;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
 
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 pub use crate::error::AutocratError;
+pub use crate::events::*;
 pub use crate::instructions::*;
 pub use crate::state::*;
 
-use amm::state::Amm;
+use amm::program::Amm as AmmProgram;
+use amm::state::{Amm, GraduationTarget, MarketOutcome, MarketStatus, MAX_FEE_BPS};
+use conditional_vault::program::ConditionalVault as ConditionalVaultProgram;
+use conditional_vault::ConditionalVault as ConditionalVaultAccount;
+use conditional_vault::VaultStatus;
 
 use solana_program::instruction::Instruction;
 #[cfg(not(feature = "no-entrypoint"))]
 use solana_security_txt::security_txt;
-use std::borrow::Borrow;
 
 #[cfg(not(feature = "no-entrypoint"))]
 security_txt! {
@@ -66,6 +87,37 @@ pub const DEFAULT_PASS_THRESHOLD_BPS: u16 = 300;
 
 pub const MAX_BPS: u16 = 10_000;
 
+// bounds on what a proposal can hold, so that its account has a known max size
+pub const MAX_DESCRIPTION_URL_LEN: usize = 256;
+pub const MAX_PROPOSAL_INSTRUCTIONS: usize = 8;
+pub const MAX_PROPOSAL_INSTRUCTIONS_LEN: usize = 4_096;
+
+// by default, sellers of the winning market get 10% more than the curve price
+// and sellers of the losing market get 10% less
+pub const DEFAULT_WINNER_PREMIUM_BPS: u16 = 1_000;
+pub const DEFAULT_LOSER_HAIRCUT_BPS: u16 = 1_000;
+
+// by default, a proposer can cancel their proposal within an hour of creating it
+pub const DEFAULT_CANCELLATION_GRACE_SLOTS: u64 = 60 * 6 * SLOTS_PER_10_SECS;
+
+// by default, a passed proposal can be executed from a day after it's
+// finalized until ten days after that
+pub const DEFAULT_EXECUTION_DELAY_SLOTS: u64 = 24 * 60 * 6 * SLOTS_PER_10_SECS;
+pub const DEFAULT_EXECUTION_EXPIRY_SLOTS: u64 = 10 * DEFAULT_EXECUTION_DELAY_SLOTS;
+
+// by default, a DAO can have up to 10 pending proposals, proposers have no
+// cooldown and proposals need no bond. if a DAO does require a bond, it's
+// slashed when the pass TWAP ends up more than 50% below the fail TWAP
+pub const DEFAULT_MAX_PENDING_PROPOSALS: u32 = 10;
+pub const DEFAULT_PROPOSER_COOLDOWN_SLOTS: u64 = 0;
+pub const DEFAULT_PROPOSAL_BOND: u64 = 0;
+pub const DEFAULT_BOND_SLASH_THRESHOLD_BPS: u16 = 5_000;
+
+// by default, proposal markets charge the 1% swap fee that markets always
+// used to, and never graduate
+pub const DEFAULT_AMM_FEE_BPS: u16 = 100;
+pub const DEFAULT_AMM_GRADUATION_THRESHOLD: u64 = u64::MAX;
+
 // TWAP can only move by $5 per slot
 pub const DEFAULT_MAX_OBSERVATION_CHANGE_PER_UPDATE_LOTS: u64 = 5_000;
 
@@ -95,6 +147,36 @@ pub mod autocrat {
         ExecuteProposal::handle(ctx)
     }
 
+    #[access_control(ctx.accounts.validate())]
+    pub fn expire_proposal(ctx: Context<ExpireProposal>) -> Result<()> {
+        ExpireProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn veto_proposal(ctx: Context<VetoProposal>) -> Result<()> {
+        VetoProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        CancelProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn settle_proposal_bond(ctx: Context<SettleProposalBond>) -> Result<()> {
+        SettleProposalBond::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+        CloseProposal::handle(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn unlock_proposal_lp(ctx: Context<UnlockProposalLp>) -> Result<()> {
+        UnlockProposalLp::handle(ctx)
+    }
+
     pub fn update_dao(ctx: Context<UpdateDao>, dao_params: UpdateDaoParams) -> Result<()> {
         UpdateDao::handle(ctx, dao_params)
     }
@@ -1,5 +1,7 @@
 pub use super::*;
 
+use amm::state::ONE_MINUTE_IN_SLOTS;
+
 #[account]
 pub struct Dao {
     pub treasury_pda_bump: u8,
@@ -35,4 +37,484 @@ pub struct Dao {
     /// 10 * 1_000_000_000 (10 META).
     pub min_quote_futarchic_liquidity: u64,
     pub min_base_futarchic_liquidity: u64,
+    /// Once a proposal is decided, its markets settle sells against the curve
+    /// price: the winning market pays `winner_premium_bps` more and the losing
+    /// market pays `loser_haircut_bps` less. Every proposal's markets must be
+    /// created with these values.
+    pub winner_premium_bps: u16,
+    pub loser_haircut_bps: u16,
+    /// How many slots after a proposal is created its proposer can still
+    /// cancel it, e.g. to fix a mistake in its instructions before trading
+    /// gets going.
+    pub cancellation_grace_slots: u64,
+    /// A passed proposal can only be executed `execution_delay_slots` after
+    /// it's finalized, which gives token holders time to react, and then only
+    /// for `execution_expiry_slots`, after which it expires.
+    pub execution_delay_slots: u64,
+    pub execution_expiry_slots: u64,
+    /// An optional emergency brake, e.g. a security council's multisig, that
+    /// can veto passed proposals during their `execution_delay_slots`. It can
+    /// only be set or removed by a proposal, through `update_dao`.
+    pub guardian: Option<Pubkey>,
+    /// Anti-spam: `initialize_proposal` fails while `max_pending_proposals`
+    /// proposals are pending, or if the proposer created a proposal in the
+    /// last `proposer_cooldown_slots`.
+    pub max_pending_proposals: u32,
+    pub pending_proposal_count: u32,
+    pub proposer_cooldown_slots: u64,
+    /// Proposers post `proposal_bond` of `token_mint` with each proposal. It's
+    /// slashed to the treasury if the pass TWAP ends up more than
+    /// `bond_slash_threshold_bps` below the fail TWAP, and refunded otherwise.
+    pub proposal_bond: u64,
+    pub bond_slash_threshold_bps: u16,
+    /// Where every proposal's markets must graduate to. It defaults to the
+    /// treasury, so that nobody can point a market's liquidity at themselves.
+    pub graduation_target: GraduationTarget,
+    /// Every proposal's markets must charge `amm_fee_bps` on swaps and
+    /// graduate once their quote reserves reach `amm_graduation_threshold`.
+    /// They trade the vaults' conditional tokens, which they can't mint, so
+    /// they have no virtual reserves and their LPs set the price.
+    pub amm_fee_bps: u16,
+    pub amm_graduation_threshold: u64,
+}
+
+impl Dao {
+    /// The PDA that holds `dao`'s assets and signs its proposals' instructions
+    pub fn treasury_address(dao: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[dao.as_ref()], &crate::ID).0
+    }
+
+    /// Checks that `vault` can split one of the DAO's tokens for proposal
+    /// number `proposal_number`.
+    pub fn check_proposal_vault(
+        &self,
+        vault: &ConditionalVaultAccount,
+        proposal_number: u32,
+    ) -> Result<()> {
+        require_keys_eq!(
+            vault.settlement_authority,
+            self.treasury,
+            AutocratError::InvalidSettlementAuthority
+        );
+
+        require_eq!(
+            vault.nonce,
+            proposal_number as u64,
+            AutocratError::InvalidVaultNonce
+        );
+
+        require!(
+            vault.status == VaultStatus::Active,
+            AutocratError::VaultNotActive
+        );
+
+        Ok(())
+    }
+
+    /// Checks that `amm` was created for proposal number `proposal_number`,
+    /// whose PDA is `proposal`, to trade `base_mint` against `quote_mint`
+    /// under the DAO's config, and that it's still fresh at `slot`.
+    pub fn check_proposal_amm(
+        &self,
+        amm: &Amm,
+        proposal: &Pubkey,
+        proposal_number: u32,
+        base_mint: &Pubkey,
+        quote_mint: &Pubkey,
+        slot: u64,
+    ) -> Result<()> {
+        require!(
+            amm.base_mint == *base_mint && amm.quote_mint == *quote_mint,
+            AutocratError::InvalidAmmMints
+        );
+
+        // markets are created ahead of their proposal, against its PDA
+        require_keys_eq!(amm.proposal, *proposal, AutocratError::InvalidAmmProposal);
+
+        require_eq!(
+            amm.proposal_number,
+            proposal_number,
+            AutocratError::InvalidAmmProposalNumber
+        );
+
+        require_keys_eq!(
+            amm.settlement_authority,
+            self.treasury,
+            AutocratError::InvalidSettlementAuthority
+        );
+
+        require!(
+            amm.status == MarketStatus::Trading,
+            AutocratError::AmmNotTrading
+        );
+
+        // an attacker is able to crank 5 observations before a proposal starts
+        require!(
+            slot < amm.created_at_slot.saturating_add(50 * ONE_MINUTE_IN_SLOTS),
+            AutocratError::AmmTooOld
+        );
+
+        let oracle = amm.oracle;
+
+        require_eq!(
+            oracle.initial_observation,
+            self.twap_initial_observation,
+            AutocratError::InvalidInitialObservation
+        );
+
+        require_eq!(
+            oracle.max_observation_change_per_update,
+            self.twap_max_observation_change_per_update,
+            AutocratError::InvalidMaxObservationChange
+        );
+
+        require_keys_eq!(
+            amm.fee_recipient,
+            self.treasury,
+            AutocratError::InvalidFeeRecipient
+        );
+
+        require_eq!(
+            amm.winner_premium_bps,
+            self.winner_premium_bps,
+            AutocratError::InvalidWinnerPremium
+        );
+
+        require_eq!(
+            amm.loser_haircut_bps,
+            self.loser_haircut_bps,
+            AutocratError::InvalidLoserHaircut
+        );
+
+        require_eq!(amm.fee_bps, self.amm_fee_bps, AutocratError::InvalidAmmFee);
+
+        require_eq!(
+            amm.graduation_threshold,
+            self.amm_graduation_threshold,
+            AutocratError::InvalidAmmGraduationThreshold
+        );
+
+        // the amm can't mint conditional tokens, so virtual base could
+        // never be paid out
+        require!(
+            amm.initial_v_base_reserves == 0 && amm.initial_v_quote_reserves == 0,
+            AutocratError::InvalidAmmVirtualReserves
+        );
+
+        // otherwise a proposer could graduate the market to themselves
+        require!(
+            amm.graduation_target == self.graduation_target,
+            AutocratError::InvalidGraduationTarget
+        );
+
+        Ok(())
+    }
+
+    /// Checks that the proposer behind `record` can create a proposal at
+    /// `slot`. A fresh record means it would be their first proposal.
+    pub fn check_rate_limit(&self, record: &ProposerRecord, slot: u64) -> Result<()> {
+        require_gt!(
+            self.max_pending_proposals,
+            self.pending_proposal_count,
+            AutocratError::TooManyPendingProposals
+        );
+
+        if record.proposer != Pubkey::default() {
+            require_gte!(
+                slot,
+                record
+                    .last_proposal_slot
+                    .saturating_add(self.proposer_cooldown_slots),
+                AutocratError::ProposerCooldown
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Whether a proposal whose markets ended at these TWAPs loses its bond,
+    /// which is only if the market thinks it's clearly harmful.
+    pub fn bond_slashed(&self, pass_market_twap: u128, fail_market_twap: u128) -> bool {
+        pass_market_twap.saturating_mul(MAX_BPS.into())
+            < fail_market_twap
+                .saturating_mul(MAX_BPS.saturating_sub(self.bond_slash_threshold_bps).into())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn dao() -> Dao {
+        let dao = Pubkey::new_unique();
+        let treasury = Dao::treasury_address(&dao);
+
+        Dao {
+            treasury_pda_bump: 255,
+            treasury,
+            token_mint: Pubkey::new_unique(),
+            usdc_mint: Pubkey::new_unique(),
+            proposal_count: 0,
+            pass_threshold_bps: DEFAULT_PASS_THRESHOLD_BPS,
+            slots_per_proposal: THREE_DAYS_IN_SLOTS,
+            twap_initial_observation: 1_000,
+            twap_max_observation_change_per_update: 10,
+            min_quote_futarchic_liquidity: 1,
+            min_base_futarchic_liquidity: 1,
+            winner_premium_bps: DEFAULT_WINNER_PREMIUM_BPS,
+            loser_haircut_bps: DEFAULT_LOSER_HAIRCUT_BPS,
+            cancellation_grace_slots: 100,
+            execution_delay_slots: 1_000,
+            execution_expiry_slots: 10_000,
+            guardian: None,
+            max_pending_proposals: DEFAULT_MAX_PENDING_PROPOSALS,
+            pending_proposal_count: 0,
+            proposer_cooldown_slots: DEFAULT_PROPOSER_COOLDOWN_SLOTS,
+            proposal_bond: DEFAULT_PROPOSAL_BOND,
+            bond_slash_threshold_bps: DEFAULT_BOND_SLASH_THRESHOLD_BPS,
+            graduation_target: GraduationTarget::Recipient(treasury),
+            amm_fee_bps: DEFAULT_AMM_FEE_BPS,
+            amm_graduation_threshold: DEFAULT_AMM_GRADUATION_THRESHOLD,
+        }
+    }
+
+    const NUMBER: u32 = 7;
+    const CREATED: u64 = 1_000;
+
+    /// Breaks one thing about a valid account
+    pub(crate) type Mutation<T> = fn(&mut T);
+
+    fn vault(dao: &Dao) -> ConditionalVaultAccount {
+        ConditionalVaultAccount {
+            status: VaultStatus::Active,
+            settlement_authority: dao.treasury,
+            underlying_token_mint: dao.token_mint,
+            underlying_token_account: Pubkey::new_unique(),
+            conditional_on_finalize_token_mint: Pubkey::new_unique(),
+            conditional_on_revert_token_mint: Pubkey::new_unique(),
+            nonce: NUMBER as u64,
+            pda_bump: 255,
+            decimals: 6,
+        }
+    }
+
+    /// A market that `dao` accepts for proposal `NUMBER` at `proposal`
+    fn amm(dao: &Dao, proposal: Pubkey, base_mint: Pubkey, quote_mint: Pubkey) -> Amm {
+        let mut amm: Amm = bytemuck::Zeroable::zeroed();
+        amm.base_mint = base_mint;
+        amm.quote_mint = quote_mint;
+        amm.proposal = proposal;
+        amm.proposal_number = NUMBER;
+        amm.settlement_authority = dao.treasury;
+        amm.status = MarketStatus::Trading;
+        amm.created_at_slot = CREATED;
+        amm.oracle.initial_observation = dao.twap_initial_observation;
+        amm.oracle.max_observation_change_per_update = dao.twap_max_observation_change_per_update;
+        amm.fee_recipient = dao.treasury;
+        amm.winner_premium_bps = dao.winner_premium_bps;
+        amm.loser_haircut_bps = dao.loser_haircut_bps;
+        amm.fee_bps = dao.amm_fee_bps;
+        amm.graduation_threshold = dao.amm_graduation_threshold;
+        amm.graduation_target = dao.graduation_target;
+        amm
+    }
+
+    #[test]
+    fn proposal_vaults_must_be_active_and_belong_to_the_proposal() {
+        let dao = dao();
+
+        assert!(dao.check_proposal_vault(&vault(&dao), NUMBER).is_ok());
+
+        let cases: [(Mutation<ConditionalVaultAccount>, AutocratError); 4] = [
+            (
+                |vault| vault.settlement_authority = Pubkey::new_unique(),
+                AutocratError::InvalidSettlementAuthority,
+            ),
+            (|vault| vault.nonce += 1, AutocratError::InvalidVaultNonce),
+            (
+                |vault| vault.status = VaultStatus::Finalized,
+                AutocratError::VaultNotActive,
+            ),
+            (
+                |vault| vault.status = VaultStatus::Reverted,
+                AutocratError::VaultNotActive,
+            ),
+        ];
+
+        for (break_vault, err) in cases {
+            let mut vault = vault(&dao);
+            break_vault(&mut vault);
+
+            assert_eq!(dao.check_proposal_vault(&vault, NUMBER), Err(err.into()));
+        }
+    }
+
+    #[test]
+    fn proposal_amms_must_be_bound_to_the_proposal_and_the_dao() {
+        let dao = dao();
+        let (proposal, base_mint, quote_mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let check = |amm: &Amm, slot| {
+            dao.check_proposal_amm(amm, &proposal, NUMBER, &base_mint, &quote_mint, slot)
+        };
+
+        let valid = amm(&dao, proposal, base_mint, quote_mint);
+        assert!(check(&valid, CREATED).is_ok());
+
+        let too_old = CREATED + 50 * ONE_MINUTE_IN_SLOTS;
+        assert!(check(&valid, too_old - 1).is_ok());
+        assert_eq!(check(&valid, too_old), Err(AutocratError::AmmTooOld.into()));
+
+        let cases: [(Mutation<Amm>, AutocratError); 14] = [
+            (
+                |amm| amm.base_mint = Pubkey::new_unique(),
+                AutocratError::InvalidAmmMints,
+            ),
+            (
+                |amm| amm.quote_mint = Pubkey::new_unique(),
+                AutocratError::InvalidAmmMints,
+            ),
+            (
+                |amm| amm.proposal = Pubkey::new_unique(),
+                AutocratError::InvalidAmmProposal,
+            ),
+            (
+                |amm| amm.proposal_number += 1,
+                AutocratError::InvalidAmmProposalNumber,
+            ),
+            (
+                |amm| amm.settlement_authority = Pubkey::new_unique(),
+                AutocratError::InvalidSettlementAuthority,
+            ),
+            (
+                |amm| amm.status = MarketStatus::Refunding,
+                AutocratError::AmmNotTrading,
+            ),
+            (
+                |amm| amm.oracle.initial_observation += 1,
+                AutocratError::InvalidInitialObservation,
+            ),
+            (
+                |amm| amm.oracle.max_observation_change_per_update += 1,
+                AutocratError::InvalidMaxObservationChange,
+            ),
+            (
+                |amm| amm.fee_recipient = Pubkey::new_unique(),
+                AutocratError::InvalidFeeRecipient,
+            ),
+            (
+                |amm| amm.winner_premium_bps += 1,
+                AutocratError::InvalidWinnerPremium,
+            ),
+            (
+                |amm| amm.loser_haircut_bps += 1,
+                AutocratError::InvalidLoserHaircut,
+            ),
+            (|amm| amm.fee_bps += 1, AutocratError::InvalidAmmFee),
+            (
+                |amm| amm.initial_v_quote_reserves = 1,
+                AutocratError::InvalidAmmVirtualReserves,
+            ),
+            (
+                |amm| amm.graduation_target = GraduationTarget::RaydiumCpSwap,
+                AutocratError::InvalidGraduationTarget,
+            ),
+        ];
+
+        for (break_amm, err) in cases {
+            let mut amm = amm(&dao, proposal, base_mint, quote_mint);
+            break_amm(&mut amm);
+
+            assert_eq!(check(&amm, CREATED), Err(err.into()));
+        }
+    }
+
+    fn record(last_proposal_slot: u64) -> ProposerRecord {
+        ProposerRecord {
+            dao: Pubkey::new_unique(),
+            proposer: Pubkey::new_unique(),
+            last_proposal_slot,
+            pda_bump: 255,
+        }
+    }
+
+    #[test]
+    fn proposals_are_capped_by_the_pending_count() {
+        let mut dao = dao();
+        dao.max_pending_proposals = 2;
+        dao.pending_proposal_count = 1;
+        let first = ProposerRecord {
+            proposer: Pubkey::default(),
+            ..record(0)
+        };
+
+        dao.check_rate_limit(&first, CREATED).unwrap();
+
+        dao.pending_proposal_count = 2;
+        assert_eq!(
+            dao.check_rate_limit(&first, CREATED),
+            Err(AutocratError::TooManyPendingProposals.into())
+        );
+
+        dao.max_pending_proposals = 0;
+        dao.pending_proposal_count = 0;
+        assert_eq!(
+            dao.check_rate_limit(&first, CREATED),
+            Err(AutocratError::TooManyPendingProposals.into())
+        );
+    }
+
+    #[test]
+    fn proposers_have_to_wait_out_their_cooldown() {
+        let mut dao = dao();
+        dao.proposer_cooldown_slots = 100;
+
+        // a first proposal doesn't wait for anything
+        let first = ProposerRecord {
+            proposer: Pubkey::default(),
+            ..record(0)
+        };
+        dao.check_rate_limit(&first, 0).unwrap();
+
+        let record = record(CREATED);
+        for slot in [CREATED, CREATED + 99] {
+            assert_eq!(
+                dao.check_rate_limit(&record, slot),
+                Err(AutocratError::ProposerCooldown.into())
+            );
+        }
+        dao.check_rate_limit(&record, CREATED + 100).unwrap();
+
+        // a cooldown past the last slot never ends, rather than wrapping
+        dao.proposer_cooldown_slots = u64::MAX;
+        assert_eq!(
+            dao.check_rate_limit(&record, u64::MAX - 1),
+            Err(AutocratError::ProposerCooldown.into())
+        );
+    }
+
+    #[test]
+    fn bonds_are_only_slashed_past_the_threshold() {
+        let mut dao = dao();
+        dao.bond_slash_threshold_bps = 5_000;
+
+        // pass has to be more than 50% below fail
+        assert!(!dao.bond_slashed(1_000, 1_000));
+        assert!(!dao.bond_slashed(500, 1_000));
+        assert!(dao.bond_slashed(499, 1_000));
+        assert!(!dao.bond_slashed(2_000, 1_000));
+
+        // a threshold above MAX_BPS saturates, so the bond is never slashed,
+        // while a zero threshold slashes it whenever pass is below fail...
+        dao.bond_slash_threshold_bps = u16::MAX;
+        assert!(!dao.bond_slashed(0, 1_000));
+        dao.bond_slash_threshold_bps = 0;
+        assert!(dao.bond_slashed(999, 1_000));
+
+        // ...and huge TWAPs don't overflow
+        assert!(!dao.bond_slashed(u128::MAX, u128::MAX));
+    }
 }
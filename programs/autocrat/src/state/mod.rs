@@ -1,7 +1,11 @@
 pub mod dao;
 pub mod proposal;
+pub mod proposal_index;
+pub mod proposer_record;
 
 pub use dao::*;
 pub use proposal::*;
+pub use proposal_index::*;
+pub use proposer_record::*;
 
 pub use super::*;
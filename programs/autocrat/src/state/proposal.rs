@@ -1,11 +1,21 @@
 use super::*;
 
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_spl::token_2022::spl_token_2022;
+use spl_token_2022::instruction::AuthorityType;
+
 #[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
 pub enum ProposalState {
     Pending,
     Passed,
     Failed,
     Executed,
+    /// Passed, but wasn't executed within the DAO's execution window
+    Expired,
+    /// Passed, but vetoed by the DAO's guardian before it was executed
+    Vetoed,
+    /// Withdrawn by the proposer during the DAO's cancellation grace window
+    Cancelled,
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
@@ -29,12 +39,26 @@ pub struct Proposal {
     pub description_url: String,
     pub slot_enqueued: u64,
     pub state: ProposalState,
-    pub instruction: ProposalInstruction,
+    /// When the proposal was finalized, or 0 while it's pending
+    pub slot_finalized: u64,
+    /// Executed atomically and in order once the proposal passes
+    pub instructions: Vec<ProposalInstruction>,
     pub pass_amm: Pubkey,
     pub fail_amm: Pubkey,
+    /// Splits `dao.token_mint` into pass and fail tokens
+    pub base_vault: Pubkey,
+    /// Splits `dao.usdc_mint` into pass and fail tokens
+    pub quote_vault: Pubkey,
     pub dao: Pubkey,
     pub pass_lp_tokens_locked: u64,
     pub fail_lp_tokens_locked: u64,
+    /// The bond the proposer posted, or 0 once it's been settled
+    pub bond_amount: u64,
+    /// Decided when the proposal is finalized
+    pub bond_slashed: bool,
+    /// Whether the proposal owns a bond vault, which `close_proposal` then
+    /// has to close
+    pub has_bond_vault: bool,
     /// We need to include a per-proposer nonce to prevent some weird proposal
     /// front-running edge cases. Using a `u64` means that proposers are unlikely
     /// to run into collisions, even if they generate nonces randomly - I've run
@@ -43,6 +67,317 @@ pub struct Proposal {
     pub pda_bump: u8,
 }
 
+impl ProposalInstruction {
+    /// The length of this instruction once borsh-serialized
+    pub fn serialized_len(&self) -> usize {
+        32 + (4 + self.accounts.len() * (32 + 1 + 1)) + (4 + self.data.len())
+    }
+
+    /// Checks that `accounts` are exactly this instruction's accounts followed
+    /// by its program. Autocrat only ever signs as the treasury, so any other
+    /// account the instruction needs a signature from must have signed the
+    /// transaction.
+    pub fn check_accounts(&self, accounts: &[AccountInfo], treasury: &Pubkey) -> Result<()> {
+        let (program, accounts) = accounts
+            .split_last()
+            .ok_or(AutocratError::MissingInstructionAccounts)?;
+
+        require_gte!(
+            accounts.len(),
+            self.accounts.len(),
+            AutocratError::MissingInstructionAccounts
+        );
+        require_gte!(
+            self.accounts.len(),
+            accounts.len(),
+            AutocratError::UnexpectedInstructionAccounts
+        );
+
+        require_keys_eq!(
+            program.key(),
+            self.program_id,
+            AutocratError::InstructionProgramMismatch
+        );
+
+        for (meta, account) in self.accounts.iter().zip(accounts) {
+            require_keys_eq!(
+                account.key(),
+                meta.pubkey,
+                AutocratError::InstructionAccountMismatch
+            );
+
+            require_eq!(
+                account.is_writable,
+                meta.is_writable,
+                AutocratError::InstructionAccountWritableMismatch
+            );
+
+            require!(
+                !meta.is_signer || meta.pubkey == *treasury || account.is_signer,
+                AutocratError::InstructionSignerMissing
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Transfers `amount` of `mint` out of `from`, a token account owned by
+    /// `dao`'s treasury. Works for both SPL Token and Token-2022.
+    pub fn spl_transfer(
+        dao: &Pubkey,
+        token_program: &Pubkey,
+        from: &Pubkey,
+        mint: &Pubkey,
+        to: &Pubkey,
+        amount: u64,
+        decimals: u8,
+    ) -> Result<Self> {
+        let treasury = Dao::treasury_address(dao);
+
+        Ok(spl_token_2022::instruction::transfer_checked(
+            token_program,
+            from,
+            mint,
+            to,
+            &treasury,
+            &[],
+            amount,
+            decimals,
+        )?
+        .into())
+    }
+
+    /// Hands `authority_type` over `account`, a mint or token account whose
+    /// authority is `dao`'s treasury, to `new_authority`. `None` removes the
+    /// authority for good.
+    pub fn set_authority(
+        dao: &Pubkey,
+        token_program: &Pubkey,
+        account: &Pubkey,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+    ) -> Result<Self> {
+        let treasury = Dao::treasury_address(dao);
+
+        Ok(spl_token_2022::instruction::set_authority(
+            token_program,
+            account,
+            new_authority,
+            authority_type,
+            &treasury,
+            &[],
+        )?
+        .into())
+    }
+
+    /// Calls autocrat's own `update_dao` on `dao`.
+    pub fn update_dao(dao: &Pubkey, dao_params: UpdateDaoParams) -> Self {
+        Self {
+            program_id: crate::ID,
+            accounts: crate::accounts::UpdateDao {
+                dao: *dao,
+                treasury: Dao::treasury_address(dao),
+            }
+            .to_account_metas(None)
+            .iter()
+            .map(Into::into)
+            .collect(),
+            data: anchor_lang::InstructionData::data(&crate::instruction::UpdateDao { dao_params }),
+        }
+    }
+
+    /// Upgrades `program` to the code in `buffer`, for a program whose upgrade
+    /// authority is `dao`'s treasury. The buffer's rent goes to `spill`.
+    pub fn upgrade_program(
+        dao: &Pubkey,
+        program: &Pubkey,
+        buffer: &Pubkey,
+        spill: &Pubkey,
+    ) -> Self {
+        bpf_loader_upgradeable::upgrade(program, buffer, &Dao::treasury_address(dao), spill).into()
+    }
+}
+
+impl From<Instruction> for ProposalInstruction {
+    fn from(ix: Instruction) -> Self {
+        Self {
+            program_id: ix.program_id,
+            accounts: ix.accounts.iter().map(Into::into).collect(),
+            data: ix.data,
+        }
+    }
+}
+
+impl Proposal {
+    /// Everything but `description_url` and `instructions`
+    pub const FIXED_LEN: usize = 4 + 32 + 8 + 1 + 8 + 32 * 5 + 8 + 8 + 8 + 1 + 1 + 8 + 1;
+
+    /// The most space a proposal can take up, discriminator included
+    pub const MAX_SPACE: usize = 8
+        + Self::FIXED_LEN
+        + (4 + MAX_DESCRIPTION_URL_LEN)
+        + (4 + MAX_PROPOSAL_INSTRUCTIONS_LEN);
+
+    /// The exact space needed to store a proposal, discriminator included
+    pub fn space(description_url: &str, instructions: &[ProposalInstruction]) -> usize {
+        8 + Self::FIXED_LEN
+            + (4 + description_url.len())
+            + (4 + Self::instructions_len(instructions))
+    }
+
+    /// Whether the proposer can still cancel the proposal at `slot`, which is
+    /// only while it's pending and within the DAO's
+    /// `cancellation_grace_slots`.
+    pub fn check_cancellable(&self, dao: &Dao, slot: u64) -> Result<()> {
+        require!(
+            self.state == ProposalState::Pending,
+            AutocratError::ProposalAlreadyFinalized
+        );
+
+        require_gt!(
+            self.slot_enqueued
+                .saturating_add(dao.cancellation_grace_slots),
+            slot,
+            AutocratError::CancellationWindowElapsed
+        );
+
+        Ok(())
+    }
+
+    /// The first slot a passed proposal can be executed in
+    pub fn execution_start_slot(&self, dao: &Dao) -> u64 {
+        self.slot_finalized.saturating_add(dao.execution_delay_slots)
+    }
+
+    /// The first slot a passed proposal can no longer be executed in
+    pub fn execution_end_slot(&self, dao: &Dao) -> u64 {
+        self.execution_start_slot(dao)
+            .saturating_add(dao.execution_expiry_slots)
+    }
+
+    /// Whether a passed proposal can be executed at `slot`, which is only
+    /// after its timelock and before it expires.
+    pub fn check_executable(&self, dao: &Dao, slot: u64) -> Result<()> {
+        require!(
+            self.state == ProposalState::Passed,
+            AutocratError::ProposalNotPassed
+        );
+
+        require_gte!(
+            slot,
+            self.execution_start_slot(dao),
+            AutocratError::ProposalTimelocked
+        );
+        require_gt!(
+            self.execution_end_slot(dao),
+            slot,
+            AutocratError::ProposalExpired
+        );
+
+        Ok(())
+    }
+
+    /// Whether `guardian` can veto the proposal at `slot`, which is only while
+    /// it's passed and still timelocked.
+    pub fn check_vetoable(&self, dao: &Dao, guardian: &Pubkey, slot: u64) -> Result<()> {
+        let dao_guardian = dao.guardian.ok_or(AutocratError::NoGuardian)?;
+
+        require_keys_eq!(*guardian, dao_guardian, AutocratError::InvalidGuardian);
+
+        require!(
+            self.state == ProposalState::Passed,
+            AutocratError::ProposalNotPassed
+        );
+
+        require_gt!(
+            self.execution_start_slot(dao),
+            slot,
+            AutocratError::VetoWindowElapsed
+        );
+
+        Ok(())
+    }
+
+    /// Whether a passed proposal can be marked as expired at `slot`, which is
+    /// only once it can no longer be executed.
+    pub fn check_expirable(&self, dao: &Dao, slot: u64) -> Result<()> {
+        require!(
+            self.state == ProposalState::Passed,
+            AutocratError::ProposalNotPassed
+        );
+
+        require_gte!(
+            slot,
+            self.execution_end_slot(dao),
+            AutocratError::ProposalNotExpired
+        );
+
+        Ok(())
+    }
+
+    /// Splits `remaining_accounts` into each instruction's accounts followed
+    /// by its program, in order, checking them along the way. There can't be
+    /// any accounts left over.
+    pub fn instructions_accounts<'a, 'info>(
+        &self,
+        remaining_accounts: &'a [AccountInfo<'info>],
+        treasury: &Pubkey,
+    ) -> Result<Vec<&'a [AccountInfo<'info>]>> {
+        let mut remaining_accounts = remaining_accounts;
+        let mut instructions_accounts = Vec::with_capacity(self.instructions.len());
+
+        for instruction in self.instructions.iter() {
+            let accounts_len = instruction.accounts.len() + 1;
+
+            require_gte!(
+                remaining_accounts.len(),
+                accounts_len,
+                AutocratError::MissingInstructionAccounts
+            );
+
+            let (instruction_accounts, rest) = remaining_accounts.split_at(accounts_len);
+            remaining_accounts = rest;
+
+            instruction.check_accounts(instruction_accounts, treasury)?;
+
+            instructions_accounts.push(instruction_accounts);
+        }
+
+        require!(
+            remaining_accounts.is_empty(),
+            AutocratError::UnexpectedInstructionAccounts
+        );
+
+        Ok(instructions_accounts)
+    }
+
+    /// Who a finalized proposal's bond goes to: the treasury if it was
+    /// slashed, and the proposer otherwise.
+    pub fn bond_recipient(&self, dao: &Dao) -> Result<Pubkey> {
+        require!(
+            self.state != ProposalState::Pending,
+            AutocratError::ProposalStillPending
+        );
+
+        require_neq!(self.bond_amount, 0, AutocratError::BondAlreadySettled);
+
+        Ok(if self.bond_slashed {
+            dao.treasury
+        } else {
+            self.proposer
+        })
+    }
+
+    /// The length of `instructions` once borsh-serialized, minus the vec's
+    /// length prefix
+    pub fn instructions_len(instructions: &[ProposalInstruction]) -> usize {
+        instructions
+            .iter()
+            .map(ProposalInstruction::serialized_len)
+            .sum()
+    }
+}
+
 impl From<&ProposalInstruction> for Instruction {
     fn from(ix: &ProposalInstruction) -> Self {
         Self {
@@ -53,6 +388,16 @@ impl From<&ProposalInstruction> for Instruction {
     }
 }
 
+impl From<&AccountMeta> for ProposalAccount {
+    fn from(meta: &AccountMeta) -> Self {
+        Self {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        }
+    }
+}
+
 impl From<&ProposalAccount> for AccountMeta {
     fn from(acc: &ProposalAccount) -> Self {
         Self {
@@ -62,3 +407,632 @@ impl From<&ProposalAccount> for AccountMeta {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::state::dao::tests::{dao, Mutation};
+
+    const ENQUEUED: u64 = 500;
+    const FINALIZED: u64 = 50_000;
+
+    fn proposal(state: ProposalState) -> Proposal {
+        Proposal {
+            number: 1,
+            proposer: Pubkey::new_unique(),
+            description_url: String::new(),
+            slot_enqueued: ENQUEUED,
+            state,
+            slot_finalized: 0,
+            instructions: vec![],
+            pass_amm: Pubkey::new_unique(),
+            fail_amm: Pubkey::new_unique(),
+            base_vault: Pubkey::new_unique(),
+            quote_vault: Pubkey::new_unique(),
+            dao: Pubkey::new_unique(),
+            pass_lp_tokens_locked: 0,
+            fail_lp_tokens_locked: 0,
+            bond_amount: 0,
+            bond_slashed: false,
+            has_bond_vault: false,
+            nonce: 0,
+            pda_bump: 255,
+        }
+    }
+
+    #[test]
+    fn proposals_can_only_be_cancelled_within_the_grace_window() {
+        let dao = dao();
+        let proposal = proposal(ProposalState::Pending);
+        let window_end = ENQUEUED + dao.cancellation_grace_slots;
+
+        assert!(proposal.check_cancellable(&dao, ENQUEUED).is_ok());
+        assert!(proposal.check_cancellable(&dao, window_end - 1).is_ok());
+        assert_eq!(
+            proposal.check_cancellable(&dao, window_end),
+            Err(AutocratError::CancellationWindowElapsed.into())
+        );
+    }
+
+    #[test]
+    fn only_pending_proposals_can_be_cancelled() {
+        let dao = dao();
+
+        for state in [
+            ProposalState::Passed,
+            ProposalState::Failed,
+            ProposalState::Executed,
+            ProposalState::Expired,
+            ProposalState::Vetoed,
+            ProposalState::Cancelled,
+        ] {
+            assert_eq!(
+                proposal(state).check_cancellable(&dao, ENQUEUED),
+                Err(AutocratError::ProposalAlreadyFinalized.into())
+            );
+        }
+    }
+
+    /// A proposal that passed at `FINALIZED`
+    fn passed() -> Proposal {
+        Proposal {
+            slot_finalized: FINALIZED,
+            ..proposal(ProposalState::Passed)
+        }
+    }
+
+    #[test]
+    fn passed_proposals_can_only_be_executed_between_the_timelock_and_expiry() {
+        let dao = dao();
+        let proposal = passed();
+        let start = FINALIZED + dao.execution_delay_slots;
+        let end = start + dao.execution_expiry_slots;
+
+        assert_eq!(
+            proposal.check_executable(&dao, FINALIZED),
+            Err(AutocratError::ProposalTimelocked.into())
+        );
+        assert_eq!(
+            proposal.check_executable(&dao, start - 1),
+            Err(AutocratError::ProposalTimelocked.into())
+        );
+        assert!(proposal.check_executable(&dao, start).is_ok());
+        assert!(proposal.check_executable(&dao, end - 1).is_ok());
+        assert_eq!(
+            proposal.check_executable(&dao, end),
+            Err(AutocratError::ProposalExpired.into())
+        );
+    }
+
+    #[test]
+    fn passed_proposals_only_expire_once_they_can_no_longer_be_executed() {
+        let dao = dao();
+        let proposal = passed();
+        let end = proposal.execution_end_slot(&dao);
+
+        assert_eq!(
+            proposal.check_expirable(&dao, end - 1),
+            Err(AutocratError::ProposalNotExpired.into())
+        );
+        assert!(proposal.check_expirable(&dao, end).is_ok());
+    }
+
+    #[test]
+    fn only_passed_proposals_can_be_executed_or_expired() {
+        let dao = dao();
+
+        for state in [
+            ProposalState::Pending,
+            ProposalState::Failed,
+            ProposalState::Executed,
+            ProposalState::Expired,
+            ProposalState::Vetoed,
+            ProposalState::Cancelled,
+        ] {
+            let proposal = Proposal {
+                slot_finalized: FINALIZED,
+                ..proposal(state)
+            };
+            let start = proposal.execution_start_slot(&dao);
+            let end = proposal.execution_end_slot(&dao);
+
+            assert_eq!(
+                proposal.check_executable(&dao, start),
+                Err(AutocratError::ProposalNotPassed.into())
+            );
+            assert_eq!(
+                proposal.check_expirable(&dao, end),
+                Err(AutocratError::ProposalNotPassed.into())
+            );
+        }
+    }
+
+    #[test]
+    fn execution_window_saturates() {
+        let mut dao = dao();
+        dao.execution_delay_slots = u64::MAX;
+        dao.execution_expiry_slots = u64::MAX;
+
+        let proposal = passed();
+        assert_eq!(proposal.execution_start_slot(&dao), u64::MAX);
+        assert_eq!(
+            proposal.check_executable(&dao, u64::MAX),
+            Err(AutocratError::ProposalExpired.into())
+        );
+    }
+
+    #[test]
+    fn the_guardian_can_only_veto_passed_proposals_during_the_timelock() {
+        let guardian = Pubkey::new_unique();
+        let mut dao = dao();
+        dao.guardian = Some(guardian);
+
+        let proposal = passed();
+        let start = proposal.execution_start_slot(&dao);
+
+        assert!(proposal.check_vetoable(&dao, &guardian, FINALIZED).is_ok());
+        assert!(proposal.check_vetoable(&dao, &guardian, start - 1).is_ok());
+        assert_eq!(
+            proposal.check_vetoable(&dao, &guardian, start),
+            Err(AutocratError::VetoWindowElapsed.into())
+        );
+
+        for state in [
+            ProposalState::Pending,
+            ProposalState::Failed,
+            ProposalState::Executed,
+            ProposalState::Vetoed,
+        ] {
+            let proposal = Proposal { state, ..passed() };
+
+            assert_eq!(
+                proposal.check_vetoable(&dao, &guardian, FINALIZED),
+                Err(AutocratError::ProposalNotPassed.into())
+            );
+        }
+    }
+
+    #[test]
+    fn only_the_guardian_can_veto() {
+        let guardian = Pubkey::new_unique();
+        let mut dao = dao();
+        let proposal = passed();
+
+        assert_eq!(
+            proposal.check_vetoable(&dao, &guardian, FINALIZED),
+            Err(AutocratError::NoGuardian.into())
+        );
+
+        dao.guardian = Some(guardian);
+        assert_eq!(
+            proposal.check_vetoable(&dao, &Pubkey::new_unique(), FINALIZED),
+            Err(AutocratError::InvalidGuardian.into())
+        );
+    }
+
+    #[test]
+    fn bonds_go_to_the_treasury_only_when_slashed() {
+        let dao = dao();
+        let proposal = Proposal {
+            bond_amount: 100,
+            ..proposal(ProposalState::Failed)
+        };
+
+        assert_eq!(proposal.bond_recipient(&dao), Ok(proposal.proposer));
+
+        let slashed = Proposal {
+            bond_slashed: true,
+            ..proposal.clone()
+        };
+        assert_eq!(slashed.bond_recipient(&dao), Ok(dao.treasury));
+
+        // cancelled proposals are never slashed
+        let cancelled = Proposal {
+            state: ProposalState::Cancelled,
+            ..proposal.clone()
+        };
+        assert_eq!(cancelled.bond_recipient(&dao), Ok(proposal.proposer));
+    }
+
+    #[test]
+    fn bonds_are_settled_once_the_proposal_is_decided() {
+        let dao = dao();
+        let proposal = Proposal {
+            bond_amount: 100,
+            ..proposal(ProposalState::Pending)
+        };
+
+        assert_eq!(
+            proposal.bond_recipient(&dao),
+            Err(AutocratError::ProposalStillPending.into())
+        );
+
+        let settled = Proposal {
+            state: ProposalState::Passed,
+            bond_amount: 0,
+            ..proposal
+        };
+        assert_eq!(
+            settled.bond_recipient(&dao),
+            Err(AutocratError::BondAlreadySettled.into())
+        );
+    }
+
+    /// The accounts `ix` needs signed, which should only ever be the treasury
+    fn signers(ix: &ProposalInstruction) -> Vec<Pubkey> {
+        ix.accounts
+            .iter()
+            .filter(|acc| acc.is_signer)
+            .map(|acc| acc.pubkey)
+            .collect()
+    }
+
+    fn assert_serialized_len(ix: &ProposalInstruction) {
+        assert_eq!(ix.serialized_len(), ix.try_to_vec().unwrap().len());
+    }
+
+    #[test]
+    fn spl_transfers_are_signed_by_the_treasury() {
+        use spl_token_2022::instruction::TokenInstruction;
+
+        let dao = Pubkey::new_unique();
+        let (from, mint, to) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        for token_program in [anchor_spl::token::ID, spl_token_2022::ID] {
+            let ix =
+                ProposalInstruction::spl_transfer(&dao, &token_program, &from, &mint, &to, 42, 6)
+                    .unwrap();
+
+            assert_eq!(ix.program_id, token_program);
+            assert_eq!(signers(&ix), [Dao::treasury_address(&dao)]);
+            assert_eq!(
+                ix.accounts.iter().map(|acc| acc.pubkey).collect::<Vec<_>>()[..3],
+                [from, mint, to]
+            );
+            assert!(matches!(
+                TokenInstruction::unpack(&ix.data).unwrap(),
+                TokenInstruction::TransferChecked {
+                    amount: 42,
+                    decimals: 6
+                }
+            ));
+            assert_serialized_len(&ix);
+        }
+
+        assert!(ProposalInstruction::spl_transfer(
+            &dao,
+            &Pubkey::new_unique(),
+            &from,
+            &mint,
+            &to,
+            42,
+            6
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn authorities_are_handed_over_by_the_treasury() {
+        use spl_token_2022::instruction::TokenInstruction;
+        use spl_token_2022::solana_program::program_option::COption;
+
+        let dao = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let new_authority = Pubkey::new_unique();
+
+        for (new, expected) in [
+            (Some(&new_authority), COption::Some(new_authority)),
+            (None, COption::None),
+        ] {
+            let ix = ProposalInstruction::set_authority(
+                &dao,
+                &spl_token_2022::ID,
+                &mint,
+                AuthorityType::MintTokens,
+                new,
+            )
+            .unwrap();
+
+            assert_eq!(ix.program_id, spl_token_2022::ID);
+            assert_eq!(ix.accounts[0].pubkey, mint);
+            assert_eq!(signers(&ix), [Dao::treasury_address(&dao)]);
+            assert_eq!(
+                TokenInstruction::unpack(&ix.data).unwrap(),
+                TokenInstruction::SetAuthority {
+                    authority_type: AuthorityType::MintTokens,
+                    new_authority: expected,
+                }
+            );
+            assert_serialized_len(&ix);
+        }
+    }
+
+    #[test]
+    fn dao_updates_call_autocrat_as_the_treasury() {
+        use anchor_lang::Discriminator;
+
+        let dao = Pubkey::new_unique();
+        let dao_params = UpdateDaoParams {
+            guardian: Some(None),
+            proposal_bond: Some(1_000),
+            ..Default::default()
+        };
+
+        let ix = ProposalInstruction::update_dao(&dao, dao_params);
+
+        assert_eq!(ix.program_id, crate::ID);
+        assert_eq!(
+            ix.accounts,
+            [
+                ProposalAccount {
+                    pubkey: dao,
+                    is_signer: false,
+                    is_writable: true,
+                },
+                ProposalAccount {
+                    pubkey: Dao::treasury_address(&dao),
+                    is_signer: true,
+                    is_writable: false,
+                },
+            ]
+        );
+        assert_eq!(ix.data[..8], crate::instruction::UpdateDao::DISCRIMINATOR);
+        assert_eq!(
+            crate::instruction::UpdateDao::try_from_slice(&ix.data[8..])
+                .unwrap()
+                .dao_params,
+            dao_params
+        );
+        assert_serialized_len(&ix);
+    }
+
+    #[test]
+    fn program_upgrades_are_authorized_by_the_treasury() {
+        let dao = Pubkey::new_unique();
+        let (program, buffer, spill) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        let ix = ProposalInstruction::upgrade_program(&dao, &program, &buffer, &spill);
+
+        assert_eq!(ix.program_id, bpf_loader_upgradeable::ID);
+        assert_eq!(signers(&ix), [Dao::treasury_address(&dao)]);
+
+        let keys: Vec<_> = ix.accounts.iter().map(|acc| acc.pubkey).collect();
+        assert!(keys.contains(&program));
+        assert!(keys.contains(&buffer));
+        assert!(keys.contains(&spill));
+        assert_serialized_len(&ix);
+    }
+
+    /// What an account looks like to `execute_proposal`
+    struct TestAccount {
+        key: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        lamports: u64,
+        data: Vec<u8>,
+        owner: Pubkey,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, is_signer: bool, is_writable: bool) -> Self {
+            Self {
+                key,
+                is_signer,
+                is_writable,
+                lamports: 0,
+                data: vec![],
+                owner: Pubkey::default(),
+            }
+        }
+
+        /// The accounts a transaction would pass to execute `ix`, which the
+        /// treasury doesn't sign
+        fn for_instruction(ix: &ProposalInstruction, treasury: &Pubkey) -> Vec<Self> {
+            ix.accounts
+                .iter()
+                .map(|acc| {
+                    Self::new(
+                        acc.pubkey,
+                        acc.is_signer && acc.pubkey != *treasury,
+                        acc.is_writable,
+                    )
+                })
+                .chain([Self::new(ix.program_id, false, false)])
+                .collect()
+        }
+    }
+
+    fn infos(accounts: &mut [TestAccount]) -> Vec<AccountInfo<'_>> {
+        accounts
+            .iter_mut()
+            .map(|acc| {
+                AccountInfo::new(
+                    &acc.key,
+                    acc.is_signer,
+                    acc.is_writable,
+                    &mut acc.lamports,
+                    &mut acc.data,
+                    &acc.owner,
+                    false,
+                    0,
+                )
+            })
+            .collect()
+    }
+
+    /// Writes to an account, reads another, and needs both the treasury and
+    /// a third party to sign
+    fn instruction(treasury: &Pubkey) -> ProposalInstruction {
+        ProposalInstruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![
+                ProposalAccount {
+                    pubkey: *treasury,
+                    is_signer: true,
+                    is_writable: true,
+                },
+                ProposalAccount {
+                    pubkey: Pubkey::new_unique(),
+                    is_signer: false,
+                    is_writable: true,
+                },
+                ProposalAccount {
+                    pubkey: Pubkey::new_unique(),
+                    is_signer: false,
+                    is_writable: false,
+                },
+                ProposalAccount {
+                    pubkey: Pubkey::new_unique(),
+                    is_signer: true,
+                    is_writable: false,
+                },
+            ],
+            data: vec![],
+        }
+    }
+
+    #[test]
+    fn instructions_run_with_exactly_their_accounts() {
+        let treasury = Pubkey::new_unique();
+        let ix = instruction(&treasury);
+        let mut accounts = TestAccount::for_instruction(&ix, &treasury);
+
+        ix.check_accounts(&infos(&mut accounts), &treasury).unwrap();
+
+        let cases: [(Mutation<Vec<TestAccount>>, AutocratError); 7] = [
+            (
+                |accounts| accounts.last_mut().unwrap().key = Pubkey::new_unique(),
+                AutocratError::InstructionProgramMismatch,
+            ),
+            (
+                |accounts| accounts[1].key = Pubkey::new_unique(),
+                AutocratError::InstructionAccountMismatch,
+            ),
+            (
+                |accounts| accounts.swap(1, 2),
+                AutocratError::InstructionAccountMismatch,
+            ),
+            (
+                |accounts| accounts[1].is_writable = false,
+                AutocratError::InstructionAccountWritableMismatch,
+            ),
+            (
+                |accounts| accounts[2].is_writable = true,
+                AutocratError::InstructionAccountWritableMismatch,
+            ),
+            (
+                |accounts| accounts[3].is_signer = false,
+                AutocratError::InstructionSignerMissing,
+            ),
+            (
+                |accounts| {
+                    accounts.remove(2);
+                },
+                AutocratError::MissingInstructionAccounts,
+            ),
+        ];
+
+        for (break_accounts, err) in cases {
+            let mut accounts = TestAccount::for_instruction(&ix, &treasury);
+            break_accounts(&mut accounts);
+
+            assert_eq!(
+                ix.check_accounts(&infos(&mut accounts), &treasury),
+                Err(err.into())
+            );
+        }
+
+        let mut accounts = TestAccount::for_instruction(&ix, &treasury);
+        accounts.insert(0, TestAccount::new(Pubkey::new_unique(), false, false));
+        assert_eq!(
+            ix.check_accounts(&infos(&mut accounts), &treasury),
+            Err(AutocratError::UnexpectedInstructionAccounts.into())
+        );
+
+        assert_eq!(
+            ix.check_accounts(&[], &treasury),
+            Err(AutocratError::MissingInstructionAccounts.into())
+        );
+    }
+
+    #[test]
+    fn only_the_treasury_is_signed_for() {
+        let treasury = Pubkey::new_unique();
+
+        // any other PDA, e.g. another DAO's treasury, has to sign itself,
+        // which it can't
+        let other_treasury = Pubkey::new_unique();
+        let ix = instruction(&other_treasury);
+        let mut accounts = TestAccount::for_instruction(&ix, &other_treasury);
+
+        assert_eq!(
+            ix.check_accounts(&infos(&mut accounts), &treasury),
+            Err(AutocratError::InstructionSignerMissing.into())
+        );
+    }
+
+    #[test]
+    fn remaining_accounts_are_split_between_the_instructions() {
+        let treasury = Pubkey::new_unique();
+        let proposal = Proposal {
+            instructions: vec![instruction(&treasury), instruction(&treasury)],
+            ..passed()
+        };
+        let instruction_accounts = |proposal: &Proposal| -> Vec<TestAccount> {
+            proposal
+                .instructions
+                .iter()
+                .flat_map(|ix| TestAccount::for_instruction(ix, &treasury))
+                .collect()
+        };
+
+        let mut accounts = instruction_accounts(&proposal);
+        let account_infos = infos(&mut accounts);
+        let split = proposal
+            .instructions_accounts(&account_infos, &treasury)
+            .unwrap();
+        assert_eq!(split.len(), 2);
+        for (ix, accounts) in proposal.instructions.iter().zip(split) {
+            assert_eq!(accounts.last().unwrap().key(), ix.program_id);
+            assert_eq!(accounts.len(), ix.accounts.len() + 1);
+        }
+
+        let mut accounts = instruction_accounts(&proposal);
+        accounts.pop();
+        assert_eq!(
+            proposal
+                .instructions_accounts(&infos(&mut accounts), &treasury)
+                .unwrap_err(),
+            AutocratError::MissingInstructionAccounts.into()
+        );
+
+        let mut accounts = instruction_accounts(&proposal);
+        accounts.push(TestAccount::new(Pubkey::new_unique(), false, false));
+        assert_eq!(
+            proposal
+                .instructions_accounts(&infos(&mut accounts), &treasury)
+                .unwrap_err(),
+            AutocratError::UnexpectedInstructionAccounts.into()
+        );
+
+        // the second instruction's accounts are checked before anything runs
+        let mut accounts = instruction_accounts(&proposal);
+        let len = accounts.len();
+        accounts[len - 2].is_signer = false;
+        assert_eq!(
+            proposal
+                .instructions_accounts(&infos(&mut accounts), &treasury)
+                .unwrap_err(),
+            AutocratError::InstructionSignerMissing.into()
+        );
+    }
+}
@@ -0,0 +1,63 @@
+pub use super::*;
+
+pub const PROPOSAL_INDEX_SEED_PREFIX: &[u8] = b"proposal_index";
+
+/// Points at proposal number `number` of `dao`, so that clients can list a
+/// DAO's proposals by deriving `[b"proposal_index", dao, number]` for every
+/// number up to `dao.proposal_count`. Closed along with its proposal, so
+/// numbers whose index is missing belong to closed proposals.
+#[account]
+pub struct ProposalIndex {
+    pub dao: Pubkey,
+    pub number: u32,
+    pub proposal: Pubkey,
+    pub pda_bump: u8,
+}
+
+impl ProposalIndex {
+    pub const INIT_SPACE: usize = 32 + 4 + 32 + 1;
+
+    /// The index of proposal number `number` of `dao`. Numbers start at 1.
+    pub fn address(dao: &Pubkey, number: u32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[PROPOSAL_INDEX_SEED_PREFIX, dao.as_ref(), &number.to_le_bytes()],
+            &crate::ID,
+        )
+        .0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_proposal_of_every_dao_has_its_own_index() {
+        let daos = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let mut addresses = vec![];
+        for dao in &daos {
+            // 256 shares its low byte with 0, so the whole number must count
+            for number in [1, 2, 3, 256, u32::MAX] {
+                addresses.push(ProposalIndex::address(dao, number));
+            }
+        }
+
+        let count = addresses.len();
+        addresses.sort();
+        addresses.dedup();
+        assert_eq!(addresses.len(), count);
+    }
+
+    #[test]
+    fn indexes_are_derived_from_the_little_endian_number() {
+        let dao = Pubkey::new_unique();
+
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"proposal_index", dao.as_ref(), &[1, 0, 0, 0]],
+            &crate::ID,
+        );
+
+        assert_eq!(ProposalIndex::address(&dao, 1), expected);
+    }
+}
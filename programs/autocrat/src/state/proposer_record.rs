@@ -0,0 +1,15 @@
+pub use super::*;
+
+/// Tracks a proposer's activity in a DAO, so that the DAO can rate-limit them.
+/// PDA of `[b"proposer_record", dao, proposer]`.
+#[account]
+pub struct ProposerRecord {
+    pub dao: Pubkey,
+    pub proposer: Pubkey,
+    pub last_proposal_slot: u64,
+    pub pda_bump: u8,
+}
+
+impl ProposerRecord {
+    pub const INIT_SPACE: usize = 32 + 32 + 8 + 1;
+}
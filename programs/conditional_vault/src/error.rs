@@ -0,0 +1,25 @@
+use super::*;
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Insufficient underlying token balance to mint this amount of conditional tokens")]
+    InsufficientUnderlyingTokens,
+    #[msg("Insufficient conditional token balance to merge this amount")]
+    InsufficientConditionalTokens,
+    #[msg("This `vault_underlying_token_account` is not this vault's `underlying_token_account`")]
+    InvalidVaultUnderlyingTokenAccount,
+    #[msg("This `underlying_token_mint` is not this vault's `underlying_token_mint`")]
+    InvalidUnderlyingTokenMint,
+    #[msg("Couldn't calculate the underlying mint's transfer fee")]
+    TransferFeeCalculationFailed,
+    #[msg("This conditional token mint is not this vault's conditional token mint")]
+    InvalidConditionalTokenMint,
+    #[msg("Conditional tokens can only be minted or merged while the vault is active")]
+    VaultNotActive,
+    #[msg("Vault needs to be settled before users can redeem conditional tokens for underlying tokens")]
+    CantRedeemConditionalTokens,
+    #[msg("Once a vault has been settled, its status as either finalized or reverted cannot be changed")]
+    VaultAlreadySettled,
+    #[msg("A vault can only be settled as finalized or reverted")]
+    InvalidSettlementStatus,
+}
@@ -0,0 +1,108 @@
+use super::*;
+
+#[derive(Accounts)]
+pub struct InteractWithVault<'info> {
+    #[account(
+        has_one = conditional_on_finalize_token_mint @ VaultError::InvalidConditionalTokenMint,
+        has_one = conditional_on_revert_token_mint @ VaultError::InvalidConditionalTokenMint,
+    )]
+    pub vault: Account<'info, ConditionalVault>,
+    #[account(mut)]
+    pub conditional_on_finalize_token_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub conditional_on_revert_token_mint: Account<'info, Mint>,
+    #[account(
+        constraint = underlying_token_mint.key() == vault.underlying_token_mint
+            @ VaultError::InvalidUnderlyingTokenMint,
+        mint::token_program = underlying_token_program,
+    )]
+    pub underlying_token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        mut,
+        constraint = vault_underlying_token_account.key() == vault.underlying_token_account
+            @ VaultError::InvalidVaultUnderlyingTokenAccount
+    )]
+    pub vault_underlying_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::mint = underlying_token_mint,
+        token::token_program = underlying_token_program,
+    )]
+    pub user_underlying_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::mint = conditional_on_finalize_token_mint,
+    )]
+    pub user_conditional_on_finalize_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::mint = conditional_on_revert_token_mint,
+    )]
+    pub user_conditional_on_revert_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub underlying_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> InteractWithVault<'info> {
+    pub fn burn_conditional_tokens(
+        &self,
+        mint: &Account<'info, Mint>,
+        from: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        token::burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: mint.to_account_info(),
+                    from: from.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    pub fn withdraw_underlying_tokens(&self, amount: u64) -> Result<()> {
+        let vault = &self.vault;
+        let seeds = generate_vault_seeds!(vault);
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.underlying_token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vault_underlying_token_account.to_account_info(),
+                    mint: self.underlying_token_mint.to_account_info(),
+                    to: self.user_underlying_token_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            self.underlying_token_mint.decimals,
+        )
+    }
+
+    /// How many of `amount` underlying tokens reach the vault after the
+    /// underlying mint's Token-2022 transfer fee, if it has one.
+    pub fn underlying_amount_received(&self, amount: u64) -> Result<u64> {
+        let mint_info = self.underlying_token_mint.to_account_info();
+        let mint_data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<
+            anchor_spl::token_2022::spl_token_2022::state::Mint,
+        >::unpack(&mint_data)?;
+
+        let fee = match mint.get_extension::<TransferFeeConfig>() {
+            Ok(config) => config
+                .calculate_epoch_fee(Clock::get()?.epoch, amount)
+                .ok_or(error!(VaultError::TransferFeeCalculationFailed))?,
+            Err(_) => 0,
+        };
+
+        Ok(amount - fee)
+    }
+}
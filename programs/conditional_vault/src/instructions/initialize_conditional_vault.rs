@@ -0,0 +1,91 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct InitializeConditionalVaultArgs {
+    pub settlement_authority: Pubkey,
+    pub nonce: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: InitializeConditionalVaultArgs)]
+pub struct InitializeConditionalVault<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ConditionalVault::INIT_SPACE,
+        seeds = [
+            CONDITIONAL_VAULT_SEED_PREFIX,
+            args.settlement_authority.key().as_ref(),
+            underlying_token_mint.key().as_ref(),
+            &args.nonce.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub vault: Box<Account<'info, ConditionalVault>>,
+    #[account(mint::token_program = underlying_token_program)]
+    pub underlying_token_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [CONDITIONAL_ON_FINALIZE_MINT_SEED_PREFIX, vault.key().as_ref()],
+        bump,
+        mint::authority = vault,
+        mint::freeze_authority = vault,
+        mint::decimals = underlying_token_mint.decimals
+    )]
+    pub conditional_on_finalize_token_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [CONDITIONAL_ON_REVERT_MINT_SEED_PREFIX, vault.key().as_ref()],
+        bump,
+        mint::authority = vault,
+        mint::freeze_authority = vault,
+        mint::decimals = underlying_token_mint.decimals
+    )]
+    pub conditional_on_revert_token_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::authority = vault,
+        associated_token::mint = underlying_token_mint,
+        associated_token::token_program = underlying_token_program,
+    )]
+    pub vault_underlying_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The conditional token mints are always SPL Token mints
+    pub token_program: Program<'info, Token>,
+    /// SPL Token or Token-2022, whichever owns the underlying mint
+    pub underlying_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl InitializeConditionalVault<'_> {
+    pub fn handle(ctx: Context<Self>, args: InitializeConditionalVaultArgs) -> Result<()> {
+        let InitializeConditionalVaultArgs {
+            settlement_authority,
+            nonce,
+        } = args;
+
+        let vault = &mut ctx.accounts.vault;
+
+        vault.set_inner(ConditionalVault {
+            status: VaultStatus::Active,
+            settlement_authority,
+            underlying_token_mint: ctx.accounts.underlying_token_mint.key(),
+            underlying_token_account: ctx.accounts.vault_underlying_token_account.key(),
+            conditional_on_finalize_token_mint: ctx
+                .accounts
+                .conditional_on_finalize_token_mint
+                .key(),
+            conditional_on_revert_token_mint: ctx.accounts.conditional_on_revert_token_mint.key(),
+            nonce,
+            pda_bump: ctx.bumps.vault,
+            decimals: ctx.accounts.underlying_token_mint.decimals,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use super::*;
+
+impl InteractWithVault<'_> {
+    pub fn validate_merge_conditional_tokens(&self) -> Result<()> {
+        require!(
+            self.vault.status == VaultStatus::Active,
+            VaultError::VaultNotActive
+        );
+
+        Ok(())
+    }
+
+    /// Burns `amount` of both conditional tokens and withdraws `amount`
+    /// underlying tokens in return.
+    pub fn handle_merge_conditional_tokens_for_underlying_tokens(
+        ctx: Context<Self>,
+        amount: u64,
+    ) -> Result<()> {
+        let accs = &ctx.accounts;
+
+        require!(
+            accs.user_conditional_on_finalize_token_account.amount >= amount
+                && accs.user_conditional_on_revert_token_account.amount >= amount,
+            VaultError::InsufficientConditionalTokens
+        );
+
+        accs.burn_conditional_tokens(
+            &accs.conditional_on_finalize_token_mint,
+            &accs.user_conditional_on_finalize_token_account,
+            amount,
+        )?;
+        accs.burn_conditional_tokens(
+            &accs.conditional_on_revert_token_mint,
+            &accs.user_conditional_on_revert_token_account,
+            amount,
+        )?;
+
+        accs.withdraw_underlying_tokens(amount)
+    }
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+impl InteractWithVault<'_> {
+    pub fn validate_mint_conditional_tokens(&self) -> Result<()> {
+        require!(
+            self.vault.status == VaultStatus::Active,
+            VaultError::VaultNotActive
+        );
+
+        Ok(())
+    }
+
+    /// Deposits `amount` underlying tokens and mints one of both conditional
+    /// tokens for every underlying token that reaches the vault.
+    pub fn handle_mint_conditional_tokens(ctx: Context<Self>, amount: u64) -> Result<()> {
+        let accs = &ctx.accounts;
+
+        require_gte!(
+            accs.user_underlying_token_account.amount,
+            amount,
+            VaultError::InsufficientUnderlyingTokens
+        );
+
+        let amount_received = accs.underlying_amount_received(amount)?;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                accs.underlying_token_program.to_account_info(),
+                TransferChecked {
+                    from: accs.user_underlying_token_account.to_account_info(),
+                    mint: accs.underlying_token_mint.to_account_info(),
+                    to: accs.vault_underlying_token_account.to_account_info(),
+                    authority: accs.authority.to_account_info(),
+                },
+            ),
+            amount,
+            accs.underlying_token_mint.decimals,
+        )?;
+
+        let vault = &accs.vault;
+        let seeds = generate_vault_seeds!(vault);
+
+        for (mint, to) in [
+            (
+                &accs.conditional_on_finalize_token_mint,
+                &accs.user_conditional_on_finalize_token_account,
+            ),
+            (
+                &accs.conditional_on_revert_token_mint,
+                &accs.user_conditional_on_revert_token_account,
+            ),
+        ] {
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    accs.token_program.to_account_info(),
+                    MintTo {
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: vault.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount_received,
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,12 @@
+use super::*;
+
+pub mod common;
+pub mod initialize_conditional_vault;
+pub mod merge_conditional_tokens;
+pub mod mint_conditional_tokens;
+pub mod redeem_conditional_tokens;
+pub mod settle_conditional_vault;
+
+pub use common::*;
+pub use initialize_conditional_vault::*;
+pub use settle_conditional_vault::*;
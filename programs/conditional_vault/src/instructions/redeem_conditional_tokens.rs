@@ -0,0 +1,42 @@
+use super::*;
+
+impl InteractWithVault<'_> {
+    pub fn validate_redeem_conditional_tokens(&self) -> Result<()> {
+        require!(
+            self.vault.status != VaultStatus::Active,
+            VaultError::CantRedeemConditionalTokens
+        );
+
+        Ok(())
+    }
+
+    /// Burns all of the user's conditional tokens and pays out underlying
+    /// tokens 1:1 for whichever side the vault was settled on.
+    pub fn handle_redeem_conditional_tokens_for_underlying_tokens(
+        ctx: Context<Self>,
+    ) -> Result<()> {
+        let accs = &ctx.accounts;
+
+        let finalize_balance = accs.user_conditional_on_finalize_token_account.amount;
+        let revert_balance = accs.user_conditional_on_revert_token_account.amount;
+
+        accs.burn_conditional_tokens(
+            &accs.conditional_on_finalize_token_mint,
+            &accs.user_conditional_on_finalize_token_account,
+            finalize_balance,
+        )?;
+        accs.burn_conditional_tokens(
+            &accs.conditional_on_revert_token_mint,
+            &accs.user_conditional_on_revert_token_account,
+            revert_balance,
+        )?;
+
+        let redeemable = accs.vault.redeemable(finalize_balance, revert_balance)?;
+
+        if redeemable > 0 {
+            accs.withdraw_underlying_tokens(redeemable)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! A conditional vault is an entity that splits underlying tokens into two
+//! sets of conditional tokens: conditional-on-finalize tokens (e.g. pTOKEN)
+//! and conditional-on-revert tokens (e.g. fTOKEN).
+//!
+//! Anyone can deposit underlying tokens to mint one of each, and anyone
+//! holding one of each can merge them back into underlying tokens.
+//!
+//! Each vault has a `settlement_authority`. For proposals, this is the DAO's
+//! treasury, which autocrat uses to settle the vault when the proposal is
+//! finalized:
+//! - if the proposal passes, the vault is finalized and conditional-on-finalize
+//!   tokens can be redeemed 1:1 for underlying tokens
+//! - if the proposal fails, the vault is reverted and conditional-on-revert
+//!   tokens can be redeemed 1:1 for underlying tokens
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+pub use crate::error::VaultError;
+pub use crate::instructions::*;
+pub use crate::state::*;
+
+#[cfg(not(feature = "no-entrypoint"))]
+use solana_security_txt::security_txt;
+
+#[cfg(not(feature = "no-entrypoint"))]
+security_txt! {
+    name: "conditional_vault",
+    project_url: "https://metadao.fi",
+    contacts: "email:metaproph3t@protonmail.com",
+    policy: "The market will decide whether we pay a bug bounty.",
+    source_code: "https://github.com/metaDAOproject/futarchy",
+    source_release: "v0.3",
+    auditors: "Neodyme",
+    acknowledgements: "DCF = (CF1 / (1 + r)^1) + (CF2 / (1 + r)^2) + ... (CFn / (1 + r)^n)"
+}
+
+declare_id!("VAU1T7S5UuEHmMvXtXMVmpEoQtZ2ya7eRb7gcN47wDp");
+
+#[program]
+pub mod conditional_vault {
+    use super::*;
+
+    pub fn initialize_conditional_vault(
+        ctx: Context<InitializeConditionalVault>,
+        args: InitializeConditionalVaultArgs,
+    ) -> Result<()> {
+        InitializeConditionalVault::handle(ctx, args)
+    }
+
+    #[access_control(ctx.accounts.validate())]
+    pub fn settle_conditional_vault(
+        ctx: Context<SettleConditionalVault>,
+        new_status: VaultStatus,
+    ) -> Result<()> {
+        SettleConditionalVault::handle(ctx, new_status)
+    }
+
+    #[access_control(ctx.accounts.validate_mint_conditional_tokens())]
+    pub fn mint_conditional_tokens(ctx: Context<InteractWithVault>, amount: u64) -> Result<()> {
+        InteractWithVault::handle_mint_conditional_tokens(ctx, amount)
+    }
+
+    #[access_control(ctx.accounts.validate_merge_conditional_tokens())]
+    pub fn merge_conditional_tokens_for_underlying_tokens(
+        ctx: Context<InteractWithVault>,
+        amount: u64,
+    ) -> Result<()> {
+        InteractWithVault::handle_merge_conditional_tokens_for_underlying_tokens(ctx, amount)
+    }
+
+    #[access_control(ctx.accounts.validate_redeem_conditional_tokens())]
+    pub fn redeem_conditional_tokens_for_underlying_tokens(
+        ctx: Context<InteractWithVault>,
+    ) -> Result<()> {
+        InteractWithVault::handle_redeem_conditional_tokens_for_underlying_tokens(ctx)
+    }
+}
@@ -0,0 +1,142 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub enum VaultStatus {
+    Active,
+    Finalized,
+    Reverted,
+}
+
+#[account]
+pub struct ConditionalVault {
+    pub status: VaultStatus,
+    /// The account that can either finalize the vault to make
+    /// conditional-on-finalize tokens redeemable for underlying tokens or revert
+    /// the vault to make conditional-on-revert tokens redeemable.
+    pub settlement_authority: Pubkey,
+    pub underlying_token_mint: Pubkey,
+    pub underlying_token_account: Pubkey,
+    pub conditional_on_finalize_token_mint: Pubkey,
+    pub conditional_on_revert_token_mint: Pubkey,
+    /// Lets one settlement authority have many vaults over the same underlying
+    /// token. Autocrat requires this to be the proposal number.
+    pub nonce: u64,
+    pub pda_bump: u8,
+    pub decimals: u8,
+}
+
+impl ConditionalVault {
+    pub const INIT_SPACE: usize = 1 + 32 * 5 + 8 + 1 + 1;
+
+    /// Settles an active vault as either finalized or reverted. Settlement is
+    /// final.
+    pub fn settle(&mut self, new_status: VaultStatus) -> Result<()> {
+        require!(
+            self.status == VaultStatus::Active,
+            VaultError::VaultAlreadySettled
+        );
+        require!(
+            new_status != VaultStatus::Active,
+            VaultError::InvalidSettlementStatus
+        );
+
+        self.status = new_status;
+
+        Ok(())
+    }
+
+    /// How many underlying tokens a holder of these conditional tokens gets
+    /// back once the vault is settled: their winning side 1:1, nothing for
+    /// the losing side.
+    pub fn redeemable(&self, finalize_balance: u64, revert_balance: u64) -> Result<u64> {
+        match self.status {
+            VaultStatus::Finalized => Ok(finalize_balance),
+            VaultStatus::Reverted => Ok(revert_balance),
+            VaultStatus::Active => err!(VaultError::CantRedeemConditionalTokens),
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! generate_vault_seeds {
+    ($vault:expr) => {{
+        &[
+            CONDITIONAL_VAULT_SEED_PREFIX,
+            $vault.settlement_authority.as_ref(),
+            $vault.underlying_token_mint.as_ref(),
+            &$vault.nonce.to_le_bytes(),
+            &[$vault.pda_bump],
+        ]
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active_vault() -> ConditionalVault {
+        ConditionalVault {
+            status: VaultStatus::Active,
+            settlement_authority: Pubkey::new_unique(),
+            underlying_token_mint: Pubkey::new_unique(),
+            underlying_token_account: Pubkey::new_unique(),
+            conditional_on_finalize_token_mint: Pubkey::new_unique(),
+            conditional_on_revert_token_mint: Pubkey::new_unique(),
+            nonce: 0,
+            pda_bump: 255,
+            decimals: 6,
+        }
+    }
+
+    #[test]
+    fn mint_settle_redeem_round_trip() {
+        for (status, expected) in [(VaultStatus::Finalized, 70), (VaultStatus::Reverted, 100)] {
+            let mut vault = active_vault();
+
+            // minting 100 underlying gives 100 of both conditional tokens,
+            // and the user then sells 30 conditional-on-finalize tokens
+            let (finalize_balance, revert_balance) = (100 - 30, 100);
+
+            assert_eq!(
+                vault.redeemable(finalize_balance, revert_balance),
+                Err(VaultError::CantRedeemConditionalTokens.into())
+            );
+
+            vault.settle(status).unwrap();
+
+            assert_eq!(
+                vault.redeemable(finalize_balance, revert_balance).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn redeeming_the_losing_side_pays_nothing() {
+        let mut vault = active_vault();
+        vault.settle(VaultStatus::Finalized).unwrap();
+        assert_eq!(vault.redeemable(0, 100).unwrap(), 0);
+
+        let mut vault = active_vault();
+        vault.settle(VaultStatus::Reverted).unwrap();
+        assert_eq!(vault.redeemable(100, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn settlement_is_final() {
+        let mut vault = active_vault();
+
+        assert_eq!(
+            vault.settle(VaultStatus::Active),
+            Err(VaultError::InvalidSettlementStatus.into())
+        );
+
+        vault.settle(VaultStatus::Reverted).unwrap();
+
+        assert_eq!(
+            vault.settle(VaultStatus::Finalized),
+            Err(VaultError::VaultAlreadySettled.into())
+        );
+        assert_eq!(vault.status, VaultStatus::Reverted);
+    }
+}
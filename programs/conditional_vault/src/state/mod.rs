@@ -0,0 +1,9 @@
+pub mod conditional_vault;
+
+pub use conditional_vault::*;
+
+pub use super::*;
+
+pub const CONDITIONAL_VAULT_SEED_PREFIX: &[u8] = b"conditional_vault";
+pub const CONDITIONAL_ON_FINALIZE_MINT_SEED_PREFIX: &[u8] = b"conditional_on_finalize_mint";
+pub const CONDITIONAL_ON_REVERT_MINT_SEED_PREFIX: &[u8] = b"conditional_on_revert_mint";
@@ -34,9 +34,14 @@ import {
   Transaction,
 } from "@solana/web3.js";
 
-import { AMM_PROGRAM_ID } from "./constants";
+import {
+  AMM_PROGRAM_ID,
+  RAYDIUM_CP_SWAP_PROGRAM_ID,
+  RAYDIUM_CREATE_POOL_FEE_RECEIVER,
+} from "./constants";
 import { AmmAccount } from "./types/";
 import { Amm as AmmIDLType } from "./types/amm";
+import { getAmmAddr, getAmmLpMintAddr } from "./utils/pda";
 import { PriceMath } from "./utils/priceMath";
 
 export async function createMint(
@@ -86,6 +91,60 @@ export type SwapType = {
   sell?: {};
 };
 
+export type GraduationTarget =
+  | { raydiumCpSwap: {} }
+  | { recipient: { 0: PublicKey } };
+
+/** The token programs of an amm's base and quote mints. */
+export type TokenPrograms = {
+  base: PublicKey;
+  quote: PublicKey;
+};
+
+const SPL_TOKEN_PROGRAMS: TokenPrograms = {
+  base: TOKEN_PROGRAM_ID,
+  quote: TOKEN_PROGRAM_ID,
+};
+
+export type MarketOutcome = { winning: {} } | { losing: {} };
+
+export type MarketStatus =
+  | { trading: {} }
+  | { finalized: { 0: MarketOutcome } }
+  | { graduated: {} }
+  | { closed: {} }
+  | { refunding: {} };
+
+export type CreateAmmArgs = {
+  pof: string;
+  uri: string;
+  proposal: PublicKey;
+  proposalNumber: number;
+  settlementAuthority: PublicKey;
+  symbol: string;
+  twapInitialObservation: BN;
+  twapMaxObservationChangePerUpdate: BN;
+  feeBps: number;
+  feeRecipient: PublicKey;
+  winnerPremiumBps: number;
+  loserHaircutBps: number;
+  initialVBaseReserves: BN;
+  initialVQuoteReserves: BN;
+  graduationThreshold: BN;
+  graduationTarget: GraduationTarget;
+  baseMintDecimals: number;
+};
+
+export const DEFAULT_AMM_FEE_BPS = 100;
+const MAX_BPS = 10_000;
+
+const RAYDIUM_AMM_CONFIG_SEED = "amm_config";
+const RAYDIUM_POOL_SEED = "pool";
+const RAYDIUM_AUTH_SEED = "vault_and_lp_mint_auth_seed";
+const RAYDIUM_POOL_VAULT_SEED = "pool_vault";
+const RAYDIUM_POOL_LP_MINT_SEED = "pool_lp_mint";
+const RAYDIUM_OBSERVATION_SEED = "observation";
+
 const MPL_TOKEN_METADATA_PROGRAM_ID = toWeb3JsPublicKey(
   UMI_MPL_TOKEN_METADATA_PROGRAM_ID
 );
@@ -170,64 +229,55 @@ export class AmmClient {
     return new PublicKey("62BiVvL2o3dHYbSAjh1ywDTqC9rm7j9eg2PoRSSG9nEH");
   }
 
+  getAmmAddr(baseMint: PublicKey, quoteMint: PublicKey): PublicKey {
+    return getAmmAddr(
+      this.getProgramId(),
+      baseMint,
+      quoteMint,
+      this.provider.publicKey
+    )[0];
+  }
+
   async createAmm(
-    proposal: PublicKey,
+    args: CreateAmmArgs,
     baseMint: PublicKey,
     quoteMint: PublicKey,
-    twapInitialObservation: number,
-    passOrFail: string,
-    uri: string,
-    proposal_number: number,
-    bata?: PublicKey,
-    qata?: PublicKey,
-    bump?: number,
     preixs?: any[] = [],
-    twapMaxObservationChangePerUpdate?: number
+    tokenPrograms: TokenPrograms = SPL_TOKEN_PROGRAMS
   ): Promise<PublicKey> {
-    if (!twapMaxObservationChangePerUpdate) {
-      twapMaxObservationChangePerUpdate = twapInitialObservation * 0.02;
-    }
-
-    var hm = await (
-      await this.createAmmIx(
-        baseMint,
-        quoteMint,
-        passOrFail,
-        uri,
-        proposal_number,
-        "Manifesto",
-        bump,
-        preixs
-      )
+    await (
+      await this.createAmmIx(args, baseMint, quoteMint, preixs, tokenPrograms)
     ).rpc({ skipPreflight: true });
-    console.log("hm", hm);
+
+    return this.getAmmAddr(baseMint, quoteMint);
   }
+
+  /**
+   * The amm is keyed by its creator, the provider's wallet. Metadata is only
+   * created for a base mint that the amm mints, which must already have the
+   * amm as its mint and freeze authority.
+   */
   async createAmmIx(
+    args: CreateAmmArgs,
     baseMint: PublicKey,
     quoteMint: PublicKey,
-    passOrFail: string,
-    uri: string,
-    proposal_number: number,
-    symbol: string,
-    bump: number,
     preixs?: any[] = [],
-    bata?: PublicKey,
-    qata?: PublicKey,
-    twapFirstObservationScaled?: BN,
-    twapMaxObservationChangePerUpdateScaled?: BN
+    tokenPrograms: TokenPrograms = SPL_TOKEN_PROGRAMS
   ): Promise<MethodsBuilder<AmmIDLType, any>> {
-    const [amm, _bump] = PublicKey.findProgramAddressSync(
-      [Buffer.from("amm__"), baseMint.toBuffer(), quoteMint.toBuffer()],
-      this.getProgramId()
-    );
-
-    const vaultAtaBase = getAssociatedTokenAddressSync(baseMint, amm, true);
-    const vaultAtaQuote = getAssociatedTokenAddressSync(quoteMint, amm, true);
+    const amm = this.getAmmAddr(baseMint, quoteMint);
+    const [lpMint] = getAmmLpMintAddr(this.getProgramId(), amm);
 
-    const baseTokenMetadata = await findMetaplexMetadataPda(baseMint);
+    const storedBaseMint = unpackMint(
+      baseMint,
+      await this.provider.connection.getAccountInfo(baseMint),
+      tokenPrograms.base
+    );
+    const ammMintsBase =
+      storedBaseMint.mintAuthority !== null &&
+      storedBaseMint.mintAuthority.equals(amm);
 
     return this.program.methods
-      .createAmm(passOrFail, uri, proposal_number, symbol, bump)
+      .createAmm(args)
       .preInstructions([
         ComputeBudgetProgram.setComputeUnitPrice({
           microLamports: 66600,
@@ -240,13 +290,15 @@ export class AmmClient {
         amm,
         baseMint,
         quoteMint,
-        vaultAtaBase,
-        vaultAtaQuote,
+        lpMint,
+        ...this.vaultAccounts(amm, baseMint, quoteMint, tokenPrograms),
         systemProgram: SystemProgram.programId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        baseTokenMetadata: baseTokenMetadata,
-        metadataProgram: MPL_TOKEN_METADATA_PROGRAM_ID,
+        lpTokenProgram: TOKEN_2022_PROGRAM_ID,
+        baseTokenMetadata: ammMintsBase
+          ? await findMetaplexMetadataPda(baseMint)
+          : null,
+        metadataProgram: ammMintsBase ? MPL_TOKEN_METADATA_PROGRAM_ID : null,
         rent: SYSVAR_RENT_PUBKEY,
       });
   }
@@ -278,7 +330,8 @@ export class AmmClient {
       storedAmm.quoteMint,
       swapType,
       inputAmountScaled,
-      outputAmountMinScaled
+      outputAmountMinScaled,
+      await this.getTokenPrograms(storedAmm.baseMint, storedAmm.quoteMint)
     ).rpc({ skipPreflight: true });
   }
 
@@ -288,143 +341,494 @@ export class AmmClient {
     quoteMint: PublicKey,
     swapType: SwapType,
     inputAmount: BN,
-    outputAmountMin: BN
+    outputAmountMin: BN,
+    tokenPrograms: TokenPrograms = SPL_TOKEN_PROGRAMS
   ) {
-    const receivingToken = swapType.buy ? baseMint : quoteMint;
-
-    const AMM_CONFIG_SEED = "amm_config";
-    const POOL_SEED = "pool";
-    const AUTH_SEED = "auth";
-    const POOL_VAULT_SEED = "pool_vault";
-    const POOL_LP_MINT_SEED = "pool_lp_mint";
-    const OBSERVATION_SEED = "observation";
-
-    const amm_config_index = 0;
-    const [amm_config_key, __bump1] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from(AMM_CONFIG_SEED),
-        new Uint8Array(new BN(amm_config_index).toArray("be", 2)),
-      ],
-      new PublicKey("62BiVvL2o3dHYbSAjh1ywDTqC9rm7j9eg2PoRSSG9nEH")
-    );
-
-    const [pool_account_key, __bump2] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from(POOL_SEED),
-        amm_config_key.toBuffer(),
-        baseMint.toBuffer(),
-        quoteMint.toBuffer(),
-      ],
-      new PublicKey("62BiVvL2o3dHYbSAjh1ywDTqC9rm7j9eg2PoRSSG9nEH")
-    );
+    return this.program.methods
+      .swap({
+        swapType,
+        inputAmount,
+        outputAmountMin,
+      })
+      .preInstructions(
+        this.swapPreInstructions(baseMint, quoteMint, tokenPrograms)
+      )
+      .accounts(this.swapAccounts(amm, baseMint, quoteMint, tokenPrograms));
+  }
 
-    const [authority, __bump3] = PublicKey.findProgramAddressSync(
-      [Buffer.from(AUTH_SEED)],
-      new PublicKey("62BiVvL2o3dHYbSAjh1ywDTqC9rm7j9eg2PoRSSG9nEH")
-    );
+  swapExactOutputIx(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    swapType: SwapType,
+    outputAmount: BN,
+    inputAmountMax: BN,
+    tokenPrograms: TokenPrograms = SPL_TOKEN_PROGRAMS
+  ) {
+    return this.program.methods
+      .swapExactOutput({
+        swapType,
+        outputAmount,
+        inputAmountMax,
+      })
+      .preInstructions(
+        this.swapPreInstructions(baseMint, quoteMint, tokenPrograms)
+      )
+      .accounts(this.swapAccounts(amm, baseMint, quoteMint, tokenPrograms));
+  }
 
-    const [token_0_vault, __bump4] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from(POOL_VAULT_SEED),
-        pool_account_key.toBuffer(),
-        baseMint.toBuffer(),
-      ],
-      new PublicKey("62BiVvL2o3dHYbSAjh1ywDTqC9rm7j9eg2PoRSSG9nEH")
-    );
+  private swapPreInstructions(
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    tokenPrograms: TokenPrograms
+  ) {
+    return [
+      ComputeBudgetProgram.setComputeUnitPrice({
+        microLamports: 66600,
+      }),
+      // the user receives one side, so make sure both of their accounts exist
+      ...this.createTokenAccountsIxs(
+        this.provider.publicKey,
+        baseMint,
+        quoteMint,
+        tokenPrograms
+      ),
+    ];
+  }
 
-    const [token_1_vault, __bump5] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from(POOL_VAULT_SEED),
-        pool_account_key.toBuffer(),
-        quoteMint.toBuffer(),
-      ],
-      new PublicKey("62BiVvL2o3dHYbSAjh1ywDTqC9rm7j9eg2PoRSSG9nEH")
+  private swapAccounts(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    tokenPrograms: TokenPrograms
+  ) {
+    const [userBaseAccount, userQuoteAccount] = this.tokenAccounts(
+      this.provider.publicKey,
+      baseMint,
+      quoteMint,
+      tokenPrograms
     );
 
-    const [lp_mint_key, __bump6] = PublicKey.findProgramAddressSync(
-      [Buffer.from(POOL_LP_MINT_SEED), pool_account_key.toBuffer()],
-      new PublicKey("62BiVvL2o3dHYbSAjh1ywDTqC9rm7j9eg2PoRSSG9nEH")
-    );
+    return {
+      user: this.provider.publicKey,
+      amm,
+      userBaseAccount,
+      userQuoteAccount,
+      baseMint,
+      quoteMint,
+      ...this.vaultAccounts(amm, baseMint, quoteMint, tokenPrograms),
+    };
+  }
 
-    const [observation_key, __bump7] = PublicKey.findProgramAddressSync(
-      [Buffer.from(OBSERVATION_SEED), pool_account_key.toBuffer()],
-      new PublicKey("62BiVvL2o3dHYbSAjh1ywDTqC9rm7j9eg2PoRSSG9nEH")
-    );
+  addLiquidityIx(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    quoteAmount: BN,
+    maxBaseAmount: BN,
+    minLpTokens: BN,
+    tokenPrograms: TokenPrograms = SPL_TOKEN_PROGRAMS
+  ) {
+    const [lpMint] = getAmmLpMintAddr(this.getProgramId(), amm);
 
     return this.program.methods
-      .swap({
-        swapType,
-        inputAmount,
-        outputAmountMin,
+      .addLiquidity({
+        quoteAmount,
+        maxBaseAmount,
+        minLpTokens,
       })
       .preInstructions([
-        ComputeBudgetProgram.setComputeUnitPrice({
-          microLamports: 66600,
-        }),
-      ])
-      .preInstructions([
-        createAssociatedTokenAccountIdempotentInstruction(
-          this.provider.publicKey,
-          getAssociatedTokenAddressSync(baseMint, this.provider.publicKey),
-          this.provider.publicKey,
-          baseMint
-        ),
         createAssociatedTokenAccountIdempotentInstruction(
           this.provider.publicKey,
-          getAssociatedTokenAddressSync(quoteMint, this.provider.publicKey),
+          getAssociatedTokenAddressSync(
+            lpMint,
+            this.provider.publicKey,
+            true,
+            TOKEN_2022_PROGRAM_ID
+          ),
           this.provider.publicKey,
-          quoteMint
+          lpMint,
+          TOKEN_2022_PROGRAM_ID
         ),
       ])
+      .accounts(
+        this.liquidityAccounts(amm, baseMint, quoteMint, lpMint, tokenPrograms)
+      );
+  }
+
+  removeLiquidityIx(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    lpTokensToBurn: BN,
+    minQuoteAmount: BN,
+    minBaseAmount: BN,
+    tokenPrograms: TokenPrograms = SPL_TOKEN_PROGRAMS
+  ) {
+    const [lpMint] = getAmmLpMintAddr(this.getProgramId(), amm);
+
+    return this.program.methods
+      .removeLiquidity({
+        lpTokensToBurn,
+        minQuoteAmount,
+        minBaseAmount,
+      })
+      .preInstructions(
+        this.createTokenAccountsIxs(
+          this.provider.publicKey,
+          baseMint,
+          quoteMint,
+          tokenPrograms
+        )
+      )
+      .accounts(
+        this.liquidityAccounts(amm, baseMint, quoteMint, lpMint, tokenPrograms)
+      );
+  }
+
+  private liquidityAccounts(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    lpMint: PublicKey,
+    tokenPrograms: TokenPrograms
+  ) {
+    const [userBaseAccount, userQuoteAccount] = this.tokenAccounts(
+      this.provider.publicKey,
+      baseMint,
+      quoteMint,
+      tokenPrograms
+    );
+
+    return {
+      user: this.provider.publicKey,
+      amm,
+      lpMint,
+      baseMint,
+      quoteMint,
+      userLpAccount: getAssociatedTokenAddressSync(
+        lpMint,
+        this.provider.publicKey,
+        true,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      userBaseAccount,
+      userQuoteAccount,
+      ...this.vaultAccounts(amm, baseMint, quoteMint, tokenPrograms),
+      lpTokenProgram: TOKEN_2022_PROGRAM_ID,
+    };
+  }
+
+  crankThatTwapIx(amm: PublicKey) {
+    return this.program.methods.crankThatTwap().accounts({
+      amm,
+    });
+  }
+
+  /**
+   * Permissionless, the fees always go to the amm's fee recipient, whose
+   * token accounts are created if needed.
+   */
+  collectFeesIx(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    feeRecipient: PublicKey,
+    tokenPrograms: TokenPrograms = SPL_TOKEN_PROGRAMS
+  ) {
+    const [feeRecipientBaseAccount, feeRecipientQuoteAccount] =
+      this.tokenAccounts(feeRecipient, baseMint, quoteMint, tokenPrograms);
+
+    return this.program.methods
+      .collectFees()
+      .preInstructions(
+        this.createTokenAccountsIxs(
+          feeRecipient,
+          baseMint,
+          quoteMint,
+          tokenPrograms
+        )
+      )
       .accounts({
-        user: this.provider.publicKey,
         amm,
-        userBaseAccount: getAssociatedTokenAddressSync(
+        feeRecipient,
+        baseMint,
+        quoteMint,
+        feeRecipientBaseAccount,
+        feeRecipientQuoteAccount,
+        ...this.vaultAccounts(amm, baseMint, quoteMint, tokenPrograms),
+      });
+  }
+
+  /** Signed by the provider's wallet, the market's settlement authority. */
+  settleMarketIx(amm: PublicKey, newStatus: MarketStatus) {
+    return this.program.methods.settleMarket(newStatus).accounts({
+      settlementAuthority: this.provider.publicKey,
+      amm,
+    });
+  }
+
+  /**
+   * Graduates a winning market to the recipient named by its
+   * `Recipient` graduation target.
+   */
+  graduateToRecipientIx(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    recipient: PublicKey,
+    tokenPrograms: TokenPrograms = SPL_TOKEN_PROGRAMS
+  ) {
+    const [recipientBaseAccount, recipientQuoteAccount] = this.tokenAccounts(
+      recipient,
+      baseMint,
+      quoteMint,
+      tokenPrograms
+    );
+
+    return this.graduateIx(amm, baseMint, quoteMint, tokenPrograms)
+      .preInstructions(
+        this.createTokenAccountsIxs(
+          recipient,
           baseMint,
-          this.provider.publicKey,
-          true
-        ),
-        userQuoteAccount: getAssociatedTokenAddressSync(
           quoteMint,
+          tokenPrograms
+        )
+      )
+      .remainingAccounts([
+        { pubkey: recipientBaseAccount, isSigner: false, isWritable: true },
+        { pubkey: recipientQuoteAccount, isSigner: false, isWritable: true },
+      ]);
+  }
+
+  /**
+   * Graduates a winning market to a new Raydium CP-Swap pool, which the
+   * provider's wallet creates and pays the pool creation fee for.
+   */
+  graduateToRaydiumCpSwapIx(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    ammConfigIndex: number = 0,
+    tokenPrograms: TokenPrograms = SPL_TOKEN_PROGRAMS
+  ) {
+    const [token0Mint, token1Mint] =
+      Buffer.compare(baseMint.toBuffer(), quoteMint.toBuffer()) < 0
+        ? [baseMint, quoteMint]
+        : [quoteMint, baseMint];
+
+    const raydiumPda = (seeds: (Buffer | Uint8Array)[]) =>
+      PublicKey.findProgramAddressSync(seeds, RAYDIUM_CP_SWAP_PROGRAM_ID)[0];
+
+    const ammConfig = raydiumPda([
+      Buffer.from(RAYDIUM_AMM_CONFIG_SEED),
+      new Uint8Array(new BN(ammConfigIndex).toArray("be", 2)),
+    ]);
+    const poolState = raydiumPda([
+      Buffer.from(RAYDIUM_POOL_SEED),
+      ammConfig.toBuffer(),
+      token0Mint.toBuffer(),
+      token1Mint.toBuffer(),
+    ]);
+    const authority = raydiumPda([Buffer.from(RAYDIUM_AUTH_SEED)]);
+    const lpMint = raydiumPda([
+      Buffer.from(RAYDIUM_POOL_LP_MINT_SEED),
+      poolState.toBuffer(),
+    ]);
+    const [token0Vault, token1Vault] = [token0Mint, token1Mint].map((mint) =>
+      raydiumPda([
+        Buffer.from(RAYDIUM_POOL_VAULT_SEED),
+        poolState.toBuffer(),
+        mint.toBuffer(),
+      ])
+    );
+    const observationState = raydiumPda([
+      Buffer.from(RAYDIUM_OBSERVATION_SEED),
+      poolState.toBuffer(),
+    ]);
+
+    const [payerBaseAccount, payerQuoteAccount] = this.tokenAccounts(
+      this.provider.publicKey,
+      baseMint,
+      quoteMint,
+      tokenPrograms
+    );
+
+    const remainingAccounts = [
+      { pubkey: payerBaseAccount, isWritable: true },
+      { pubkey: payerQuoteAccount, isWritable: true },
+      { pubkey: RAYDIUM_CP_SWAP_PROGRAM_ID, isWritable: false },
+      { pubkey: ammConfig, isWritable: false },
+      { pubkey: authority, isWritable: false },
+      { pubkey: poolState, isWritable: true },
+      { pubkey: lpMint, isWritable: true },
+      {
+        pubkey: getAssociatedTokenAddressSync(lpMint, this.provider.publicKey),
+        isWritable: true,
+      },
+      { pubkey: token0Vault, isWritable: true },
+      { pubkey: token1Vault, isWritable: true },
+      { pubkey: RAYDIUM_CREATE_POOL_FEE_RECEIVER, isWritable: true },
+      { pubkey: observationState, isWritable: true },
+      // Raydium's LP mint is always an SPL Token mint
+      { pubkey: TOKEN_PROGRAM_ID, isWritable: false },
+      { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isWritable: false },
+      { pubkey: SystemProgram.programId, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isWritable: false },
+    ].map((account) => ({ ...account, isSigner: false }));
+
+    return this.graduateIx(amm, baseMint, quoteMint, tokenPrograms)
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+        ...this.createTokenAccountsIxs(
           this.provider.publicKey,
-          true
-        ),
-        vaultAtaBase: getAssociatedTokenAddressSync(baseMint, amm, true),
-        vaultAtaQuote: getAssociatedTokenAddressSync(quoteMint, amm, true),
-        ammConfig: amm_config_key,
-        authority: authority,
-        poolAccount: pool_account_key,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        token2022Program: TOKEN_2022_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
-        rent: SYSVAR_RENT_PUBKEY,
-        token0Vault: token_0_vault,
-        token1Vault: token_1_vault,
-        createLpAccount: lp_mint_key,
-        createPoolFee: new PublicKey(
-          "DNXgeM9EiiaAbaWvwjHj9fQQLAX5ZsfHyvmYUNRAdNC8"
+          baseMint,
+          quoteMint,
+          tokenPrograms
         ),
-        observationKey: observation_key,
+      ])
+      .remainingAccounts(remainingAccounts);
+  }
+
+  private graduateIx(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    tokenPrograms: TokenPrograms
+  ) {
+    const [lpMint] = getAmmLpMintAddr(this.getProgramId(), amm);
+
+    return this.program.methods.graduate().accounts({
+      payer: this.provider.publicKey,
+      amm,
+      baseMint,
+      quoteMint,
+      lpMint,
+      ...this.vaultAccounts(amm, baseMint, quoteMint, tokenPrograms),
+    });
+  }
+
+  /**
+   * Permissionless. Whatever the vaults still hold goes to the fee
+   * recipient, and the rent of the vaults and the LP mint to the amm's
+   * creator.
+   */
+  closeMarketIx(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    feeRecipient: PublicKey,
+    creator: PublicKey = this.provider.publicKey,
+    tokenPrograms: TokenPrograms = SPL_TOKEN_PROGRAMS
+  ) {
+    const [lpMint] = getAmmLpMintAddr(this.getProgramId(), amm);
+    const [feeRecipientBaseAccount, feeRecipientQuoteAccount] =
+      this.tokenAccounts(feeRecipient, baseMint, quoteMint, tokenPrograms);
+
+    return this.program.methods
+      .closeMarket()
+      .preInstructions(
+        this.createTokenAccountsIxs(
+          feeRecipient,
+          baseMint,
+          quoteMint,
+          tokenPrograms
+        )
+      )
+      .accounts({
+        amm,
+        creator,
+        feeRecipient,
+        baseMint,
+        quoteMint,
+        feeRecipientBaseAccount,
+        feeRecipientQuoteAccount,
+        lpMint,
+        ...this.vaultAccounts(amm, baseMint, quoteMint, tokenPrograms),
+        lpTokenProgram: TOKEN_2022_PROGRAM_ID,
+      });
+  }
+
+  /** The amm's vaults, along with the token programs they live under. */
+  private vaultAccounts(
+    amm: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    tokenPrograms: TokenPrograms
+  ) {
+    const [vaultAtaBase, vaultAtaQuote] = this.tokenAccounts(
+      amm,
+      baseMint,
+      quoteMint,
+      tokenPrograms
+    );
+
+    return {
+      vaultAtaBase,
+      vaultAtaQuote,
+      baseTokenProgram: tokenPrograms.base,
+      quoteTokenProgram: tokenPrograms.quote,
+    };
+  }
+
+  /** `owner`'s associated base and quote token accounts. */
+  private tokenAccounts(
+    owner: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    tokenPrograms: TokenPrograms
+  ): [PublicKey, PublicKey] {
+    return [
+      getAssociatedTokenAddressSync(baseMint, owner, true, tokenPrograms.base),
+      getAssociatedTokenAddressSync(
+        quoteMint,
+        owner,
+        true,
+        tokenPrograms.quote
+      ),
+    ];
+  }
+
+  /** Creates `owner`'s base and quote token accounts if they don't exist. */
+  private createTokenAccountsIxs(
+    owner: PublicKey,
+    baseMint: PublicKey,
+    quoteMint: PublicKey,
+    tokenPrograms: TokenPrograms
+  ) {
+    const accounts = this.tokenAccounts(
+      owner,
+      baseMint,
+      quoteMint,
+      tokenPrograms
+    );
+
+    return [
+      [baseMint, tokenPrograms.base],
+      [quoteMint, tokenPrograms.quote],
+    ].map(([mint, tokenProgram], i) =>
+      createAssociatedTokenAccountIdempotentInstruction(
+        this.provider.publicKey,
+        accounts[i],
+        owner,
+        mint,
+        tokenProgram
+      )
+    );
+  }
+
+  /** Either SPL Token or Token-2022, whichever owns each mint. */
+  async getTokenPrograms(
+    baseMint: PublicKey,
+    quoteMint: PublicKey
+  ): Promise<TokenPrograms> {
+    const [baseMintInfo, quoteMintInfo] =
+      await this.provider.connection.getMultipleAccountsInfo([
         baseMint,
         quoteMint,
-        lpMint: lp_mint_key,
-        raydiumCpSwapProgram: new PublicKey(
-          "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C"
-        ),
-      })
-      .preInstructions([
-        // create the receiving token account if it doesn't exist
-        createAssociatedTokenAccountIdempotentInstruction(
-          this.provider.publicKey,
-          getAssociatedTokenAddressSync(
-            receivingToken,
-            this.provider.publicKey
-          ),
-          this.provider.publicKey,
-          receivingToken
-        ),
       ]);
+
+    return { base: baseMintInfo.owner, quote: quoteMintInfo.owner };
   }
 
   // getter functions
@@ -447,12 +851,19 @@ export class AmmClient {
     return await this.program.account.amm.fetch(amm);
   }
 
+  /**
+   * Mirrors the program's `quote_swap` for a trading market: the fee is
+   * taken from the input, rounded up, and the rest is swapped along the
+   * constant product curve. Doesn't account for transfer fees or the price
+   * floor of markets with virtual liquidity.
+   */
   simulateSwap(
     inputAmount: BN,
     swapType: SwapType,
     baseReserves: BN,
     quoteReserves: BN,
-    slippageBps?: BN
+    slippageBps?: BN,
+    feeBps: number = DEFAULT_AMM_FEE_BPS
   ): SwapSimulation {
     if (baseReserves.eqn(0) || quoteReserves.eqn(0)) {
       throw new Error("reserves must be non-zero");
@@ -467,21 +878,14 @@ export class AmmClient {
       outputReserves = quoteReserves;
     }
 
-    let inputAmountWithFee: BN;
-    if (swapType.buy) {
-      inputAmountWithFee = inputAmount
-        .mul(quoteReserves)
-        .div(baseReserves.sub(inputAmount))
-        .muln(99);
-    } else {
-      inputAmountWithFee = inputAmount
-        .mul(quoteReserves)
-        .div(baseReserves.add(inputAmount))
-        .muln(99);
-    }
+    const fee = inputAmount
+      .muln(feeBps)
+      .add(new BN(MAX_BPS - 1))
+      .divn(MAX_BPS);
+    const inputAmountAfterFee = inputAmount.sub(fee);
 
-    let numerator: BN = inputAmountWithFee.mul(outputReserves);
-    let denominator: BN = inputReserves.muln(100).add(inputAmountWithFee);
+    let numerator: BN = inputAmountAfterFee.mul(outputReserves);
+    let denominator: BN = inputReserves.add(inputAmountAfterFee);
 
     let expectedOut = numerator.div(denominator);
     let minExpectedOut;
@@ -492,9 +896,9 @@ export class AmmClient {
     let newBaseReserves, newQuoteReserves: BN;
     if (swapType.buy) {
       newBaseReserves = baseReserves.sub(expectedOut);
-      newQuoteReserves = quoteReserves.add(inputAmount);
+      newQuoteReserves = quoteReserves.add(inputAmountAfterFee);
     } else {
-      newBaseReserves = baseReserves.add(inputAmount);
+      newBaseReserves = baseReserves.add(inputAmountAfterFee);
       newQuoteReserves = quoteReserves.sub(expectedOut);
     }
 
@@ -533,7 +937,7 @@ export class AmmClient {
   }
 
   async getDecimals(mint: PublicKey): Promise<number> {
-    return unpackMint(mint, await this.provider.connection.getAccountInfo(mint))
-      .decimals;
+    const mintInfo = await this.provider.connection.getAccountInfo(mint);
+    return unpackMint(mint, mintInfo, mintInfo.owner).decimals;
   }
 }
@@ -21,7 +21,7 @@ import {
   TransactionInstruction,
 } from "@solana/web3.js";
 
-import { AmmClient, createMint } from "./AmmClient";
+import { AmmClient, CreateAmmArgs, createMint } from "./AmmClient";
 import {
   AUTOCRAT_PROGRAM_ID,
   CONDITIONAL_VAULT_PROGRAM_ID,
@@ -127,19 +127,23 @@ export class AutocratClient {
     passAmm: AmmAccount;
     failAmm: AmmAccount;
   }> {
+    const storedDao = await this.getDao(dao);
+
     const passAmmKp = Keypair.generate();
     const failAmmKp = Keypair.generate();
 
     const [passAmm, _1] = getAmmAddr(
       this.ammClient.program.programId,
       passAmmKp.publicKey,
-      quoteMint
+      quoteMint,
+      this.provider.publicKey
     );
 
     const [failAmm, _] = getAmmAddr(
       this.ammClient.program.programId,
       failAmmKp.publicKey,
-      quoteMint
+      quoteMint,
+      this.provider.publicKey
     );
     const passBaseMint = await createMint(
       this.provider.connection,
@@ -153,8 +157,8 @@ export class AutocratClient {
           )
         )
       ),
-      passAmm,
-      passAmm,
+      this.provider.publicKey,
+      this.provider.publicKey,
       6,
       passAmmKp
     );
@@ -170,24 +174,16 @@ export class AutocratClient {
           )
         )
       ),
-      failAmm,
-      failAmm,
+      this.provider.publicKey,
+      this.provider.publicKey,
       6,
       failAmmKp
     );
 
     this.ammClient.createAmm(
-      proposal,
+      this.proposalAmmArgs(proposal, storedDao),
       passBaseMint,
       quoteMint,
-      500_000_000_000,
-
-      "p",
-      "http://google.com",
-      1,
-      undefined,
-      undefined,
-      undefined,
       [
         createAssociatedTokenAccountInstruction(
           this.provider.wallet.publicKey,
@@ -205,17 +201,9 @@ export class AutocratClient {
     );
 
     this.ammClient.createAmm(
-      proposal,
+      this.proposalAmmArgs(proposal, storedDao),
       failBaseMint,
       quoteMint,
-      500_000_000_000,
-
-      "p",
-      "http://google.com",
-      1,
-      undefined,
-      undefined,
-      undefined,
       [
         createAssociatedTokenAccountInstruction(
           this.provider.wallet.publicKey,
@@ -241,6 +229,34 @@ export class AutocratClient {
     };
   }
 
+  /**
+   * The proposal's markets have to match the DAO's market settings, start
+   * without virtual liquidity, pay their fees to the DAO's treasury and be
+   * settled by it.
+   */
+  proposalAmmArgs(proposal: PublicKey, storedDao: any): CreateAmmArgs {
+    return {
+      pof: "",
+      uri: "",
+      proposal,
+      proposalNumber: storedDao.proposalCount + 1,
+      settlementAuthority: storedDao.treasury,
+      symbol: "",
+      twapInitialObservation: storedDao.twapInitialObservation,
+      twapMaxObservationChangePerUpdate:
+        storedDao.twapMaxObservationChangePerUpdate,
+      feeBps: storedDao.ammFeeBps,
+      feeRecipient: storedDao.treasury,
+      winnerPremiumBps: storedDao.winnerPremiumBps,
+      loserHaircutBps: storedDao.loserHaircutBps,
+      initialVBaseReserves: new BN(0),
+      initialVQuoteReserves: new BN(0),
+      graduationThreshold: storedDao.ammGraduationThreshold,
+      graduationTarget: storedDao.graduationTarget,
+      baseMintDecimals: 6,
+    };
+  }
+
   async initializeDao(
     tokenMint: PublicKey,
     tokenPriceUiAmount: number,
@@ -375,13 +391,15 @@ export class AutocratClient {
     const [amm1, bump1] = getAmmAddr(
       this.ammClient.program.programId,
       passBaseMint,
-      passQuoteMint
+      passQuoteMint,
+      this.provider.publicKey
     );
 
     const [amm2, bump2] = getAmmAddr(
       this.ammClient.program.programId,
       failBaseMint,
-      failQuoteMint
+      failQuoteMint,
+      this.provider.publicKey
     );
 
     // this is how many original tokens are created
@@ -14,8 +14,8 @@ export type Amm = {
   };
   instructions: [
     {
-      name: "createAmm";
-      discriminator: [242, 91, 21, 170, 5, 68, 125, 64];
+      name: "addLiquidity";
+      discriminator: [181, 157, 89, 67, 143, 182, 52, 72];
       accounts: [
         {
           name: "user";
@@ -25,19 +25,19 @@ export type Amm = {
         {
           name: "amm";
           writable: true;
+        },
+        {
+          name: "lpMint";
+          writable: true;
           pda: {
             seeds: [
               {
                 kind: "const";
-                value: [97, 109, 109, 95, 95];
-              },
-              {
-                kind: "account";
-                path: "baseMint";
+                value: [97, 109, 109, 95, 108, 112, 95, 109, 105, 110, 116];
               },
               {
                 kind: "account";
-                path: "quoteMint";
+                path: "amm";
               }
             ];
           };
@@ -50,319 +50,1830 @@ export type Amm = {
           name: "quoteMint";
         },
         {
-          name: "vaultAtaBase";
+          name: "userLpAccount";
           writable: true;
         },
         {
-          name: "vaultAtaQuote";
+          name: "userBaseAccount";
           writable: true;
         },
         {
-          name: "associatedTokenProgram";
-          address: "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+          name: "userQuoteAccount";
+          writable: true;
         },
         {
-          name: "tokenProgram";
-          address: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+          name: "vaultAtaBase";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "baseTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "baseMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
         },
         {
-          name: "systemProgram";
-          address: "11111111111111111111111111111111";
+          name: "vaultAtaQuote";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "quoteTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "quoteMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
         },
         {
-          name: "baseTokenMetadata";
-          writable: true;
+          name: "baseTokenProgram";
         },
         {
-          name: "metadataProgram";
-          address: "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+          name: "quoteTokenProgram";
         },
         {
-          name: "rent";
-          address: "SysvarRent111111111111111111111111111111111";
+          name: "lpTokenProgram";
+          address: "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
         }
       ];
       args: [
         {
-          name: "pof";
-          type: "string";
-        },
-        {
-          name: "uri";
-          type: "string";
-        },
-        {
-          name: "proposalNumber";
-          type: "u16";
-        },
-        {
-          name: "symbol";
-          type: "string";
-        },
-        {
-          name: "a";
-          type: "u8";
+          name: "args";
+          type: {
+            defined: {
+              name: "addLiquidityArgs";
+            };
+          };
         }
       ];
     },
     {
-      name: "swap";
-      discriminator: [248, 198, 158, 145, 225, 117, 135, 200];
+      name: "closeMarket";
+      discriminator: [88, 154, 248, 186, 48, 14, 123, 244];
       accounts: [
         {
-          name: "user";
+          name: "amm";
           writable: true;
-          signer: true;
         },
         {
-          name: "amm";
+          name: "creator";
           writable: true;
         },
         {
-          name: "userBaseAccount";
-          writable: true;
+          name: "feeRecipient";
         },
         {
-          name: "userQuoteAccount";
+          name: "baseMint";
           writable: true;
         },
         {
-          name: "vaultAtaBase";
-          writable: true;
+          name: "quoteMint";
         },
         {
-          name: "vaultAtaQuote";
+          name: "feeRecipientBaseAccount";
           writable: true;
         },
         {
-          name: "tokenProgram";
-          address: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+          name: "feeRecipientQuoteAccount";
+          writable: true;
         },
         {
-          name: "token2022Program";
-          address: "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+          name: "lpMint";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 109, 109, 95, 108, 112, 95, 109, 105, 110, 116];
+              },
+              {
+                kind: "account";
+                path: "amm";
+              }
+            ];
+          };
         },
         {
-          name: "baseMint";
+          name: "vaultAtaBase";
           writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "baseTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "baseMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
         },
         {
-          name: "quoteMint";
+          name: "vaultAtaQuote";
           writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "quoteTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "quoteMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
         },
         {
-          name: "raydiumCpSwapProgram";
-          address: "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+          name: "baseTokenProgram";
         },
         {
-          name: "ammConfig";
+          name: "quoteTokenProgram";
         },
         {
-          name: "authority";
-        },
+          name: "lpTokenProgram";
+          address: "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "collectFees";
+      discriminator: [164, 152, 207, 99, 30, 186, 19, 182];
+      accounts: [
         {
-          name: "poolAccount";
+          name: "amm";
           writable: true;
         },
         {
-          name: "associatedTokenProgram";
-          address: "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+          name: "feeRecipient";
         },
         {
-          name: "systemProgram";
-          address: "11111111111111111111111111111111";
+          name: "baseMint";
         },
         {
-          name: "rent";
-          address: "SysvarRent111111111111111111111111111111111";
+          name: "quoteMint";
         },
         {
-          name: "token0Vault";
+          name: "feeRecipientBaseAccount";
           writable: true;
         },
         {
-          name: "token1Vault";
+          name: "feeRecipientQuoteAccount";
           writable: true;
         },
         {
-          name: "createLpAccount";
+          name: "vaultAtaBase";
           writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "baseTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "baseMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
         },
         {
-          name: "createPoolFee";
+          name: "vaultAtaQuote";
           writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "quoteTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "quoteMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
         },
         {
-          name: "observationKey";
-          writable: true;
+          name: "baseTokenProgram";
         },
         {
-          name: "lpMint";
-          writable: true;
+          name: "quoteTokenProgram";
         }
       ];
-      args: [
+      args: [];
+    },
+    {
+      name: "crankThatTwap";
+      discriminator: [220, 100, 25, 249, 0, 92, 195, 193];
+      accounts: [
         {
-          name: "args";
-          type: {
-            defined: {
-              name: "swapArgs";
-            };
-          };
+          name: "amm";
+          writable: true;
         }
       ];
-    }
-  ];
-  accounts: [
-    {
-      name: "amm";
-      discriminator: [143, 245, 200, 17, 74, 214, 196, 135];
-    }
-  ];
-  errors: [
-    {
-      code: 6000;
-      name: "noSlotsPassed";
-      msg: "Can't get a TWAP before some observations have been stored";
-    },
-    {
-      code: 6001;
-      name: "noReserves";
-      msg: "Can't swap through a pool without token reserves on either side";
-    },
-    {
-      code: 6002;
-      name: "inputAmountOverflow";
-      msg: "Input token amount is too large for a swap, causes overflow";
-    },
-    {
-      code: 6003;
-      name: "addLiquidityCalculationError";
-      msg: "Add liquidity calculation error";
-    },
-    {
-      code: 6004;
-      name: "decimalScaleError";
-      msg: "Error in decimal scale conversion";
-    },
-    {
-      code: 6005;
-      name: "sameTokenMints";
-      msg: "You can't create an AMM pool where the token mints are the same";
-    },
-    {
-      code: 6006;
-      name: "swapSlippageExceeded";
-      msg: "A user wouldn't have gotten back their `output_amount_min`, reverting";
-    },
-    {
-      code: 6007;
-      name: "insufficientBalance";
-      msg: "The user had insufficient balance to do this";
+      args: [];
     },
     {
-      code: 6008;
-      name: "zeroLiquidityRemove";
-      msg: "Must remove a non-zero amount of liquidity";
-    },
-    {
-      code: 6009;
-      name: "zeroLiquidityToAdd";
-      msg: "Cannot add liquidity with 0 tokens on either side";
+      name: "createAmm";
+      discriminator: [242, 91, 21, 170, 5, 68, 125, 64];
+      accounts: [
+        {
+          name: "user";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "amm";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 109, 109, 95, 95];
+              },
+              {
+                kind: "account";
+                path: "baseMint";
+              },
+              {
+                kind: "account";
+                path: "quoteMint";
+              },
+              {
+                kind: "account";
+                path: "user";
+              }
+            ];
+          };
+        },
+        {
+          name: "baseMint";
+          docs: [
+            "Either a fresh mint whose mint and freeze authority is the amm, or an",
+            "existing mint such as one of a conditional vault's. See `BaseSupply`."
+          ];
+          writable: true;
+        },
+        {
+          name: "quoteMint";
+        },
+        {
+          name: "lpMint";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 109, 109, 95, 108, 112, 95, 109, 105, 110, 116];
+              },
+              {
+                kind: "account";
+                path: "amm";
+              }
+            ];
+          };
+        },
+        {
+          name: "vaultAtaBase";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "baseTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "baseMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "vaultAtaQuote";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "quoteTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "quoteMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "associatedTokenProgram";
+          address: "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+        },
+        {
+          name: "baseTokenProgram";
+          docs: [
+            "The base mint's program, which can be either SPL Token or Token-2022"
+          ];
+        },
+        {
+          name: "quoteTokenProgram";
+          docs: [
+            "The quote mint's program, which can be either SPL Token or Token-2022"
+          ];
+        },
+        {
+          name: "lpTokenProgram";
+          docs: [
+            "LP mints always live under Token-2022, so that `close_market` can",
+            "close them"
+          ];
+          address: "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        },
+        {
+          name: "baseTokenMetadata";
+          docs: ["mints base."];
+          writable: true;
+          optional: true;
+        },
+        {
+          name: "metadataProgram";
+          optional: true;
+          address: "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+        },
+        {
+          name: "rent";
+          address: "SysvarRent111111111111111111111111111111111";
+        }
+      ];
+      args: [
+        {
+          name: "args";
+          type: {
+            defined: {
+              name: "createAmmArgs";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "graduate";
+      discriminator: [45, 235, 225, 181, 17, 218, 64, 130];
+      accounts: [
+        {
+          name: "payer";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "amm";
+          writable: true;
+        },
+        {
+          name: "baseMint";
+          writable: true;
+        },
+        {
+          name: "quoteMint";
+        },
+        {
+          name: "lpMint";
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 109, 109, 95, 108, 112, 95, 109, 105, 110, 116];
+              },
+              {
+                kind: "account";
+                path: "amm";
+              }
+            ];
+          };
+        },
+        {
+          name: "vaultAtaBase";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "baseTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "baseMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "vaultAtaQuote";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "quoteTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "quoteMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "baseTokenProgram";
+        },
+        {
+          name: "quoteTokenProgram";
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "removeLiquidity";
+      discriminator: [80, 85, 209, 72, 24, 206, 177, 108];
+      accounts: [
+        {
+          name: "user";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "amm";
+          writable: true;
+        },
+        {
+          name: "lpMint";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [97, 109, 109, 95, 108, 112, 95, 109, 105, 110, 116];
+              },
+              {
+                kind: "account";
+                path: "amm";
+              }
+            ];
+          };
+        },
+        {
+          name: "baseMint";
+          writable: true;
+        },
+        {
+          name: "quoteMint";
+        },
+        {
+          name: "userLpAccount";
+          writable: true;
+        },
+        {
+          name: "userBaseAccount";
+          writable: true;
+        },
+        {
+          name: "userQuoteAccount";
+          writable: true;
+        },
+        {
+          name: "vaultAtaBase";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "baseTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "baseMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "vaultAtaQuote";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "quoteTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "quoteMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "baseTokenProgram";
+        },
+        {
+          name: "quoteTokenProgram";
+        },
+        {
+          name: "lpTokenProgram";
+          address: "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+        }
+      ];
+      args: [
+        {
+          name: "args";
+          type: {
+            defined: {
+              name: "removeLiquidityArgs";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "settleMarket";
+      discriminator: [193, 153, 95, 216, 166, 6, 144, 217];
+      accounts: [
+        {
+          name: "settlementAuthority";
+          signer: true;
+        },
+        {
+          name: "amm";
+          writable: true;
+        }
+      ];
+      args: [
+        {
+          name: "newStatus";
+          type: {
+            defined: {
+              name: "marketStatus";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "swap";
+      discriminator: [248, 198, 158, 145, 225, 117, 135, 200];
+      accounts: [
+        {
+          name: "user";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "amm";
+          writable: true;
+        },
+        {
+          name: "userBaseAccount";
+          writable: true;
+        },
+        {
+          name: "userQuoteAccount";
+          writable: true;
+        },
+        {
+          name: "vaultAtaBase";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "baseTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "baseMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "vaultAtaQuote";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "quoteTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "quoteMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "baseMint";
+          writable: true;
+        },
+        {
+          name: "quoteMint";
+        },
+        {
+          name: "baseTokenProgram";
+        },
+        {
+          name: "quoteTokenProgram";
+        }
+      ];
+      args: [
+        {
+          name: "args";
+          type: {
+            defined: {
+              name: "swapArgs";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "swapExactOutput";
+      discriminator: [45, 99, 76, 242, 223, 112, 168, 162];
+      accounts: [
+        {
+          name: "user";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "amm";
+          writable: true;
+        },
+        {
+          name: "userBaseAccount";
+          writable: true;
+        },
+        {
+          name: "userQuoteAccount";
+          writable: true;
+        },
+        {
+          name: "vaultAtaBase";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "baseTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "baseMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "vaultAtaQuote";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "amm";
+              },
+              {
+                kind: "account";
+                path: "quoteTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "quoteMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "baseMint";
+          writable: true;
+        },
+        {
+          name: "quoteMint";
+        },
+        {
+          name: "baseTokenProgram";
+        },
+        {
+          name: "quoteTokenProgram";
+        }
+      ];
+      args: [
+        {
+          name: "args";
+          type: {
+            defined: {
+              name: "swapExactOutputArgs";
+            };
+          };
+        }
+      ];
+    }
+  ];
+  accounts: [
+    {
+      name: "amm";
+      discriminator: [143, 245, 200, 17, 74, 214, 196, 135];
+    }
+  ];
+  events: [
+    {
+      name: "createAmmEvent";
+      discriminator: [87, 213, 21, 39, 67, 210, 139, 177];
+    },
+    {
+      name: "graduationEvent";
+      discriminator: [10, 246, 223, 127, 48, 98, 149, 55];
+    },
+    {
+      name: "swapEvent";
+      discriminator: [64, 198, 205, 232, 38, 8, 113, 226];
+    }
+  ];
+  errors: [
+    {
+      code: 6000;
+      name: "noSlotsPassed";
+      msg: "Can't get a TWAP before some observations have been stored";
+    },
+    {
+      code: 6001;
+      name: "noReserves";
+      msg: "Can't swap through a pool without token reserves on either side";
+    },
+    {
+      code: 6002;
+      name: "inputAmountOverflow";
+      msg: "Input token amount is too large for a swap, causes overflow";
+    },
+    {
+      code: 6003;
+      name: "addLiquidityCalculationError";
+      msg: "Add liquidity calculation error";
+    },
+    {
+      code: 6004;
+      name: "decimalScaleError";
+      msg: "Error in decimal scale conversion";
+    },
+    {
+      code: 6005;
+      name: "sameTokenMints";
+      msg: "You can't create an AMM pool where the token mints are the same";
+    },
+    {
+      code: 6006;
+      name: "swapSlippageExceeded";
+      msg: "A user wouldn't have gotten back their `output_amount_min`, reverting";
+    },
+    {
+      code: 6007;
+      name: "insufficientBalance";
+      msg: "The user had insufficient balance to do this";
+    },
+    {
+      code: 6008;
+      name: "zeroLiquidityRemove";
+      msg: "Must remove a non-zero amount of liquidity";
+    },
+    {
+      code: 6009;
+      name: "zeroLiquidityToAdd";
+      msg: "Cannot add liquidity with 0 tokens on either side";
+    },
+    {
+      code: 6010;
+      name: "zeroMinLpTokens";
+      msg: "Must specify a non-zero `min_lp_tokens` when adding to an existing pool";
+    },
+    {
+      code: 6011;
+      name: "addLiquiditySlippageExceeded";
+      msg: "LP wouldn't have gotten back `lp_token_min`";
+    },
+    {
+      code: 6012;
+      name: "addLiquidityMaxBaseExceeded";
+      msg: "LP would have spent more than `max_base_amount`";
+    },
+    {
+      code: 6013;
+      name: "insufficientQuoteAmount";
+      msg: "`quote_amount` must be greater than 100000000 when initializing a pool";
+    },
+    {
+      code: 6014;
+      name: "zeroSwapAmount";
+      msg: "Users must swap a non-zero amount";
+    },
+    {
+      code: 6015;
+      name: "constantProductInvariantFailed";
+      msg: "K should always be increasing";
+    },
+    {
+      code: 6016;
+      name: "castingOverflow";
+      msg: "Casting has caused an overflow";
+    },
+    {
+      code: 6017;
+      name: "invalidSupply";
+      msg: "The pool has an invalid supply";
+    },
+    {
+      code: 6018;
+      name: "invalidMintAuthority";
+      msg: "The pool has an invalid mint authority";
+    },
+    {
+      code: 6019;
+      name: "buyDisabled";
+      msg: "The pool disabled buying";
+    },
+    {
+      code: 6020;
+      name: "sellDisabled";
+      msg: "The pool disabled selling";
+    },
+    {
+      code: 6021;
+      name: "invalidFeeBps";
+      msg: "`fee_bps` can't be more than `MAX_FEE_BPS`";
+    },
+    {
+      code: 6022;
+      name: "marketAlreadyFinalized";
+      msg: "The market has already been finalized";
+    },
+    {
+      code: 6023;
+      name: "marketNotFinalized";
+      msg: "The market must be finalized first";
+    },
+    {
+      code: 6024;
+      name: "losingMarketCannotGraduate";
+      msg: "Only a winning market can graduate";
+    },
+    {
+      code: 6025;
+      name: "marketNotGraduated";
+      msg: "A winning market must graduate before it can be closed";
+    },
+    {
+      code: 6026;
+      name: "marketAlreadyGraduated";
+      msg: "The market has already graduated";
+    },
+    {
+      code: 6027;
+      name: "marketClosed";
+      msg: "The market is closed";
+    },
+    {
+      code: 6028;
+      name: "marketCannotResumeTrading";
+      msg: "A market can't go back to trading once it has left it";
+    },
+    {
+      code: 6029;
+      name: "insufficientReserves";
+      msg: "The swap would take more out of the reserves than they hold";
+    },
+    {
+      code: 6030;
+      name: "insufficientVaultBalance";
+      msg: "The vault doesn't hold enough tokens to cover the payout";
+    },
+    {
+      code: 6031;
+      name: "invalidSettlementBps";
+      msg: "`winner_premium_bps` and `loser_haircut_bps` can't be more than `MAX_BPS`";
     },
     {
-      code: 6010;
-      name: "zeroMinLpTokens";
-      msg: "Must specify a non-zero `min_lp_tokens` when adding to an existing pool";
+      code: 6032;
+      name: "marketNotTrading";
+      msg: "Liquidity can only be added while the market is trading";
     },
     {
-      code: 6011;
-      name: "addLiquiditySlippageExceeded";
-      msg: "LP wouldn't have gotten back `lp_token_min`";
+      code: 6033;
+      name: "liquidityNotWithdrawable";
+      msg: "Liquidity can't be withdrawn once the market has closed";
     },
     {
-      code: 6012;
-      name: "addLiquidityMaxBaseExceeded";
-      msg: "LP would have spent more than `max_base_amount`";
+      code: 6034;
+      name: "removeLiquiditySlippageExceeded";
+      msg: "LP wouldn't have gotten back `min_base_amount` or `min_quote_amount`";
     },
     {
-      code: 6013;
-      name: "insufficientQuoteAmount";
-      msg: "`quote_amount` must be greater than 100000000 when initializing a pool";
+      code: 6035;
+      name: "invalidVirtualReserves";
+      msg: "Virtual reserves must be at least one whole token on each side if the amm mints base, and zero otherwise";
     },
     {
-      code: 6014;
-      name: "zeroSwapAmount";
-      msg: "Users must swap a non-zero amount";
+      code: 6036;
+      name: "invalidGraduationThreshold";
+      msg: "`graduation_threshold` must be above `initial_v_quote_reserves`";
     },
     {
-      code: 6015;
-      name: "constantProductInvariantFailed";
-      msg: "K should always be increasing";
+      code: 6037;
+      name: "graduationThresholdNotReached";
+      msg: "The market's quote reserves haven't reached `graduation_threshold`";
+    },
+    {
+      code: 6038;
+      name: "nothingToGraduate";
+      msg: "There is no liquidity to migrate";
+    },
+    {
+      code: 6039;
+      name: "invalidGraduationTarget";
+      msg: "A `Recipient` graduation target can't be the default pubkey";
+    },
+    {
+      code: 6040;
+      name: "invalidGraduationAccount";
+      msg: "An account passed to `graduate` doesn't match the market's graduation target";
+    },
+    {
+      code: 6041;
+      name: "baseMintTransferFee";
+      msg: "The base mint can't have a Token-2022 transfer fee";
+    },
+    {
+      code: 6042;
+      name: "swapInputMaxExceeded";
+      msg: "A user would have spent more than their `input_amount_max`, reverting";
+    },
+    {
+      code: 6043;
+      name: "outputAmountUnreachable";
+      msg: "No input can swap into this `output_amount`";
+    },
+    {
+      code: 6044;
+      name: "marketRefunding";
+      msg: "The market is in refund-only mode and can only be closed";
+    },
+    {
+      code: 6045;
+      name: "marketCannotRefund";
+      msg: "Only a trading market can switch to refund-only mode";
+    },
+    {
+      code: 6046;
+      name: "invalidSettlementAuthority";
+      msg: "Only the market's settlement authority can settle it";
+    },
+    {
+      code: 6047;
+      name: "invalidSettlementStatus";
+      msg: "Markets can only be settled as finalized or refunding";
+    },
+    {
+      code: 6048;
+      name: "marketNotEmpty";
+      msg: "A market can only be closed once its LPs, and the holders of the base it minted, are out";
+    },
+    {
+      code: 6049;
+      name: "invalidBaseMintAuthority";
+      msg: "A base mint that the amm mints must also have the amm as its freeze authority";
+    },
+    {
+      code: 6050;
+      name: "missingMetadataAccounts";
+      msg: "The metadata accounts are required when the amm mints base";
+    },
+    {
+      code: 6051;
+      name: "belowInitialPrice";
+      msg: "Sells can't take the price below the initial price of the virtual reserves";
+    }
+  ];
+  types: [
+    {
+      name: "addLiquidityArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "quoteAmount";
+            docs: ["How much quote token you will deposit to the pool"];
+            type: "u64";
+          },
+          {
+            name: "maxBaseAmount";
+            docs: [
+              "The maximum base token you will deposit to the pool. The first deposit",
+              "into an empty pool deposits exactly this much, which sets the price."
+            ];
+            type: "u64";
+          },
+          {
+            name: "minLpTokens";
+            docs: ["The minimum LP token you will get back"];
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "amm";
+      serialization: "bytemuckunsafe";
+      repr: {
+        kind: "rust";
+        packed: true;
+      };
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "bump";
+            type: "u8";
+          },
+          {
+            name: "createdAtSlot";
+            type: "u64";
+          },
+          {
+            name: "creator";
+            docs: [
+              "Who created the market. It's part of the amm's address, so nobody can",
+              "squat on a mint pair that someone else needs a market for."
+            ];
+            type: "pubkey";
+          },
+          {
+            name: "proposal";
+            docs: [
+              "The futarchy proposal this market trades, and its number. Autocrat",
+              "only accepts markets created for the proposal being initialized."
+            ];
+            type: "pubkey";
+          },
+          {
+            name: "proposalNumber";
+            type: "u32";
+          },
+          {
+            name: "settlementAuthority";
+            docs: [
+              "The only account that can finalize the market or switch it to",
+              "refund-only mode, through `settle_market`. For proposals, this is the",
+              "DAO's treasury."
+            ];
+            type: "pubkey";
+          },
+          {
+            name: "baseMint";
+            type: "pubkey";
+          },
+          {
+            name: "quoteMint";
+            type: "pubkey";
+          },
+          {
+            name: "baseSupply";
+            type: {
+              defined: {
+                name: "baseSupply";
+              };
+            };
+          },
+          {
+            name: "lpMint";
+            docs: ["PDA of `[AMM_LP_MINT_SEED_PREFIX, amm]`, owned by the amm"];
+            type: "pubkey";
+          },
+          {
+            name: "virtualLpSupply";
+            docs: [
+              "LP tokens that represent the virtual reserves the curve starts with.",
+              "They are never minted, so nobody can withdraw that liquidity, but they",
+              "count towards the total LP supply. Real LPs own what they deposited",
+              "plus their share of what traders paid in or took out since, and sells",
+              "can't take the price below the initial price, which keeps the virtual",
+              "share of the quote backed by what traders paid in."
+            ];
+            type: "u64";
+          },
+          {
+            name: "baseMintDecimals";
+            type: "u8";
+          },
+          {
+            name: "quoteMintDecimals";
+            type: "u8";
+          },
+          {
+            name: "vQuoteReserves";
+            type: "u64";
+          },
+          {
+            name: "vBaseReserves";
+            type: "u64";
+          },
+          {
+            name: "initialVQuoteReserves";
+            docs: [
+              "The virtual reserves the curve starts with, which also set the lowest",
+              "price that sells can reach"
+            ];
+            type: "u64";
+          },
+          {
+            name: "initialVBaseReserves";
+            type: "u64";
+          },
+          {
+            name: "graduationThreshold";
+            docs: [
+              "The market can graduate once `v_quote_reserves` reach this amount"
+            ];
+            type: "u64";
+          },
+          {
+            name: "graduationTarget";
+            type: {
+              defined: {
+                name: "graduationTarget";
+              };
+            };
+          },
+          {
+            name: "graduatedPool";
+            docs: [
+              "The pool or recipient that `graduate` migrated the liquidity to, or the",
+              "default pubkey if the market hasn't graduated"
+            ];
+            type: "pubkey";
+          },
+          {
+            name: "status";
+            type: {
+              defined: {
+                name: "marketStatus";
+              };
+            };
+          },
+          {
+            name: "oracle";
+            type: {
+              defined: {
+                name: "twapOracle";
+              };
+            };
+          },
+          {
+            name: "feeBps";
+            docs: ["The swap fee, taken out of the input amount"];
+            type: "u16";
+          },
+          {
+            name: "feeRecipient";
+            docs: ["Who `collect_fees` pays out to"];
+            type: "pubkey";
+          },
+          {
+            name: "baseFeesAccrued";
+            docs: [
+              "Fees that have been charged but not yet collected. They sit in the",
+              "vaults but are not part of the reserves."
+            ];
+            type: "u64";
+          },
+          {
+            name: "quoteFeesAccrued";
+            type: "u64";
+          },
+          {
+            name: "winnerPremiumBps";
+            docs: [
+              "How much more than the curve price sellers of a winning market get"
+            ];
+            type: "u16";
+          },
+          {
+            name: "loserHaircutBps";
+            docs: [
+              "How much less than the curve price sellers of a losing market get"
+            ];
+            type: "u16";
+          }
+        ];
+      };
+    },
+    {
+      name: "baseSupply";
+      docs: ["Where the base that a market sells comes from."];
+      repr: {
+        kind: "rust";
+      };
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "minted";
+          },
+          {
+            name: "deposited";
+          }
+        ];
+      };
+    },
+    {
+      name: "createAmmArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "pof";
+            docs: [
+              "`pof`, `uri` and `symbol` name a base mint that the amm mints, and",
+              "are ignored otherwise"
+            ];
+            type: "string";
+          },
+          {
+            name: "uri";
+            type: "string";
+          },
+          {
+            name: "proposal";
+            docs: [
+              "The proposal PDA this market is created for, which can be derived",
+              "before the proposal exists"
+            ];
+            type: "pubkey";
+          },
+          {
+            name: "proposalNumber";
+            type: "u32";
+          },
+          {
+            name: "settlementAuthority";
+            type: "pubkey";
+          },
+          {
+            name: "symbol";
+            type: "string";
+          },
+          {
+            name: "twapInitialObservation";
+            type: "u128";
+          },
+          {
+            name: "twapMaxObservationChangePerUpdate";
+            type: "u128";
+          },
+          {
+            name: "feeBps";
+            type: "u16";
+          },
+          {
+            name: "feeRecipient";
+            type: "pubkey";
+          },
+          {
+            name: "winnerPremiumBps";
+            type: "u16";
+          },
+          {
+            name: "loserHaircutBps";
+            type: "u16";
+          },
+          {
+            name: "initialVBaseReserves";
+            docs: [
+              "Both in raw token units, and at least one whole token each if the amm",
+              "mints base. Zero if it doesn't, in which case LPs seed the price."
+            ];
+            type: "u64";
+          },
+          {
+            name: "initialVQuoteReserves";
+            type: "u64";
+          },
+          {
+            name: "graduationThreshold";
+            docs: ["Must be above `initial_v_quote_reserves`"];
+            type: "u64";
+          },
+          {
+            name: "graduationTarget";
+            type: {
+              defined: {
+                name: "graduationTarget";
+              };
+            };
+          },
+          {
+            name: "baseMintDecimals";
+            type: "u8";
+          }
+        ];
+      };
     },
     {
-      code: 6016;
-      name: "castingOverflow";
-      msg: "Casting has caused an overflow";
+      name: "createAmmEvent";
+      docs: [
+        "Prices are quote units per base unit scaled by `PRICE_SCALE`, like the",
+        "oracle's. Reserves and prices are as of after the event."
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "user";
+            type: "pubkey";
+          },
+          {
+            name: "amm";
+            type: "pubkey";
+          },
+          {
+            name: "baseMint";
+            type: "pubkey";
+          },
+          {
+            name: "quoteMint";
+            type: "pubkey";
+          },
+          {
+            name: "lpMint";
+            type: "pubkey";
+          },
+          {
+            name: "proposal";
+            type: "pubkey";
+          },
+          {
+            name: "proposalNumber";
+            type: "u32";
+          },
+          {
+            name: "vBaseReserves";
+            type: "u64";
+          },
+          {
+            name: "vQuoteReserves";
+            type: "u64";
+          },
+          {
+            name: "price";
+            type: "u128";
+          },
+          {
+            name: "graduationThreshold";
+            type: "u64";
+          },
+          {
+            name: "graduationTarget";
+            type: {
+              defined: {
+                name: "graduationTarget";
+              };
+            };
+          },
+          {
+            name: "feeBps";
+            type: "u16";
+          },
+          {
+            name: "slot";
+            type: "u64";
+          }
+        ];
+      };
     },
     {
-      code: 6017;
-      name: "invalidSupply";
-      msg: "The pool has an invalid supply";
+      name: "graduationEvent";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "payer";
+            type: "pubkey";
+          },
+          {
+            name: "amm";
+            type: "pubkey";
+          },
+          {
+            name: "graduationTarget";
+            type: {
+              defined: {
+                name: "graduationTarget";
+              };
+            };
+          },
+          {
+            name: "graduatedPool";
+            type: "pubkey";
+          },
+          {
+            name: "baseAmount";
+            type: "u64";
+          },
+          {
+            name: "quoteAmount";
+            type: "u64";
+          },
+          {
+            name: "vBaseReserves";
+            type: "u64";
+          },
+          {
+            name: "vQuoteReserves";
+            type: "u64";
+          },
+          {
+            name: "price";
+            type: "u128";
+          },
+          {
+            name: "slot";
+            type: "u64";
+          }
+        ];
+      };
     },
     {
-      code: 6018;
-      name: "invalidMintAuthority";
-      msg: "The pool has an invalid mint authority";
+      name: "graduationTarget";
+      docs: [
+        "Where a market's liquidity goes when it graduates. Each target has its own",
+        "set of accounts that `graduate` expects after the common ones."
+      ];
+      repr: {
+        kind: "rust";
+      };
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "raydiumCpSwap";
+          },
+          {
+            name: "recipient";
+            fields: ["pubkey"];
+          }
+        ];
+      };
     },
     {
-      code: 6019;
-      name: "buyDisabled";
-      msg: "The pool disabled buying";
+      name: "marketOutcome";
+      docs: ["Which side of the proposal a market ended up on."];
+      repr: {
+        kind: "rust";
+      };
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "winning";
+          },
+          {
+            name: "losing";
+          }
+        ];
+      };
     },
     {
-      code: 6020;
-      name: "sellDisabled";
-      msg: "The pool disabled selling";
-    }
-  ];
-  types: [
-    {
-      name: "amm";
-      serialization: "bytemuckunsafe";
+      name: "marketStatus";
+      docs: [
+        "The lifecycle of a market. The only legal transitions are:",
+        "- Trading -> Finalized",
+        "- Trading -> Refunding",
+        "- Finalized(Winning) -> Graduated",
+        "- Finalized(Winning) -> Closed, if there's no virtual liquidity to graduate",
+        "- Finalized(Losing) -> Closed",
+        "- Graduated -> Closed",
+        "- Refunding -> Closed",
+        "",
+        "`repr(u8)` pins the layout so that it can live inside the zero-copy `Amm`.",
+        "A zeroed status is `Trading`."
+      ];
       repr: {
         kind: "rust";
-        packed: true;
       };
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "trading";
+          },
+          {
+            name: "finalized";
+            fields: [
+              {
+                defined: {
+                  name: "marketOutcome";
+                };
+              }
+            ];
+          },
+          {
+            name: "graduated";
+          },
+          {
+            name: "closed";
+          },
+          {
+            name: "refunding";
+          }
+        ];
+      };
+    },
+    {
+      name: "removeLiquidityArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "lpTokensToBurn";
+            type: "u64";
+          },
+          {
+            name: "minQuoteAmount";
+            type: "u64";
+          },
+          {
+            name: "minBaseAmount";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "swapArgs";
       type: {
         kind: "struct";
         fields: [
           {
-            name: "bump";
-            type: "u8";
+            name: "swapType";
+            type: {
+              defined: {
+                name: "swapType";
+              };
+            };
           },
           {
-            name: "createdAtSlot";
+            name: "inputAmount";
             type: "u64";
           },
           {
-            name: "baseMint";
+            name: "outputAmountMin";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "swapEvent";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "user";
             type: "pubkey";
           },
           {
-            name: "quoteMint";
+            name: "amm";
             type: "pubkey";
           },
           {
-            name: "baseMintDecimals";
-            type: "u8";
+            name: "swapType";
+            type: {
+              defined: {
+                name: "swapType";
+              };
+            };
           },
           {
-            name: "quoteMintDecimals";
-            type: "u8";
+            name: "inputAmount";
+            docs: ["What left the user, before any transfer fee"];
+            type: "u64";
           },
           {
-            name: "vQuoteReserves";
+            name: "outputAmount";
+            docs: ["What left the vault, before any transfer fee"];
+            type: "u64";
+          },
+          {
+            name: "feeAmount";
             type: "u64";
           },
           {
@@ -370,14 +1881,22 @@ export type Amm = {
             type: "u64";
           },
           {
-            name: "vaultStatus";
-            type: "u8";
+            name: "vQuoteReserves";
+            type: "u64";
+          },
+          {
+            name: "price";
+            type: "u128";
+          },
+          {
+            name: "slot";
+            type: "u64";
           }
         ];
       };
     },
     {
-      name: "swapArgs";
+      name: "swapExactOutputArgs";
       type: {
         kind: "struct";
         fields: [
@@ -390,11 +1909,12 @@ export type Amm = {
             };
           },
           {
-            name: "inputAmount";
+            name: "outputAmount";
+            docs: ["What the user receives, after any transfer fee"];
             type: "u64";
           },
           {
-            name: "outputAmountMin";
+            name: "inputAmountMax";
             type: "u64";
           }
         ];
@@ -413,6 +1933,64 @@ export type Amm = {
           }
         ];
       };
+    },
+    {
+      name: "twapOracle";
+      serialization: "bytemuckunsafe";
+      repr: {
+        kind: "rust";
+        packed: true;
+      };
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "lastUpdatedSlot";
+            type: "u64";
+          },
+          {
+            name: "lastPrice";
+            docs: [
+              "A price is the number of quote units per base unit multiplied by 1e12.",
+              "You cannot simply divide by 1e12 to get a price you can display in the UI",
+              "because the base and quote decimals may be different. Instead, do:",
+              "ui_price = (price * (10**(base_decimals - quote_decimals))) / 1e12"
+            ];
+            type: "u128";
+          },
+          {
+            name: "lastObservation";
+            docs: [
+              "If we did a raw TWAP over prices, someone could push the TWAP heavily with",
+              "a few extremely large outliers. So we use observations, which can only move",
+              "by `max_observation_change_per_update` per update."
+            ];
+            type: "u128";
+          },
+          {
+            name: "aggregator";
+            docs: [
+              "Running sum of slots_per_last_update * last_observation.",
+              "",
+              "Assuming latest observations are as big as possible (u64::MAX * 1e12),",
+              "we can store (2**128 / (2**64 * 1e12)) / (60 * 60 * 24 * 365 * 2.5) = 589",
+              "years of slots before this value overflows. It's wrapping anyway, so",
+              "consumers should only ever look at the difference between two aggregators."
+            ];
+            type: "u128";
+          },
+          {
+            name: "maxObservationChangePerUpdate";
+            docs: ["The most that an observation can change per update."];
+            type: "u128";
+          },
+          {
+            name: "initialObservation";
+            docs: ["What the initial `last_observation` is set to."];
+            type: "u128";
+          }
+        ];
+      };
     }
   ];
 };
@@ -13,6 +13,206 @@ export type Autocrat = {
     description: "SVM-based program for running futarchy";
   };
   instructions: [
+    {
+      name: "cancelProposal";
+      discriminator: [106, 74, 128, 146, 19, 65, 39, 23];
+      accounts: [
+        {
+          name: "proposal";
+          writable: true;
+        },
+        {
+          name: "proposer";
+          signer: true;
+          relations: ["proposal"];
+        },
+        {
+          name: "passAmm";
+          writable: true;
+          relations: ["proposal"];
+        },
+        {
+          name: "failAmm";
+          writable: true;
+          relations: ["proposal"];
+        },
+        {
+          name: "dao";
+          writable: true;
+          relations: ["proposal"];
+        },
+        {
+          name: "treasury";
+          relations: ["dao"];
+        },
+        {
+          name: "ammProgram";
+          address: "62BiVvL2o3dHYbSAjh1ywDTqC9rm7j9eg2PoRSSG9nEH";
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "closeProposal";
+      discriminator: [213, 178, 139, 19, 50, 191, 82, 245];
+      accounts: [
+        {
+          name: "proposal";
+          writable: true;
+          relations: ["proposalIndex"];
+        },
+        {
+          name: "proposalIndex";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  112, 114, 111, 112, 111, 115, 97, 108, 95, 105, 110, 100, 101,
+                  120
+                ];
+              },
+              {
+                kind: "account";
+                path: "dao";
+              },
+              {
+                kind: "account";
+                path: "proposal.number";
+                account: "proposal";
+              }
+            ];
+          };
+        },
+        {
+          name: "proposer";
+          writable: true;
+          signer: true;
+          relations: ["proposal"];
+        },
+        {
+          name: "dao";
+          relations: ["proposal"];
+        },
+        {
+          name: "passAmm";
+          relations: ["proposal"];
+        },
+        {
+          name: "failAmm";
+          relations: ["proposal"];
+        },
+        {
+          name: "passLpMint";
+          writable: true;
+        },
+        {
+          name: "failLpMint";
+          writable: true;
+        },
+        {
+          name: "passLpVaultAccount";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "proposal";
+              },
+              {
+                kind: "account";
+                path: "tokenProgram";
+              },
+              {
+                kind: "account";
+                path: "passLpMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "failLpVaultAccount";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "proposal";
+              },
+              {
+                kind: "account";
+                path: "tokenProgram";
+              },
+              {
+                kind: "account";
+                path: "failLpMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "tokenMint";
+          docs: ["Only needed if the proposal has a bond vault"];
+          writable: true;
+          optional: true;
+        },
+        {
+          name: "bondVaultAccount";
+          docs: ["Only needed if the proposal has a bond vault"];
+          writable: true;
+          optional: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "proposal";
+              },
+              {
+                kind: "account";
+                path: "bondTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "tokenMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "tokenProgram";
+        },
+        {
+          name: "bondTokenProgram";
+          optional: true;
+        }
+      ];
+      args: [];
+    },
     {
       name: "executeProposal";
       discriminator: [186, 60, 116, 133, 108, 128, 111, 28];
@@ -28,6 +228,21 @@ export type Autocrat = {
       ];
       args: [];
     },
+    {
+      name: "expireProposal";
+      discriminator: [21, 237, 43, 176, 1, 202, 146, 144];
+      accounts: [
+        {
+          name: "proposal";
+          writable: true;
+        },
+        {
+          name: "dao";
+          relations: ["proposal"];
+        }
+      ];
+      args: [];
+    },
     {
       name: "finalizeProposal";
       discriminator: [23, 68, 51, 167, 109, 173, 187, 164];
@@ -38,19 +253,40 @@ export type Autocrat = {
         },
         {
           name: "passAmm";
+          writable: true;
           relations: ["proposal"];
         },
         {
           name: "failAmm";
+          writable: true;
+          relations: ["proposal"];
+        },
+        {
+          name: "baseVault";
+          writable: true;
+          relations: ["proposal"];
+        },
+        {
+          name: "quoteVault";
+          writable: true;
           relations: ["proposal"];
         },
         {
           name: "dao";
+          writable: true;
           relations: ["proposal"];
         },
         {
           name: "treasury";
           relations: ["dao"];
+        },
+        {
+          name: "vaultProgram";
+          address: "VAU1T7S5UuEHmMvXtXMVmpEoQtZ2ya7eRb7gcN47wDp";
+        },
+        {
+          name: "ammProgram";
+          address: "62BiVvL2o3dHYbSAjh1ywDTqC9rm7j9eg2PoRSSG9nEH";
         }
       ];
       args: [];
@@ -75,9 +311,11 @@ export type Autocrat = {
         },
         {
           name: "tokenMint";
+          docs: ["SPL Token or Token-2022"];
         },
         {
           name: "usdcMint";
+          docs: ["SPL Token or Token-2022"];
         }
       ];
       args: [
@@ -119,6 +357,10 @@ export type Autocrat = {
           name: "dao";
           writable: true;
         },
+        {
+          name: "proposalIndex";
+          writable: true;
+        },
         {
           name: "failAmm";
         },
@@ -126,50 +368,373 @@ export type Autocrat = {
           name: "passAmm";
         },
         {
-          name: "proposer";
+          name: "baseVault";
+        },
+        {
+          name: "quoteVault";
+        },
+        {
+          name: "passLpMint";
+        },
+        {
+          name: "failLpMint";
+        },
+        {
+          name: "passLpUserAccount";
           writable: true;
-          signer: true;
         },
         {
-          name: "systemProgram";
-          address: "11111111111111111111111111111111";
-        }
-      ];
-      args: [
+          name: "failLpUserAccount";
+          writable: true;
+        },
         {
-          name: "params";
-          type: {
-            defined: {
-              name: "initializeProposalParams";
+          name: "passLpVaultAccount";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "proposal";
+              },
+              {
+                kind: "account";
+                path: "tokenProgram";
+              },
+              {
+                kind: "account";
+                path: "passLpMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
             };
           };
-        }
-      ];
-    },
-    {
-      name: "updateDao";
-      discriminator: [131, 72, 75, 25, 112, 210, 109, 2];
-      accounts: [
+        },
         {
-          name: "dao";
+          name: "failLpVaultAccount";
           writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "proposal";
+              },
+              {
+                kind: "account";
+                path: "tokenProgram";
+              },
+              {
+                kind: "account";
+                path: "failLpMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
         },
         {
-          name: "treasury";
-          signer: true;
-          relations: ["dao"];
-        }
-      ];
-      args: [
+          name: "proposerRecord";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  112, 114, 111, 112, 111, 115, 101, 114, 95, 114, 101, 99, 111,
+                  114, 100
+                ];
+              },
+              {
+                kind: "account";
+                path: "dao";
+              },
+              {
+                kind: "account";
+                path: "proposer";
+              }
+            ];
+          };
+        },
         {
-          name: "daoParams";
-          type: {
-            defined: {
+          name: "tokenMint";
+        },
+        {
+          name: "bondUserAccount";
+          docs: ["Only needed if the DAO has a `proposal_bond`"];
+          writable: true;
+          optional: true;
+        },
+        {
+          name: "bondVaultAccount";
+          docs: ["Only needed if the DAO has a `proposal_bond`"];
+          writable: true;
+          optional: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "proposal";
+              },
+              {
+                kind: "account";
+                path: "bondTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "tokenMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "proposer";
+          writable: true;
+          signer: true;
+        },
+        {
+          name: "tokenProgram";
+          docs: ["The markets' token program, which owns their LP mints"];
+        },
+        {
+          name: "bondTokenProgram";
+          docs: ["`dao.token_mint` can be an SPL Token or a Token-2022 mint"];
+        },
+        {
+          name: "associatedTokenProgram";
+          address: "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+        },
+        {
+          name: "systemProgram";
+          address: "11111111111111111111111111111111";
+        }
+      ];
+      args: [
+        {
+          name: "params";
+          type: {
+            defined: {
+              name: "initializeProposalParams";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "settleProposalBond";
+      discriminator: [25, 250, 254, 212, 161, 19, 191, 15];
+      accounts: [
+        {
+          name: "proposal";
+          writable: true;
+        },
+        {
+          name: "dao";
+          relations: ["proposal"];
+        },
+        {
+          name: "tokenMint";
+        },
+        {
+          name: "bondVaultAccount";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "proposal";
+              },
+              {
+                kind: "account";
+                path: "tokenProgram";
+              },
+              {
+                kind: "account";
+                path: "tokenMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "recipientAccount";
+          docs: [
+            "The treasury's token account if the bond was slashed, and the",
+            "proposer's otherwise"
+          ];
+          writable: true;
+        },
+        {
+          name: "tokenProgram";
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "unlockProposalLp";
+      discriminator: [234, 242, 115, 200, 231, 50, 176, 169];
+      accounts: [
+        {
+          name: "proposal";
+          writable: true;
+        },
+        {
+          name: "proposer";
+          signer: true;
+          relations: ["proposal"];
+        },
+        {
+          name: "passAmm";
+          relations: ["proposal"];
+        },
+        {
+          name: "failAmm";
+          relations: ["proposal"];
+        },
+        {
+          name: "passLpMint";
+        },
+        {
+          name: "failLpMint";
+        },
+        {
+          name: "passLpVaultAccount";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "proposal";
+              },
+              {
+                kind: "account";
+                path: "tokenProgram";
+              },
+              {
+                kind: "account";
+                path: "passLpMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "failLpVaultAccount";
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "proposal";
+              },
+              {
+                kind: "account";
+                path: "tokenProgram";
+              },
+              {
+                kind: "account";
+                path: "failLpMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
+        },
+        {
+          name: "passLpUserAccount";
+          writable: true;
+        },
+        {
+          name: "failLpUserAccount";
+          writable: true;
+        },
+        {
+          name: "tokenProgram";
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "updateDao";
+      discriminator: [131, 72, 75, 25, 112, 210, 109, 2];
+      accounts: [
+        {
+          name: "dao";
+          writable: true;
+        },
+        {
+          name: "treasury";
+          signer: true;
+          relations: ["dao"];
+        }
+      ];
+      args: [
+        {
+          name: "daoParams";
+          type: {
+            defined: {
               name: "updateDaoParams";
             };
           };
         }
       ];
+    },
+    {
+      name: "vetoProposal";
+      discriminator: [177, 197, 208, 96, 169, 68, 23, 162];
+      accounts: [
+        {
+          name: "proposal";
+          writable: true;
+        },
+        {
+          name: "dao";
+          relations: ["proposal"];
+        },
+        {
+          name: "guardian";
+          signer: true;
+        }
+      ];
+      args: [];
     }
   ];
   accounts: [
@@ -177,6 +742,10 @@ export type Autocrat = {
       name: "amm";
       discriminator: [143, 245, 200, 17, 74, 214, 196, 135];
     },
+    {
+      name: "conditionalVault";
+      discriminator: [63, 132, 87, 98, 36, 51, 175, 247];
+    },
     {
       name: "dao";
       discriminator: [163, 9, 47, 31, 52, 85, 197, 49];
@@ -184,6 +753,44 @@ export type Autocrat = {
     {
       name: "proposal";
       discriminator: [26, 94, 189, 187, 116, 136, 53, 33];
+    },
+    {
+      name: "proposalIndex";
+      discriminator: [83, 97, 143, 58, 176, 46, 177, 195];
+    },
+    {
+      name: "proposerRecord";
+      discriminator: [239, 77, 160, 78, 99, 50, 69, 71];
+    }
+  ];
+  events: [
+    {
+      name: "proposalBondSettled";
+      discriminator: [173, 38, 68, 91, 251, 250, 99, 241];
+    },
+    {
+      name: "proposalCancelled";
+      discriminator: [253, 59, 104, 46, 129, 78, 9, 14];
+    },
+    {
+      name: "proposalCreated";
+      discriminator: [186, 8, 160, 108, 81, 13, 51, 206];
+    },
+    {
+      name: "proposalExecuted";
+      discriminator: [92, 213, 189, 201, 101, 83, 111, 83];
+    },
+    {
+      name: "proposalExpired";
+      discriminator: [48, 8, 10, 52, 213, 133, 166, 223];
+    },
+    {
+      name: "proposalFinalized";
+      discriminator: [159, 104, 210, 220, 86, 209, 61, 51];
+    },
+    {
+      name: "proposalVetoed";
+      discriminator: [185, 29, 77, 23, 134, 251, 155, 27];
     }
   ];
   errors: [
@@ -233,346 +840,1394 @@ export type Autocrat = {
       msg: "This proposal can't be executed because it isn't in the passed state";
     },
     {
-      code: 6009;
-      name: "insufficientLpTokenBalance";
-      msg: "The proposer has fewer pass or fail LP tokens than they requested to lock";
+      code: 6009;
+      name: "insufficientLpTokenBalance";
+      msg: "The proposer has fewer pass or fail LP tokens than they requested to lock";
+    },
+    {
+      code: 6010;
+      name: "insufficientLpTokenLock";
+      msg: "The LP tokens passed in have less liquidity than the DAO's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`";
+    },
+    {
+      code: 6011;
+      name: "invalidFeeRecipient";
+      msg: "An amm has a `fee_recipient` that isn't the `dao`'s treasury";
+    },
+    {
+      code: 6012;
+      name: "invalidWinnerPremium";
+      msg: "An amm has a `winner_premium_bps` that doesn't match the `dao`'s config, or it's more than `MAX_BPS`";
+    },
+    {
+      code: 6013;
+      name: "invalidLoserHaircut";
+      msg: "An amm has a `loser_haircut_bps` that doesn't match the `dao`'s config, or it's more than `MAX_BPS`";
+    },
+    {
+      code: 6014;
+      name: "proposalStillPending";
+      msg: "This proposal is still pending, so its LP tokens are still locked";
+    },
+    {
+      code: 6015;
+      name: "descriptionUrlTooLong";
+      msg: "A proposal's `description_url` can be at most `MAX_DESCRIPTION_URL_LEN` bytes";
+    },
+    {
+      code: 6016;
+      name: "tooManyProposalInstructions";
+      msg: "A proposal can have at most `MAX_PROPOSAL_INSTRUCTIONS` instructions";
+    },
+    {
+      code: 6017;
+      name: "proposalInstructionsTooLarge";
+      msg: "A proposal's instructions can take up at most `MAX_PROPOSAL_INSTRUCTIONS_LEN` bytes";
+    },
+    {
+      code: 6018;
+      name: "missingInstructionAccounts";
+      msg: "Not enough `remaining_accounts` were passed to execute every proposal instruction";
+    },
+    {
+      code: 6019;
+      name: "unexpectedInstructionAccounts";
+      msg: "`remaining_accounts` hold more accounts than the proposal's instructions use";
+    },
+    {
+      code: 6020;
+      name: "instructionAccountMismatch";
+      msg: "An instruction's account in `remaining_accounts` isn't the one the proposal specifies";
+    },
+    {
+      code: 6021;
+      name: "instructionProgramMismatch";
+      msg: "An instruction's program in `remaining_accounts` isn't the one the proposal specifies";
+    },
+    {
+      code: 6022;
+      name: "instructionAccountWritableMismatch";
+      msg: "An account's writability doesn't match the proposal instruction's account meta";
+    },
+    {
+      code: 6023;
+      name: "instructionSignerMissing";
+      msg: "An account the proposal needs a signature from, other than the treasury, didn't sign";
+    },
+    {
+      code: 6024;
+      name: "proposalNotClosable";
+      msg: "Only executed, failed, expired, vetoed or cancelled proposals can be closed";
+    },
+    {
+      code: 6025;
+      name: "lpTokensStillLocked";
+      msg: "The proposal's LP tokens need to be unlocked before it can be closed";
+    },
+    {
+      code: 6026;
+      name: "cancellationWindowElapsed";
+      msg: "The DAO's `cancellation_grace_slots` have passed, so the proposal can't be cancelled";
+    },
+    {
+      code: 6027;
+      name: "proposalTimelocked";
+      msg: "The DAO's `execution_delay_slots` haven't passed since the proposal was finalized";
+    },
+    {
+      code: 6028;
+      name: "proposalExpired";
+      msg: "The proposal's execution window has passed, so it can only be expired";
+    },
+    {
+      code: 6029;
+      name: "proposalNotExpired";
+      msg: "The proposal's execution window hasn't passed yet";
+    },
+    {
+      code: 6030;
+      name: "invalidExecutionExpiry";
+      msg: "`execution_expiry_slots` must be non-zero, or no proposal could ever be executed";
+    },
+    {
+      code: 6031;
+      name: "noGuardian";
+      msg: "This DAO doesn't have a guardian";
+    },
+    {
+      code: 6032;
+      name: "invalidGuardian";
+      msg: "Only the DAO's guardian can veto proposals";
+    },
+    {
+      code: 6033;
+      name: "duplicateAmm";
+      msg: "`pass_amm` and `fail_amm` must be different markets";
+    },
+    {
+      code: 6034;
+      name: "invalidAmmProposal";
+      msg: "The market wasn't created for this proposal";
+    },
+    {
+      code: 6035;
+      name: "invalidAmmProposalNumber";
+      msg: "The market wasn't created for this proposal number";
+    },
+    {
+      code: 6036;
+      name: "invalidAmmMints";
+      msg: "The pass and fail markets must trade the vaults' pass and fail tokens respectively";
+    },
+    {
+      code: 6037;
+      name: "ammNotTrading";
+      msg: "The market has already been settled";
+    },
+    {
+      code: 6038;
+      name: "tooManyPendingProposals";
+      msg: "The DAO already has `max_pending_proposals` pending proposals";
+    },
+    {
+      code: 6039;
+      name: "proposerCooldown";
+      msg: "The proposer created a proposal within the DAO's `proposer_cooldown_slots`";
+    },
+    {
+      code: 6040;
+      name: "invalidMaxPendingProposals";
+      msg: "`max_pending_proposals` must be non-zero, or no proposal could ever be created";
+    },
+    {
+      code: 6041;
+      name: "invalidBondSlashThreshold";
+      msg: "`bond_slash_threshold_bps` must be at most 10,000";
+    },
+    {
+      code: 6042;
+      name: "bondAlreadySettled";
+      msg: "The proposal's bond has already been settled";
+    },
+    {
+      code: 6043;
+      name: "invalidBondRecipient";
+      msg: "The bond must go to the treasury if it was slashed, and to the proposer otherwise";
+    },
+    {
+      code: 6044;
+      name: "bondNotSettled";
+      msg: "The proposal's bond must be settled before it can be closed";
+    },
+    {
+      code: 6045;
+      name: "missingBondAccounts";
+      msg: "The bond token accounts must all be passed when there is a bond";
+    },
+    {
+      code: 6046;
+      name: "invalidGraduationTarget";
+      msg: "The market must graduate to the DAO's `graduation_target`";
+    },
+    {
+      code: 6047;
+      name: "invalidAmmFee";
+      msg: "The market's fee must be the DAO's `amm_fee_bps`, which is at most `amm::state::MAX_FEE_BPS`";
+    },
+    {
+      code: 6048;
+      name: "invalidAmmGraduationThreshold";
+      msg: "The market must graduate at the DAO's `amm_graduation_threshold`";
+    },
+    {
+      code: 6049;
+      name: "invalidAmmVirtualReserves";
+      msg: "Proposal markets can't have virtual reserves";
+    },
+    {
+      code: 6050;
+      name: "vaultNotActive";
+      msg: "The proposal's vaults have already been settled";
+    },
+    {
+      code: 6051;
+      name: "vetoWindowElapsed";
+      msg: "The proposal's timelock is over, so it can no longer be vetoed";
+    }
+  ];
+  types: [
+    {
+      name: "amm";
+      serialization: "bytemuckunsafe";
+      repr: {
+        kind: "rust";
+        packed: true;
+      };
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "bump";
+            type: "u8";
+          },
+          {
+            name: "createdAtSlot";
+            type: "u64";
+          },
+          {
+            name: "creator";
+            docs: [
+              "Who created the market. It's part of the amm's address, so nobody can",
+              "squat on a mint pair that someone else needs a market for."
+            ];
+            type: "pubkey";
+          },
+          {
+            name: "proposal";
+            docs: [
+              "The futarchy proposal this market trades, and its number. Autocrat",
+              "only accepts markets created for the proposal being initialized."
+            ];
+            type: "pubkey";
+          },
+          {
+            name: "proposalNumber";
+            type: "u32";
+          },
+          {
+            name: "settlementAuthority";
+            docs: [
+              "The only account that can finalize the market or switch it to",
+              "refund-only mode, through `settle_market`. For proposals, this is the",
+              "DAO's treasury."
+            ];
+            type: "pubkey";
+          },
+          {
+            name: "baseMint";
+            type: "pubkey";
+          },
+          {
+            name: "quoteMint";
+            type: "pubkey";
+          },
+          {
+            name: "baseSupply";
+            type: {
+              defined: {
+                name: "baseSupply";
+              };
+            };
+          },
+          {
+            name: "lpMint";
+            docs: ["PDA of `[AMM_LP_MINT_SEED_PREFIX, amm]`, owned by the amm"];
+            type: "pubkey";
+          },
+          {
+            name: "virtualLpSupply";
+            docs: [
+              "LP tokens that represent the virtual reserves the curve starts with.",
+              "They are never minted, so nobody can withdraw that liquidity, but they",
+              "count towards the total LP supply. Real LPs own what they deposited",
+              "plus their share of what traders paid in or took out since, and sells",
+              "can't take the price below the initial price, which keeps the virtual",
+              "share of the quote backed by what traders paid in."
+            ];
+            type: "u64";
+          },
+          {
+            name: "baseMintDecimals";
+            type: "u8";
+          },
+          {
+            name: "quoteMintDecimals";
+            type: "u8";
+          },
+          {
+            name: "vQuoteReserves";
+            type: "u64";
+          },
+          {
+            name: "vBaseReserves";
+            type: "u64";
+          },
+          {
+            name: "initialVQuoteReserves";
+            docs: [
+              "The virtual reserves the curve starts with, which also set the lowest",
+              "price that sells can reach"
+            ];
+            type: "u64";
+          },
+          {
+            name: "initialVBaseReserves";
+            type: "u64";
+          },
+          {
+            name: "graduationThreshold";
+            docs: [
+              "The market can graduate once `v_quote_reserves` reach this amount"
+            ];
+            type: "u64";
+          },
+          {
+            name: "graduationTarget";
+            type: {
+              defined: {
+                name: "graduationTarget";
+              };
+            };
+          },
+          {
+            name: "graduatedPool";
+            docs: [
+              "The pool or recipient that `graduate` migrated the liquidity to, or the",
+              "default pubkey if the market hasn't graduated"
+            ];
+            type: "pubkey";
+          },
+          {
+            name: "status";
+            type: {
+              defined: {
+                name: "marketStatus";
+              };
+            };
+          },
+          {
+            name: "oracle";
+            type: {
+              defined: {
+                name: "twapOracle";
+              };
+            };
+          },
+          {
+            name: "feeBps";
+            docs: ["The swap fee, taken out of the input amount"];
+            type: "u16";
+          },
+          {
+            name: "feeRecipient";
+            docs: ["Who `collect_fees` pays out to"];
+            type: "pubkey";
+          },
+          {
+            name: "baseFeesAccrued";
+            docs: [
+              "Fees that have been charged but not yet collected. They sit in the",
+              "vaults but are not part of the reserves."
+            ];
+            type: "u64";
+          },
+          {
+            name: "quoteFeesAccrued";
+            type: "u64";
+          },
+          {
+            name: "winnerPremiumBps";
+            docs: [
+              "How much more than the curve price sellers of a winning market get"
+            ];
+            type: "u16";
+          },
+          {
+            name: "loserHaircutBps";
+            docs: [
+              "How much less than the curve price sellers of a losing market get"
+            ];
+            type: "u16";
+          }
+        ];
+      };
+    },
+    {
+      name: "baseSupply";
+      docs: ["Where the base that a market sells comes from."];
+      repr: {
+        kind: "rust";
+      };
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "minted";
+          },
+          {
+            name: "deposited";
+          }
+        ];
+      };
+    },
+    {
+      name: "conditionalVault";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "status";
+            type: {
+              defined: {
+                name: "vaultStatus";
+              };
+            };
+          },
+          {
+            name: "settlementAuthority";
+            docs: [
+              "The account that can either finalize the vault to make",
+              "conditional-on-finalize tokens redeemable for underlying tokens or revert",
+              "the vault to make conditional-on-revert tokens redeemable."
+            ];
+            type: "pubkey";
+          },
+          {
+            name: "underlyingTokenMint";
+            type: "pubkey";
+          },
+          {
+            name: "underlyingTokenAccount";
+            type: "pubkey";
+          },
+          {
+            name: "conditionalOnFinalizeTokenMint";
+            type: "pubkey";
+          },
+          {
+            name: "conditionalOnRevertTokenMint";
+            type: "pubkey";
+          },
+          {
+            name: "nonce";
+            docs: [
+              "Lets one settlement authority have many vaults over the same underlying",
+              "token. Autocrat requires this to be the proposal number."
+            ];
+            type: "u64";
+          },
+          {
+            name: "pdaBump";
+            type: "u8";
+          },
+          {
+            name: "decimals";
+            type: "u8";
+          }
+        ];
+      };
+    },
+    {
+      name: "dao";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "treasuryPdaBump";
+            type: "u8";
+          },
+          {
+            name: "treasury";
+            type: "pubkey";
+          },
+          {
+            name: "tokenMint";
+            type: "pubkey";
+          },
+          {
+            name: "usdcMint";
+            type: "pubkey";
+          },
+          {
+            name: "proposalCount";
+            type: "u32";
+          },
+          {
+            name: "passThresholdBps";
+            type: "u16";
+          },
+          {
+            name: "slotsPerProposal";
+            type: "u64";
+          },
+          {
+            name: "twapInitialObservation";
+            docs: [
+              "For manipulation-resistance the TWAP is a time-weighted average observation,",
+              "where observation tries to approximate price but can only move by",
+              "`twap_max_observation_change_per_update` per update. Because it can only move",
+              "a little bit per update, you need to check that it has a good initial observation.",
+              "Otherwise, an attacker could create a very high initial observation in the pass",
+              "market and a very low one in the fail market to force the proposal to pass.",
+              "",
+              "We recommend setting an initial observation around the spot price of the token,",
+              "and max observation change per update around 2% the spot price of the token.",
+              "For example, if the spot price of META is $400, we'd recommend setting an initial",
+              "observation of 400 (converted into the AMM prices) and a max observation change per",
+              "update of 8 (also converted into the AMM prices). Observations can be updated once",
+              "a minute, so 2% allows the proposal market to reach double the spot price or 0",
+              "in 50 minutes."
+            ];
+            type: "u128";
+          },
+          {
+            name: "twapMaxObservationChangePerUpdate";
+            type: "u128";
+          },
+          {
+            name: "minQuoteFutarchicLiquidity";
+            docs: [
+              "As an anti-spam measure and to help liquidity, you need to lock up some liquidity",
+              "in both futarchic markets in order to create a proposal.",
+              "",
+              "For example, for META, we can use a `min_quote_futarchic_liquidity` of",
+              "5000 * 1_000_000 (5000 USDC) and a `min_base_futarchic_liquidity` of",
+              "10 * 1_000_000_000 (10 META)."
+            ];
+            type: "u64";
+          },
+          {
+            name: "minBaseFutarchicLiquidity";
+            type: "u64";
+          },
+          {
+            name: "winnerPremiumBps";
+            docs: [
+              "Once a proposal is decided, its markets settle sells against the curve",
+              "price: the winning market pays `winner_premium_bps` more and the losing",
+              "market pays `loser_haircut_bps` less. Every proposal's markets must be",
+              "created with these values."
+            ];
+            type: "u16";
+          },
+          {
+            name: "loserHaircutBps";
+            type: "u16";
+          },
+          {
+            name: "cancellationGraceSlots";
+            docs: [
+              "How many slots after a proposal is created its proposer can still",
+              "cancel it, e.g. to fix a mistake in its instructions before trading",
+              "gets going."
+            ];
+            type: "u64";
+          },
+          {
+            name: "executionDelaySlots";
+            docs: [
+              "A passed proposal can only be executed `execution_delay_slots` after",
+              "it's finalized, which gives token holders time to react, and then only",
+              "for `execution_expiry_slots`, after which it expires."
+            ];
+            type: "u64";
+          },
+          {
+            name: "executionExpirySlots";
+            type: "u64";
+          },
+          {
+            name: "guardian";
+            docs: [
+              "An optional emergency brake, e.g. a security council's multisig, that",
+              "can veto passed proposals during their `execution_delay_slots`. It can",
+              "only be set or removed by a proposal, through `update_dao`."
+            ];
+            type: {
+              option: "pubkey";
+            };
+          },
+          {
+            name: "maxPendingProposals";
+            docs: [
+              "Anti-spam: `initialize_proposal` fails while `max_pending_proposals`",
+              "proposals are pending, or if the proposer created a proposal in the",
+              "last `proposer_cooldown_slots`."
+            ];
+            type: "u32";
+          },
+          {
+            name: "pendingProposalCount";
+            type: "u32";
+          },
+          {
+            name: "proposerCooldownSlots";
+            type: "u64";
+          },
+          {
+            name: "proposalBond";
+            docs: [
+              "Proposers post `proposal_bond` of `token_mint` with each proposal. It's",
+              "slashed to the treasury if the pass TWAP ends up more than",
+              "`bond_slash_threshold_bps` below the fail TWAP, and refunded otherwise."
+            ];
+            type: "u64";
+          },
+          {
+            name: "bondSlashThresholdBps";
+            type: "u16";
+          },
+          {
+            name: "graduationTarget";
+            docs: [
+              "Where every proposal's markets must graduate to. It defaults to the",
+              "treasury, so that nobody can point a market's liquidity at themselves."
+            ];
+            type: {
+              defined: {
+                name: "graduationTarget";
+              };
+            };
+          },
+          {
+            name: "ammFeeBps";
+            docs: [
+              "Every proposal's markets must charge `amm_fee_bps` on swaps and",
+              "graduate once their quote reserves reach `amm_graduation_threshold`.",
+              "They trade the vaults' conditional tokens, which they can't mint, so",
+              "they have no virtual reserves and their LPs set the price."
+            ];
+            type: "u16";
+          },
+          {
+            name: "ammGraduationThreshold";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "graduationTarget";
+      docs: [
+        "Where a market's liquidity goes when it graduates. Each target has its own",
+        "set of accounts that `graduate` expects after the common ones."
+      ];
+      repr: {
+        kind: "rust";
+      };
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "raydiumCpSwap";
+          },
+          {
+            name: "recipient";
+            fields: ["pubkey"];
+          }
+        ];
+      };
+    },
+    {
+      name: "initializeDaoParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "twapInitialObservation";
+            type: "u128";
+          },
+          {
+            name: "twapMaxObservationChangePerUpdate";
+            type: "u128";
+          },
+          {
+            name: "minQuoteFutarchicLiquidity";
+            type: "u64";
+          },
+          {
+            name: "minBaseFutarchicLiquidity";
+            type: "u64";
+          },
+          {
+            name: "passThresholdBps";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "slotsPerProposal";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "winnerPremiumBps";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "loserHaircutBps";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "cancellationGraceSlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "executionDelaySlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "executionExpirySlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "maxPendingProposals";
+            type: {
+              option: "u32";
+            };
+          },
+          {
+            name: "proposerCooldownSlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "proposalBond";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "bondSlashThresholdBps";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "graduationTarget";
+            type: {
+              option: {
+                defined: {
+                  name: "graduationTarget";
+                };
+              };
+            };
+          },
+          {
+            name: "ammFeeBps";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "ammGraduationThreshold";
+            type: {
+              option: "u64";
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "initializeProposalParams";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "descriptionUrl";
+            type: "string";
+          },
+          {
+            name: "instructions";
+            type: {
+              vec: {
+                defined: {
+                  name: "proposalInstruction";
+                };
+              };
+            };
+          },
+          {
+            name: "passLpTokensToLock";
+            type: "u64";
+          },
+          {
+            name: "failLpTokensToLock";
+            type: "u64";
+          },
+          {
+            name: "nonce";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "marketOutcome";
+      docs: ["Which side of the proposal a market ended up on."];
+      repr: {
+        kind: "rust";
+      };
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "winning";
+          },
+          {
+            name: "losing";
+          }
+        ];
+      };
     },
     {
-      code: 6010;
-      name: "insufficientLpTokenLock";
-      msg: "The LP tokens passed in have less liquidity than the DAO's `min_quote_futarchic_liquidity` or `min_base_futachic_liquidity`";
-    }
-  ];
-  types: [
-    {
-      name: "amm";
-      serialization: "bytemuckunsafe";
+      name: "marketStatus";
+      docs: [
+        "The lifecycle of a market. The only legal transitions are:",
+        "- Trading -> Finalized",
+        "- Trading -> Refunding",
+        "- Finalized(Winning) -> Graduated",
+        "- Finalized(Winning) -> Closed, if there's no virtual liquidity to graduate",
+        "- Finalized(Losing) -> Closed",
+        "- Graduated -> Closed",
+        "- Refunding -> Closed",
+        "",
+        "`repr(u8)` pins the layout so that it can live inside the zero-copy `Amm`.",
+        "A zeroed status is `Trading`."
+      ];
       repr: {
         kind: "rust";
-        packed: true;
       };
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "trading";
+          },
+          {
+            name: "finalized";
+            fields: [
+              {
+                defined: {
+                  name: "marketOutcome";
+                };
+              }
+            ];
+          },
+          {
+            name: "graduated";
+          },
+          {
+            name: "closed";
+          },
+          {
+            name: "refunding";
+          }
+        ];
+      };
+    },
+    {
+      name: "proposal";
       type: {
         kind: "struct";
         fields: [
           {
-            name: "bump";
-            type: "u8";
+            name: "number";
+            type: "u32";
           },
           {
-            name: "createdAtSlot";
+            name: "proposer";
+            type: "pubkey";
+          },
+          {
+            name: "descriptionUrl";
+            type: "string";
+          },
+          {
+            name: "slotEnqueued";
             type: "u64";
           },
           {
-            name: "baseMint";
+            name: "state";
+            type: {
+              defined: {
+                name: "proposalState";
+              };
+            };
+          },
+          {
+            name: "slotFinalized";
+            docs: ["When the proposal was finalized, or 0 while it's pending"];
+            type: "u64";
+          },
+          {
+            name: "instructions";
+            docs: ["Executed atomically and in order once the proposal passes"];
+            type: {
+              vec: {
+                defined: {
+                  name: "proposalInstruction";
+                };
+              };
+            };
+          },
+          {
+            name: "passAmm";
             type: "pubkey";
           },
           {
-            name: "quoteMint";
+            name: "failAmm";
             type: "pubkey";
           },
           {
-            name: "baseMintDecimals";
-            type: "u8";
+            name: "baseVault";
+            docs: ["Splits `dao.token_mint` into pass and fail tokens"];
+            type: "pubkey";
           },
           {
-            name: "quoteMintDecimals";
-            type: "u8";
+            name: "quoteVault";
+            docs: ["Splits `dao.usdc_mint` into pass and fail tokens"];
+            type: "pubkey";
           },
           {
-            name: "vQuoteReserves";
+            name: "dao";
+            type: "pubkey";
+          },
+          {
+            name: "passLpTokensLocked";
             type: "u64";
           },
           {
-            name: "vBaseReserves";
+            name: "failLpTokensLocked";
+            type: "u64";
+          },
+          {
+            name: "bondAmount";
+            docs: ["The bond the proposer posted, or 0 once it's been settled"];
+            type: "u64";
+          },
+          {
+            name: "bondSlashed";
+            docs: ["Decided when the proposal is finalized"];
+            type: "bool";
+          },
+          {
+            name: "hasBondVault";
+            docs: [
+              "Whether the proposal owns a bond vault, which `close_proposal` then",
+              "has to close"
+            ];
+            type: "bool";
+          },
+          {
+            name: "nonce";
+            docs: [
+              "We need to include a per-proposer nonce to prevent some weird proposal",
+              "front-running edge cases. Using a `u64` means that proposers are unlikely",
+              "to run into collisions, even if they generate nonces randomly - I've run",
+              "the math :D"
+            ];
             type: "u64";
           },
           {
-            name: "vaultStatus";
+            name: "pdaBump";
             type: "u8";
           }
         ];
       };
     },
     {
-      name: "dao";
+      name: "proposalAccount";
       type: {
         kind: "struct";
         fields: [
           {
-            name: "treasuryPdaBump";
-            type: "u8";
+            name: "pubkey";
+            type: "pubkey";
           },
           {
-            name: "treasury";
+            name: "isSigner";
+            type: "bool";
+          },
+          {
+            name: "isWritable";
+            type: "bool";
+          }
+        ];
+      };
+    },
+    {
+      name: "proposalBondSettled";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "proposal";
             type: "pubkey";
           },
           {
-            name: "tokenMint";
+            name: "dao";
             type: "pubkey";
           },
           {
-            name: "usdcMint";
+            name: "recipient";
             type: "pubkey";
           },
           {
-            name: "proposalCount";
-            type: "u32";
+            name: "amount";
+            type: "u64";
           },
           {
-            name: "passThresholdBps";
-            type: "u16";
+            name: "slashed";
+            type: "bool";
           },
           {
-            name: "slotsPerProposal";
+            name: "slot";
             type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "proposalCancelled";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "proposal";
+            type: "pubkey";
           },
           {
-            name: "twapInitialObservation";
-            docs: [
-              "For manipulation-resistance the TWAP is a time-weighted average observation,",
-              "where observation tries to approximate price but can only move by",
-              "`twap_max_observation_change_per_update` per update. Because it can only move",
-              "a little bit per update, you need to check that it has a good initial observation.",
-              "Otherwise, an attacker could create a very high initial observation in the pass",
-              "market and a very low one in the fail market to force the proposal to pass.",
-              "",
-              "We recommend setting an initial observation around the spot price of the token,",
-              "and max observation change per update around 2% the spot price of the token.",
-              "For example, if the spot price of META is $400, we'd recommend setting an initial",
-              "observation of 400 (converted into the AMM prices) and a max observation change per",
-              "update of 8 (also converted into the AMM prices). Observations can be updated once",
-              "a minute, so 2% allows the proposal market to reach double the spot price or 0",
-              "in 50 minutes."
-            ];
-            type: "u128";
+            name: "dao";
+            type: "pubkey";
           },
           {
-            name: "twapMaxObservationChangePerUpdate";
-            type: "u128";
+            name: "proposer";
+            type: "pubkey";
           },
           {
-            name: "minQuoteFutarchicLiquidity";
-            docs: [
-              "As an anti-spam measure and to help liquidity, you need to lock up some liquidity",
-              "in both futarchic markets in order to create a proposal.",
-              "",
-              "For example, for META, we can use a `min_quote_futarchic_liquidity` of",
-              "5000 * 1_000_000 (5000 USDC) and a `min_base_futarchic_liquidity` of",
-              "10 * 1_000_000_000 (10 META)."
-            ];
+            name: "slot";
             type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "proposalCreated";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "proposal";
+            type: "pubkey";
           },
           {
-            name: "minBaseFutarchicLiquidity";
+            name: "dao";
+            type: "pubkey";
+          },
+          {
+            name: "proposer";
+            type: "pubkey";
+          },
+          {
+            name: "number";
+            type: "u32";
+          },
+          {
+            name: "passAmm";
+            type: "pubkey";
+          },
+          {
+            name: "failAmm";
+            type: "pubkey";
+          },
+          {
+            name: "descriptionUrl";
+            type: "string";
+          },
+          {
+            name: "slot";
             type: "u64";
           }
         ];
       };
     },
     {
-      name: "initializeDaoParams";
+      name: "proposalExecuted";
       type: {
         kind: "struct";
         fields: [
           {
-            name: "twapInitialObservation";
-            type: "u128";
+            name: "proposal";
+            type: "pubkey";
           },
           {
-            name: "twapMaxObservationChangePerUpdate";
-            type: "u128";
+            name: "dao";
+            type: "pubkey";
           },
           {
-            name: "minQuoteFutarchicLiquidity";
-            type: "u64";
+            name: "instructionsExecuted";
+            type: "u32";
           },
           {
-            name: "minBaseFutarchicLiquidity";
+            name: "slot";
             type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "proposalExpired";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "proposal";
+            type: "pubkey";
           },
           {
-            name: "passThresholdBps";
-            type: {
-              option: "u16";
-            };
+            name: "dao";
+            type: "pubkey";
           },
           {
-            name: "slotsPerProposal";
-            type: {
-              option: "u64";
-            };
+            name: "slot";
+            type: "u64";
           }
         ];
       };
     },
     {
-      name: "initializeProposalParams";
+      name: "proposalFinalized";
+      docs: [
+        "TWAPs are quote units per base unit scaled by `amm::state::PRICE_SCALE`."
+      ];
       type: {
         kind: "struct";
         fields: [
           {
-            name: "descriptionUrl";
-            type: "string";
+            name: "proposal";
+            type: "pubkey";
+          },
+          {
+            name: "dao";
+            type: "pubkey";
           },
           {
-            name: "instruction";
+            name: "state";
             type: {
               defined: {
-                name: "proposalInstruction";
+                name: "proposalState";
               };
             };
           },
           {
-            name: "passLpTokensToLock";
-            type: "u64";
+            name: "passMarketTwap";
+            type: "u128";
           },
           {
-            name: "failLpTokensToLock";
-            type: "u64";
+            name: "failMarketTwap";
+            type: "u128";
           },
           {
-            name: "nonce";
+            name: "slot";
             type: "u64";
           }
         ];
       };
     },
     {
-      name: "proposal";
+      name: "proposalIndex";
+      docs: [
+        "Points at proposal number `number` of `dao`, so that clients can list a",
+        "DAO's proposals by deriving `[b\"proposal_index\", dao, number]` for every",
+        "number up to `dao.proposal_count`. Closed along with its proposal, so",
+        "numbers whose index is missing belong to closed proposals."
+      ];
       type: {
         kind: "struct";
         fields: [
+          {
+            name: "dao";
+            type: "pubkey";
+          },
           {
             name: "number";
             type: "u32";
           },
           {
-            name: "proposer";
+            name: "proposal";
             type: "pubkey";
           },
           {
-            name: "descriptionUrl";
-            type: "string";
-          },
+            name: "pdaBump";
+            type: "u8";
+          }
+        ];
+      };
+    },
+    {
+      name: "proposalInstruction";
+      type: {
+        kind: "struct";
+        fields: [
           {
-            name: "slotEnqueued";
-            type: "u64";
+            name: "programId";
+            type: "pubkey";
           },
           {
-            name: "state";
+            name: "accounts";
             type: {
-              defined: {
-                name: "proposalState";
+              vec: {
+                defined: {
+                  name: "proposalAccount";
+                };
               };
             };
           },
           {
-            name: "instruction";
-            type: {
-              defined: {
-                name: "proposalInstruction";
-              };
-            };
-          },
+            name: "data";
+            type: "bytes";
+          }
+        ];
+      };
+    },
+    {
+      name: "proposalState";
+      type: {
+        kind: "enum";
+        variants: [
           {
-            name: "passAmm";
-            type: "pubkey";
+            name: "pending";
           },
           {
-            name: "failAmm";
-            type: "pubkey";
+            name: "passed";
           },
           {
-            name: "dao";
-            type: "pubkey";
+            name: "failed";
           },
           {
-            name: "passLpTokensLocked";
-            type: "u64";
+            name: "executed";
           },
           {
-            name: "failLpTokensLocked";
-            type: "u64";
+            name: "expired";
           },
           {
-            name: "nonce";
-            docs: [
-              "We need to include a per-proposer nonce to prevent some weird proposal",
-              "front-running edge cases. Using a `u64` means that proposers are unlikely",
-              "to run into collisions, even if they generate nonces randomly - I've run",
-              "the math :D"
-            ];
-            type: "u64";
+            name: "vetoed";
           },
           {
-            name: "pdaBump";
-            type: "u8";
+            name: "cancelled";
           }
         ];
       };
     },
     {
-      name: "proposalAccount";
+      name: "proposalVetoed";
       type: {
         kind: "struct";
         fields: [
           {
-            name: "pubkey";
+            name: "proposal";
             type: "pubkey";
           },
           {
-            name: "isSigner";
-            type: "bool";
+            name: "dao";
+            type: "pubkey";
           },
           {
-            name: "isWritable";
-            type: "bool";
+            name: "guardian";
+            type: "pubkey";
+          },
+          {
+            name: "slot";
+            type: "u64";
           }
         ];
       };
     },
     {
-      name: "proposalInstruction";
+      name: "proposerRecord";
+      docs: [
+        "Tracks a proposer's activity in a DAO, so that the DAO can rate-limit them.",
+        'PDA of `[b"proposer_record", dao, proposer]`.'
+      ];
       type: {
         kind: "struct";
         fields: [
           {
-            name: "programId";
+            name: "dao";
             type: "pubkey";
           },
           {
-            name: "accounts";
-            type: {
-              vec: {
-                defined: {
-                  name: "proposalAccount";
-                };
-              };
-            };
+            name: "proposer";
+            type: "pubkey";
           },
           {
-            name: "data";
-            type: "bytes";
+            name: "lastProposalSlot";
+            type: "u64";
+          },
+          {
+            name: "pdaBump";
+            type: "u8";
           }
         ];
       };
     },
     {
-      name: "proposalState";
+      name: "twapOracle";
+      serialization: "bytemuckunsafe";
+      repr: {
+        kind: "rust";
+        packed: true;
+      };
       type: {
-        kind: "enum";
-        variants: [
+        kind: "struct";
+        fields: [
           {
-            name: "pending";
+            name: "lastUpdatedSlot";
+            type: "u64";
           },
           {
-            name: "passed";
+            name: "lastPrice";
+            docs: [
+              "A price is the number of quote units per base unit multiplied by 1e12.",
+              "You cannot simply divide by 1e12 to get a price you can display in the UI",
+              "because the base and quote decimals may be different. Instead, do:",
+              "ui_price = (price * (10**(base_decimals - quote_decimals))) / 1e12"
+            ];
+            type: "u128";
           },
           {
-            name: "failed";
+            name: "lastObservation";
+            docs: [
+              "If we did a raw TWAP over prices, someone could push the TWAP heavily with",
+              "a few extremely large outliers. So we use observations, which can only move",
+              "by `max_observation_change_per_update` per update."
+            ];
+            type: "u128";
           },
           {
-            name: "executed";
+            name: "aggregator";
+            docs: [
+              "Running sum of slots_per_last_update * last_observation.",
+              "",
+              "Assuming latest observations are as big as possible (u64::MAX * 1e12),",
+              "we can store (2**128 / (2**64 * 1e12)) / (60 * 60 * 24 * 365 * 2.5) = 589",
+              "years of slots before this value overflows. It's wrapping anyway, so",
+              "consumers should only ever look at the difference between two aggregators."
+            ];
+            type: "u128";
+          },
+          {
+            name: "maxObservationChangePerUpdate";
+            docs: ["The most that an observation can change per update."];
+            type: "u128";
+          },
+          {
+            name: "initialObservation";
+            docs: ["What the initial `last_observation` is set to."];
+            type: "u128";
           }
         ];
       };
@@ -617,6 +2272,108 @@ export type Autocrat = {
             type: {
               option: "u64";
             };
+          },
+          {
+            name: "winnerPremiumBps";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "loserHaircutBps";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "cancellationGraceSlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "executionDelaySlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "executionExpirySlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "maxPendingProposals";
+            type: {
+              option: "u32";
+            };
+          },
+          {
+            name: "proposerCooldownSlots";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "proposalBond";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "bondSlashThresholdBps";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "guardian";
+            docs: ["`Some(None)` removes the guardian"];
+            type: {
+              option: {
+                option: "pubkey";
+              };
+            };
+          },
+          {
+            name: "graduationTarget";
+            type: {
+              option: {
+                defined: {
+                  name: "graduationTarget";
+                };
+              };
+            };
+          },
+          {
+            name: "ammFeeBps";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "ammGraduationThreshold";
+            type: {
+              option: "u64";
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "vaultStatus";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "active";
+          },
+          {
+            name: "finalized";
+          },
+          {
+            name: "reverted";
           }
         ];
       };
@@ -1,203 +1,199 @@
+/**
+ * Program IDL in camelCase format in order to be used in JS/TS.
+ *
+ * Note that this is only a type helper and is not the actual IDL. The original
+ * IDL can be found at `target/idl/conditional_vault.json`.
+ */
 export type ConditionalVault = {
-  version: "0.3.0";
-  name: "conditional_vault";
+  address: "VAU1T7S5UuEHmMvXtXMVmpEoQtZ2ya7eRb7gcN47wDp";
+  metadata: {
+    name: "conditionalVault";
+    version: "0.3.0";
+    spec: "0.1.0";
+    description: "SVM-based program for minting conditional tokens";
+  };
   instructions: [
     {
       name: "initializeConditionalVault";
+      discriminator: [37, 88, 250, 212, 54, 218, 227, 175];
       accounts: [
         {
           name: "vault";
-          isMut: true;
-          isSigner: false;
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  99, 111, 110, 100, 105, 116, 105, 111, 110, 97, 108, 95, 118,
+                  97, 117, 108, 116
+                ];
+              },
+              {
+                kind: "arg";
+                path: "args.settlement_authority";
+              },
+              {
+                kind: "account";
+                path: "underlyingTokenMint";
+              },
+              {
+                kind: "arg";
+                path: "args.nonce";
+              }
+            ];
+          };
         },
         {
           name: "underlyingTokenMint";
-          isMut: false;
-          isSigner: false;
         },
         {
           name: "conditionalOnFinalizeTokenMint";
-          isMut: true;
-          isSigner: false;
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  99, 111, 110, 100, 105, 116, 105, 111, 110, 97, 108, 95, 111,
+                  110, 95, 102, 105, 110, 97, 108, 105, 122, 101, 95, 109, 105,
+                  110, 116
+                ];
+              },
+              {
+                kind: "account";
+                path: "vault";
+              }
+            ];
+          };
         },
         {
           name: "conditionalOnRevertTokenMint";
-          isMut: true;
-          isSigner: false;
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                value: [
+                  99, 111, 110, 100, 105, 116, 105, 111, 110, 97, 108, 95, 111,
+                  110, 95, 114, 101, 118, 101, 114, 116, 95, 109, 105, 110, 116
+                ];
+              },
+              {
+                kind: "account";
+                path: "vault";
+              }
+            ];
+          };
         },
         {
           name: "vaultUnderlyingTokenAccount";
-          isMut: false;
-          isSigner: false;
+          writable: true;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                path: "vault";
+              },
+              {
+                kind: "account";
+                path: "underlyingTokenProgram";
+              },
+              {
+                kind: "account";
+                path: "underlyingTokenMint";
+              }
+            ];
+            program: {
+              kind: "const";
+              value: [
+                140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142,
+                13, 131, 11, 90, 19, 153, 218, 255, 16, 132, 4, 142, 123, 216,
+                219, 233, 248, 89
+              ];
+            };
+          };
         },
         {
           name: "payer";
-          isMut: true;
-          isSigner: true;
+          writable: true;
+          signer: true;
         },
         {
           name: "tokenProgram";
-          isMut: false;
-          isSigner: false;
-        },
-        {
-          name: "associatedTokenProgram";
-          isMut: false;
-          isSigner: false;
-        },
-        {
-          name: "systemProgram";
-          isMut: false;
-          isSigner: false;
-        }
-      ];
-      args: [
-        {
-          name: "args";
-          type: {
-            defined: "InitializeConditionalVaultArgs";
-          };
-        }
-      ];
-    },
-    {
-      name: "addMetadataToConditionalTokens";
-      accounts: [
-        {
-          name: "payer";
-          isMut: true;
-          isSigner: true;
-        },
-        {
-          name: "vault";
-          isMut: true;
-          isSigner: false;
-        },
-        {
-          name: "underlyingTokenMint";
-          isMut: true;
-          isSigner: false;
-        },
-        {
-          name: "underlyingTokenMetadata";
-          isMut: false;
-          isSigner: false;
-        },
-        {
-          name: "conditionalOnFinalizeTokenMint";
-          isMut: true;
-          isSigner: false;
-        },
-        {
-          name: "conditionalOnRevertTokenMint";
-          isMut: true;
-          isSigner: false;
-        },
-        {
-          name: "conditionalOnFinalizeTokenMetadata";
-          isMut: true;
-          isSigner: false;
+          docs: ["The conditional token mints are always SPL Token mints"];
+          address: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
         },
         {
-          name: "conditionalOnRevertTokenMetadata";
-          isMut: true;
-          isSigner: false;
+          name: "underlyingTokenProgram";
+          docs: ["SPL Token or Token-2022, whichever owns the underlying mint"];
         },
         {
-          name: "tokenMetadataProgram";
-          isMut: false;
-          isSigner: false;
+          name: "associatedTokenProgram";
+          address: "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
         },
         {
           name: "systemProgram";
-          isMut: false;
-          isSigner: false;
-        },
-        {
-          name: "rent";
-          isMut: false;
-          isSigner: false;
+          address: "11111111111111111111111111111111";
         }
       ];
       args: [
         {
           name: "args";
           type: {
-            defined: "AddMetadataToConditionalTokensArgs";
-          };
-        }
-      ];
-    },
-    {
-      name: "settleConditionalVault";
-      accounts: [
-        {
-          name: "settlementAuthority";
-          isMut: false;
-          isSigner: true;
-        },
-        {
-          name: "vault";
-          isMut: true;
-          isSigner: false;
-        }
-      ];
-      args: [
-        {
-          name: "newStatus";
-          type: {
-            defined: "VaultStatus";
+            defined: {
+              name: "initializeConditionalVaultArgs";
+            };
           };
         }
       ];
     },
     {
       name: "mergeConditionalTokensForUnderlyingTokens";
+      discriminator: [217, 250, 121, 158, 129, 16, 63, 240];
       accounts: [
         {
           name: "vault";
-          isMut: false;
-          isSigner: false;
         },
         {
           name: "conditionalOnFinalizeTokenMint";
-          isMut: true;
-          isSigner: false;
+          writable: true;
+          relations: ["vault"];
         },
         {
           name: "conditionalOnRevertTokenMint";
-          isMut: true;
-          isSigner: false;
+          writable: true;
+          relations: ["vault"];
+        },
+        {
+          name: "underlyingTokenMint";
         },
         {
           name: "vaultUnderlyingTokenAccount";
-          isMut: true;
-          isSigner: false;
+          writable: true;
         },
         {
           name: "authority";
-          isMut: false;
-          isSigner: true;
+          signer: true;
+        },
+        {
+          name: "userUnderlyingTokenAccount";
+          writable: true;
         },
         {
           name: "userConditionalOnFinalizeTokenAccount";
-          isMut: true;
-          isSigner: false;
+          writable: true;
         },
         {
           name: "userConditionalOnRevertTokenAccount";
-          isMut: true;
-          isSigner: false;
+          writable: true;
         },
         {
-          name: "userUnderlyingTokenAccount";
-          isMut: true;
-          isSigner: false;
+          name: "tokenProgram";
+          address: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
         },
         {
-          name: "tokenProgram";
-          isMut: false;
-          isSigner: false;
+          name: "underlyingTokenProgram";
         }
       ];
       args: [
@@ -209,51 +205,50 @@ export type ConditionalVault = {
     },
     {
       name: "mintConditionalTokens";
+      discriminator: [63, 20, 202, 25, 179, 103, 54, 128];
       accounts: [
         {
           name: "vault";
-          isMut: false;
-          isSigner: false;
         },
         {
           name: "conditionalOnFinalizeTokenMint";
-          isMut: true;
-          isSigner: false;
+          writable: true;
+          relations: ["vault"];
         },
         {
           name: "conditionalOnRevertTokenMint";
-          isMut: true;
-          isSigner: false;
+          writable: true;
+          relations: ["vault"];
+        },
+        {
+          name: "underlyingTokenMint";
         },
         {
           name: "vaultUnderlyingTokenAccount";
-          isMut: true;
-          isSigner: false;
+          writable: true;
         },
         {
           name: "authority";
-          isMut: false;
-          isSigner: true;
+          signer: true;
+        },
+        {
+          name: "userUnderlyingTokenAccount";
+          writable: true;
         },
         {
           name: "userConditionalOnFinalizeTokenAccount";
-          isMut: true;
-          isSigner: false;
+          writable: true;
         },
         {
           name: "userConditionalOnRevertTokenAccount";
-          isMut: true;
-          isSigner: false;
+          writable: true;
         },
         {
-          name: "userUnderlyingTokenAccount";
-          isMut: true;
-          isSigner: false;
+          name: "tokenProgram";
+          address: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
         },
         {
-          name: "tokenProgram";
-          isMut: false;
-          isSigner: false;
+          name: "underlyingTokenProgram";
         }
       ];
       args: [
@@ -265,631 +260,228 @@ export type ConditionalVault = {
     },
     {
       name: "redeemConditionalTokensForUnderlyingTokens";
+      discriminator: [88, 122, 227, 150, 217, 183, 89, 81];
       accounts: [
         {
           name: "vault";
-          isMut: false;
-          isSigner: false;
         },
         {
           name: "conditionalOnFinalizeTokenMint";
-          isMut: true;
-          isSigner: false;
+          writable: true;
+          relations: ["vault"];
         },
         {
           name: "conditionalOnRevertTokenMint";
-          isMut: true;
-          isSigner: false;
+          writable: true;
+          relations: ["vault"];
+        },
+        {
+          name: "underlyingTokenMint";
         },
         {
           name: "vaultUnderlyingTokenAccount";
-          isMut: true;
-          isSigner: false;
+          writable: true;
         },
         {
           name: "authority";
-          isMut: false;
-          isSigner: true;
+          signer: true;
+        },
+        {
+          name: "userUnderlyingTokenAccount";
+          writable: true;
         },
         {
           name: "userConditionalOnFinalizeTokenAccount";
-          isMut: true;
-          isSigner: false;
+          writable: true;
         },
         {
           name: "userConditionalOnRevertTokenAccount";
-          isMut: true;
-          isSigner: false;
+          writable: true;
         },
         {
-          name: "userUnderlyingTokenAccount";
-          isMut: true;
-          isSigner: false;
+          name: "tokenProgram";
+          address: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
         },
         {
-          name: "tokenProgram";
-          isMut: false;
-          isSigner: false;
+          name: "underlyingTokenProgram";
         }
       ];
       args: [];
-    }
-  ];
-  accounts: [
+    },
     {
-      name: "conditionalVault";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "status";
-            type: {
-              defined: "VaultStatus";
+      name: "settleConditionalVault";
+      discriminator: [119, 121, 76, 31, 130, 158, 252, 103];
+      accounts: [
+        {
+          name: "settlementAuthority";
+          signer: true;
+          relations: ["vault"];
+        },
+        {
+          name: "vault";
+          writable: true;
+        }
+      ];
+      args: [
+        {
+          name: "newStatus";
+          type: {
+            defined: {
+              name: "vaultStatus";
             };
-          },
-          {
-            name: "settlementAuthority";
-            docs: [
-              "The account that can either finalize the vault to make conditional tokens",
-              "redeemable for underlying tokens or revert the vault to make deposit",
-              "slips redeemable for underlying tokens."
-            ];
-            type: "publicKey";
-          },
-          {
-            name: "underlyingTokenMint";
-            docs: ["The mint of the tokens that are deposited into the vault."];
-            type: "publicKey";
-          },
-          {
-            name: "underlyingTokenAccount";
-            docs: ["The vault's storage account for deposited funds."];
-            type: "publicKey";
-          },
-          {
-            name: "conditionalOnFinalizeTokenMint";
-            type: "publicKey";
-          },
-          {
-            name: "conditionalOnRevertTokenMint";
-            type: "publicKey";
-          },
-          {
-            name: "pdaBump";
-            type: "u8";
-          },
-          {
-            name: "decimals";
-            type: "u8";
-          }
-        ];
-      };
+          };
+        }
+      ];
     }
   ];
-  types: [
-    {
-      name: "AddMetadataToConditionalTokensArgs";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "proposalNumber";
-            type: "u64";
-          },
-          {
-            name: "onFinalizeUri";
-            type: "string";
-          },
-          {
-            name: "onRevertUri";
-            type: "string";
-          }
-        ];
-      };
-    },
-    {
-      name: "InitializeConditionalVaultArgs";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "settlementAuthority";
-            type: "publicKey";
-          }
-        ];
-      };
-    },
+  accounts: [
     {
-      name: "VaultStatus";
-      type: {
-        kind: "enum";
-        variants: [
-          {
-            name: "Active";
-          },
-          {
-            name: "Finalized";
-          },
-          {
-            name: "Reverted";
-          }
-        ];
-      };
+      name: "conditionalVault";
+      discriminator: [63, 132, 87, 98, 36, 51, 175, 247];
     }
   ];
   errors: [
     {
       code: 6000;
-      name: "InsufficientUnderlyingTokens";
+      name: "insufficientUnderlyingTokens";
       msg: "Insufficient underlying token balance to mint this amount of conditional tokens";
     },
     {
       code: 6001;
-      name: "InvalidVaultUnderlyingTokenAccount";
-      msg: "This `vault_underlying_token_account` is not this vault's `underlying_token_account`";
+      name: "insufficientConditionalTokens";
+      msg: "Insufficient conditional token balance to merge this amount";
     },
     {
       code: 6002;
-      name: "InvalidConditionalTokenMint";
-      msg: "This conditional token mint is not this vault's conditional token mint";
+      name: "invalidVaultUnderlyingTokenAccount";
+      msg: "This `vault_underlying_token_account` is not this vault's `underlying_token_account`";
     },
     {
       code: 6003;
-      name: "CantRedeemConditionalTokens";
-      msg: "Vault needs to be settled as finalized before users can redeem conditional tokens for underlying tokens";
+      name: "invalidUnderlyingTokenMint";
+      msg: "This `underlying_token_mint` is not this vault's `underlying_token_mint`";
     },
     {
       code: 6004;
-      name: "VaultAlreadySettled";
-      msg: "Once a vault has been settled, its status as either finalized or reverted cannot be changed";
-    }
-  ];
-};
-
-export const IDL: ConditionalVault = {
-  version: "0.3.0",
-  name: "conditional_vault",
-  instructions: [
-    {
-      name: "initializeConditionalVault",
-      accounts: [
-        {
-          name: "vault",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "underlyingTokenMint",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnFinalizeTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnRevertTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "vaultUnderlyingTokenAccount",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "payer",
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: "tokenProgram",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "associatedTokenProgram",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
-        },
-      ],
-      args: [
-        {
-          name: "args",
-          type: {
-            defined: "InitializeConditionalVaultArgs",
-          },
-        },
-      ],
+      name: "transferFeeCalculationFailed";
+      msg: "Couldn't calculate the underlying mint's transfer fee";
     },
     {
-      name: "addMetadataToConditionalTokens",
-      accounts: [
-        {
-          name: "payer",
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: "vault",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "underlyingTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "underlyingTokenMetadata",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnFinalizeTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnRevertTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnFinalizeTokenMetadata",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnRevertTokenMetadata",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "tokenMetadataProgram",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "rent",
-          isMut: false,
-          isSigner: false,
-        },
-      ],
-      args: [
-        {
-          name: "args",
-          type: {
-            defined: "AddMetadataToConditionalTokensArgs",
-          },
-        },
-      ],
+      code: 6005;
+      name: "invalidConditionalTokenMint";
+      msg: "This conditional token mint is not this vault's conditional token mint";
     },
     {
-      name: "settleConditionalVault",
-      accounts: [
-        {
-          name: "settlementAuthority",
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: "vault",
-          isMut: true,
-          isSigner: false,
-        },
-      ],
-      args: [
-        {
-          name: "newStatus",
-          type: {
-            defined: "VaultStatus",
-          },
-        },
-      ],
+      code: 6006;
+      name: "vaultNotActive";
+      msg: "Conditional tokens can only be minted or merged while the vault is active";
     },
     {
-      name: "mergeConditionalTokensForUnderlyingTokens",
-      accounts: [
-        {
-          name: "vault",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnFinalizeTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnRevertTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "vaultUnderlyingTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "authority",
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: "userConditionalOnFinalizeTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userConditionalOnRevertTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userUnderlyingTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "tokenProgram",
-          isMut: false,
-          isSigner: false,
-        },
-      ],
-      args: [
-        {
-          name: "amount",
-          type: "u64",
-        },
-      ],
+      code: 6007;
+      name: "cantRedeemConditionalTokens";
+      msg: "Vault needs to be settled before users can redeem conditional tokens for underlying tokens";
     },
     {
-      name: "mintConditionalTokens",
-      accounts: [
-        {
-          name: "vault",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnFinalizeTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnRevertTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "vaultUnderlyingTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "authority",
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: "userConditionalOnFinalizeTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userConditionalOnRevertTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userUnderlyingTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "tokenProgram",
-          isMut: false,
-          isSigner: false,
-        },
-      ],
-      args: [
-        {
-          name: "amount",
-          type: "u64",
-        },
-      ],
+      code: 6008;
+      name: "vaultAlreadySettled";
+      msg: "Once a vault has been settled, its status as either finalized or reverted cannot be changed";
     },
     {
-      name: "redeemConditionalTokensForUnderlyingTokens",
-      accounts: [
-        {
-          name: "vault",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnFinalizeTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "conditionalOnRevertTokenMint",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "vaultUnderlyingTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "authority",
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: "userConditionalOnFinalizeTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userConditionalOnRevertTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userUnderlyingTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "tokenProgram",
-          isMut: false,
-          isSigner: false,
-        },
-      ],
-      args: [],
-    },
-  ],
-  accounts: [
+      code: 6009;
+      name: "invalidSettlementStatus";
+      msg: "A vault can only be settled as finalized or reverted";
+    }
+  ];
+  types: [
     {
-      name: "conditionalVault",
+      name: "conditionalVault";
       type: {
-        kind: "struct",
+        kind: "struct";
         fields: [
           {
-            name: "status",
+            name: "status";
             type: {
-              defined: "VaultStatus",
-            },
+              defined: {
+                name: "vaultStatus";
+              };
+            };
           },
           {
-            name: "settlementAuthority",
+            name: "settlementAuthority";
             docs: [
-              "The account that can either finalize the vault to make conditional tokens",
-              "redeemable for underlying tokens or revert the vault to make deposit",
-              "slips redeemable for underlying tokens.",
-            ],
-            type: "publicKey",
-          },
-          {
-            name: "underlyingTokenMint",
-            docs: ["The mint of the tokens that are deposited into the vault."],
-            type: "publicKey",
-          },
-          {
-            name: "underlyingTokenAccount",
-            docs: ["The vault's storage account for deposited funds."],
-            type: "publicKey",
+              "The account that can either finalize the vault to make",
+              "conditional-on-finalize tokens redeemable for underlying tokens or revert",
+              "the vault to make conditional-on-revert tokens redeemable."
+            ];
+            type: "pubkey";
           },
           {
-            name: "conditionalOnFinalizeTokenMint",
-            type: "publicKey",
+            name: "underlyingTokenMint";
+            type: "pubkey";
           },
           {
-            name: "conditionalOnRevertTokenMint",
-            type: "publicKey",
+            name: "underlyingTokenAccount";
+            type: "pubkey";
           },
           {
-            name: "pdaBump",
-            type: "u8",
+            name: "conditionalOnFinalizeTokenMint";
+            type: "pubkey";
           },
           {
-            name: "decimals",
-            type: "u8",
+            name: "conditionalOnRevertTokenMint";
+            type: "pubkey";
           },
-        ],
-      },
-    },
-  ],
-  types: [
-    {
-      name: "AddMetadataToConditionalTokensArgs",
-      type: {
-        kind: "struct",
-        fields: [
           {
-            name: "proposalNumber",
-            type: "u64",
+            name: "nonce";
+            docs: [
+              "Lets one settlement authority have many vaults over the same underlying",
+              "token. Autocrat requires this to be the proposal number."
+            ];
+            type: "u64";
           },
           {
-            name: "onFinalizeUri",
-            type: "string",
+            name: "pdaBump";
+            type: "u8";
           },
           {
-            name: "onRevertUri",
-            type: "string",
-          },
-        ],
-      },
+            name: "decimals";
+            type: "u8";
+          }
+        ];
+      };
     },
     {
-      name: "InitializeConditionalVaultArgs",
+      name: "initializeConditionalVaultArgs";
       type: {
-        kind: "struct",
+        kind: "struct";
         fields: [
           {
-            name: "settlementAuthority",
-            type: "publicKey",
+            name: "settlementAuthority";
+            type: "pubkey";
           },
-        ],
-      },
+          {
+            name: "nonce";
+            type: "u64";
+          }
+        ];
+      };
     },
     {
-      name: "VaultStatus",
+      name: "vaultStatus";
       type: {
-        kind: "enum",
+        kind: "enum";
         variants: [
           {
-            name: "Active",
+            name: "active";
           },
           {
-            name: "Finalized",
+            name: "finalized";
           },
           {
-            name: "Reverted",
-          },
-        ],
-      },
-    },
-  ],
-  errors: [
-    {
-      code: 6000,
-      name: "InsufficientUnderlyingTokens",
-      msg: "Insufficient underlying token balance to mint this amount of conditional tokens",
-    },
-    {
-      code: 6001,
-      name: "InvalidVaultUnderlyingTokenAccount",
-      msg: "This `vault_underlying_token_account` is not this vault's `underlying_token_account`",
-    },
-    {
-      code: 6002,
-      name: "InvalidConditionalTokenMint",
-      msg: "This conditional token mint is not this vault's conditional token mint",
-    },
-    {
-      code: 6003,
-      name: "CantRedeemConditionalTokens",
-      msg: "Vault needs to be settled as finalized before users can redeem conditional tokens for underlying tokens",
-    },
-    {
-      code: 6004,
-      name: "VaultAlreadySettled",
-      msg: "Once a vault has been settled, its status as either finalized or reverted cannot be changed",
-    },
-  ],
+            name: "reverted";
+          }
+        ];
+      };
+    }
+  ];
 };
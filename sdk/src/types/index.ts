@@ -7,7 +7,7 @@ export { Amm, Amm as AmmIDL } from "./amm";
 import { ConditionalVault } from "./conditional_vault";
 export {
   ConditionalVault,
-  IDL as ConditionalVaultIDL,
+  ConditionalVault as ConditionalVaultIDL,
 } from "./conditional_vault";
 
 export { LowercaseKeys } from "./utils";
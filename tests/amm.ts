@@ -1,187 +1,209 @@
 // @ts-nocheck
 
-import { assert } from 'chai';
+import { assert } from "chai";
 
-import * as anchor from '@coral-xyz/anchor';
-import {
-  AnchorProvider,
-  BN,
-} from '@coral-xyz/anchor';
-import { MAINNET_USDC as USDC } from '@metadaoproject/futarchy';
+import * as anchor from "@coral-xyz/anchor";
+import { AnchorProvider, BN } from "@coral-xyz/anchor";
 import {
+  getAccount,
   getAssociatedTokenAddressSync,
   getOrCreateAssociatedTokenAccount,
-} from '@solana/spl-token';
-import {
-  Connection,
-  Keypair,
-  PublicKey,
-} from '@solana/web3.js';
+  mintTo,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
 
 import {
   AmmClient,
+  CreateAmmArgs,
   createMint,
   getAmmAddr,
-  PriceMath,
-} from '../sdk/src';
-import { expectError } from './utils/utils';
+  getAmmLpMintAddr,
+} from "../sdk/src";
+import { expectError } from "./utils/utils";
 
 const META_DECIMALS = 6;
 const USDC_DECIMALS = 6;
 
+const META = (amount: number) => new BN(amount * 10 ** META_DECIMALS);
+const USDC = (amount: number) => new BN(amount * 10 ** USDC_DECIMALS);
+
 describe("amm", async function () {
   let provider: AnchorProvider,
     ammClient: AmmClient,
-    META: PublicKey,
+    META_MINT: PublicKey,
+    USDC_MINT: PublicKey,
     payer: Keypair,
     connection: Connection,
-    proposal: PublicKey,
     amm: PublicKey,
-    
-    lpMint: PublicKey;
+    proposal: PublicKey,
+    ammArgs: CreateAmmArgs;
 
   before(async function () {
-    connection = new Connection(process.env.ANCHOR_PROVIDER as string, "confirmed"),
+    connection = new Connection(
+      process.env.ANCHOR_PROVIDER as string,
+      "confirmed"
+    );
     payer = Keypair.fromSecretKey(
       new Uint8Array(
-        JSON.parse(require('fs').readFileSync(process.env.ANCHOR_WALLET as string, "utf-8"))
+        JSON.parse(
+          require("fs").readFileSync(
+            process.env.ANCHOR_WALLET as string,
+            "utf-8"
+          )
+        )
       )
     );
 
-    provider = new AnchorProvider(connection, new anchor.Wallet(payer), {})
-    ammClient = await AmmClient.createClient({ provider, ammProgramId: new PublicKey("62BiVvL2o3dHYbSAjh1ywDTqC9rm7j9eg2PoRSSG9nEH") });
-  });
+    provider = new AnchorProvider(connection, new anchor.Wallet(payer), {});
+    ammClient = await AmmClient.createClient({
+      provider,
+      ammProgramId: new PublicKey(
+        "62BiVvL2o3dHYbSAjh1ywDTqC9rm7j9eg2PoRSSG9nEH"
+      ),
+    });
 
-  beforeEach(async function () {
-    
-    let userUsdcAccount = await getAssociatedTokenAddressSync(
-      USDC,
+    USDC_MINT = await createMint(
+      connection,
+      payer,
       payer.publicKey,
-      true,
-    
+      null,
+      USDC_DECIMALS
     );
-    console.log(userUsdcAccount)
-      const mkp = Keypair.generate()
-    var  [amm, bump] = getAmmAddr(
+    const userUsdcAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      payer,
+      USDC_MINT,
+      payer.publicKey
+    );
+    await mintTo(
+      connection,
+      payer,
+      USDC_MINT,
+      userUsdcAccount.address,
+      payer,
+      BigInt(USDC(1_000_000).toString())
+    );
+  });
+
+  beforeEach(async function () {
+    // the amm mints META, so it has to be META's mint and freeze authority
+    // before it exists
+    const metaKeypair = Keypair.generate();
+    [amm] = getAmmAddr(
       ammClient.program.programId,
-      mkp.publicKey,
-      USDC,
+      metaKeypair.publicKey,
+      USDC_MINT,
+      payer.publicKey
     );
 
-    META = await createMint(
+    META_MINT = await createMint(
       connection,
       payer,
       amm,
       amm,
       META_DECIMALS,
-      mkp,
-      {
-        skipPreflight: false
-      }
-    )
-  getOrCreateAssociatedTokenAccount(
-      connection,
-      payer,
-      META,
-      payer.publicKey,
-      true,
-
-      "recent",
-      {
-        skipPreflight: false
-      }
+      metaKeypair
     );
- const qata =   await getAssociatedTokenAddressSync(
-
-     USDC,
-     amm,
-     true)
-   console.log(qata)
-
-const bata = await  getAssociatedTokenAddressSync(
-    META,
-    amm,
-    true,
-  );
-  
-console.log(bata)
+
     proposal = Keypair.generate().publicKey;
-    amm = await ammClient
-    .createAmm(
+    ammArgs = {
+      pof: "p",
+      uri: "http://google.com",
       proposal,
-      META,
-      USDC,
-      500_000_000_000,
-      
-      "p",
-      "http://google.com",
-      1,
-      bata,
-      qata,
-      bump
-
-    );
+      proposalNumber: 1,
+      settlementAuthority: payer.publicKey,
+      symbol: "META",
+      twapInitialObservation: new BN(500_000_000_000),
+      twapMaxObservationChangePerUpdate: new BN(10_000_000_000),
+      feeBps: 100,
+      feeRecipient: payer.publicKey,
+      winnerPremiumBps: 0,
+      loserHaircutBps: 0,
+      // 10 META for 10,000 USDC, so META starts at 1,000 USDC
+      initialVBaseReserves: META(10),
+      initialVQuoteReserves: USDC(10_000),
+      graduationThreshold: USDC(10_500),
+      graduationTarget: { recipient: { 0: payer.publicKey } },
+      baseMintDecimals: META_DECIMALS,
+    };
+
+    await ammClient.createAmm(ammArgs, META_MINT, USDC_MINT);
   });
 
   describe("#create_amm", async function () {
-    it("creates an amm", async function () {
-      let expectedInitialObservation = new BN(500_000_000_000);
-      let expectedMaxObservationChangePerUpdate = new BN(10_000_000_000);
-
-      let bump: number;
-      [amm, bump] = getAmmAddr(
+    it("creates an amm keyed by its creator", async function () {
+      const [expectedAmm, bump] = getAmmAddr(
         ammClient.program.programId,
-        META,
-        USDC,
+        META_MINT,
+        USDC_MINT,
+        payer.publicKey
       );
+      assert.equal(amm.toBase58(), expectedAmm.toBase58());
+
       const ammAcc = await ammClient.getAmm(amm);
 
       assert.equal(ammAcc.bump, bump);
-      assert.equal(ammAcc.baseMint.toBase58(), META.toBase58());
-      assert.equal(ammAcc.quoteMint.toBase58(), USDC.toBase58());
-      assert.equal(ammAcc.baseMintDecimals, 6);
-      assert.equal(ammAcc.quoteMintDecimals, 6);
+      assert.equal(ammAcc.creator.toBase58(), payer.publicKey.toBase58());
+      assert.equal(ammAcc.baseMint.toBase58(), META_MINT.toBase58());
+      assert.equal(ammAcc.quoteMint.toBase58(), USDC_MINT.toBase58());
+      assert.deepEqual(ammAcc.baseSupply, { minted: {} });
+      assert.equal(ammAcc.baseMintDecimals, META_DECIMALS);
+      assert.equal(ammAcc.quoteMintDecimals, USDC_DECIMALS);
+      assert.equal(ammAcc.feeBps, 100);
+      assert.equal(ammAcc.feeRecipient.toBase58(), payer.publicKey.toBase58());
+      assert.equal(ammAcc.vBaseReserves.toString(), META(10).toString());
+      assert.equal(ammAcc.vQuoteReserves.toString(), USDC(10_000).toString());
+      assert.equal(
+        ammAcc.graduationThreshold.toString(),
+        USDC(10_500).toString()
+      );
+      assert.deepEqual(ammAcc.status, { trading: {} });
+      assert.equal(
+        ammAcc.oracle.initialObservation.toString(),
+        ammArgs.twapInitialObservation.toString()
+      );
+      assert.equal(
+        ammAcc.oracle.lastObservation.toString(),
+        ammArgs.twapInitialObservation.toString()
+      );
+      assert.equal(
+        ammAcc.oracle.maxObservationChangePerUpdate.toString(),
+        ammArgs.twapMaxObservationChangePerUpdate.toString()
+      );
     });
 
     it("fails to create an amm with two identical mints", async function () {
-      let [
-        twapFirstObservationScaled,
-        twapMaxObservationChangePerUpdateScaled,
-      ] = PriceMath.getAmmPrices(META_DECIMALS, USDC_DECIMALS, 100, 1);
-
       const callbacks = expectError(
         "SameTokenMints",
         "create AMM succeeded despite same token mints"
       );
-        const [_, bump] = getAmmAddr(
-        ammClient.program.programId,
-        META,
-        META,
-      );
-      let proposal = Keypair.generate().publicKey;
-
-      (await ammClient
-        .createAmmIx(
-          META,
-          META,
-          "p",
-          "http://google.com",
-          0,
-
-        "Manifesto",
-        bump
-        ))
+
+      (await ammClient.createAmmIx(ammArgs, USDC_MINT, USDC_MINT))
         .rpc()
         .then(callbacks[0], callbacks[1]);
     });
   });
 
-  describe("#swap", async function () {
-    beforeEach(async function () {
-      await ammClient
-        .swap(amm, { buy: {} }, 0.01, 1)
+  describe("#crank_that_twap", async function () {
+    it("doesn't record an observation within a minute of the last one", async function () {
+      const ammStart = await ammClient.getAmm(amm);
+
+      await ammClient.crankThatTwapIx(amm).rpc();
+
+      const ammEnd = await ammClient.getAmm(amm);
+      assert.equal(
+        ammEnd.oracle.lastUpdatedSlot.toString(),
+        ammStart.oracle.lastUpdatedSlot.toString()
+      );
+      assert.equal(
+        ammEnd.oracle.aggregator.toString(),
+        ammStart.oracle.aggregator.toString()
+      );
     });
+  });
 
+  describe("#swap", async function () {
     it("fails when you have insufficient balance", async () => {
       let callbacks = expectError(
         "InsufficientBalance",
@@ -189,19 +211,8 @@ console.log(bata)
       );
 
       await ammClient
-        .swap(amm, { buy: {} }, 0.01, 1)
-        .then(callbacks[0], callbacks[1]);
-        await ammClient
-          .swap(amm, { buy: {} }, 0.01, 1)
-          .then(callbacks[0], callbacks[1]);
-
-          await ammClient
-          .swap(amm, { buy: {} }, 0.01, 1)
-          .then(callbacks[0], callbacks[1]);
-    
-
-      await ammClient
-        .swap(amm, { sell: {} }, 1, 1)
+        .swapIx(amm, META_MINT, USDC_MINT, { sell: {} }, META(1), new BN(1))
+        .rpc()
         .then(callbacks[0], callbacks[1]);
     });
 
@@ -212,48 +223,65 @@ console.log(bata)
       // swap amount = 100
       // swap amount after fees = 99
       // new USDC amount = 10,099
-      // new META amount = 100,000 / 10,099 = 9.9019...
-      // meta out = 10 - 9.9019 = 0.098029507
-
-      const expectedOut = 0.098029507;
+      // new META amount = 100,000 / 10,099 = 9.901970...
+      // meta out = 10 - 9.901970 = 0.098029
+      const expectedOut = new BN(98_029);
 
       const storedAmm = await ammClient.getAmm(amm);
       let sim = ammClient.simulateSwap(
-        new BN(100 * 10 ** 6),
+        USDC(100),
         { buy: {} },
         storedAmm.vBaseReserves,
         storedAmm.vQuoteReserves
       );
-      assert.equal(
-        sim.expectedOut.toString(),
-        new BN(expectedOut * 10 ** 9).toString()
-      );
+      assert.equal(sim.expectedOut.toString(), expectedOut.toString());
 
-      // first, show that it fails when we expect 1 hanson too much
+      // first, show that it fails when we expect 1 token too much
       let callbacks = expectError(
         "SwapSlippageExceeded",
         "we got back too many tokens from the AMM"
       );
 
       await ammClient
-        .swap(amm, { buy: {} }, 0.01, expectedOut + 0.000000001)
+        .swapIx(
+          amm,
+          META_MINT,
+          USDC_MINT,
+          { buy: {} },
+          USDC(100),
+          expectedOut.addn(1)
+        )
+        .rpc()
         .then(callbacks[0], callbacks[1]);
 
-      await ammClient.swap(amm, { buy: {} }, 0.01, expectedOut);
+      await ammClient
+        .swapIx(amm, META_MINT, USDC_MINT, { buy: {} }, USDC(100), expectedOut)
+        .rpc();
 
+      const ammAfter = await ammClient.getAmm(amm);
+      assert.equal(
+        ammAfter.vBaseReserves.toString(),
+        sim.newBaseReserves.toString()
+      );
+      assert.equal(
+        ammAfter.vQuoteReserves.toString(),
+        sim.newQuoteReserves.toString()
+      );
     });
 
-    it("sells", async function () {
-      // USDC amount = 10,000
-      // META amount = 10
-      // k = (10,000 * 10) = 100,000
-      // swap amount = 1
-      // swap amount after fees = 0.99
-      // new META amount = 10.99
-      // new USDC amount = 100,000 / 10.99 = 9099.181074
-      // usdc out = 10,000 - 9099.181074 = 900.818926
+    it("sells what it bought", async function () {
+      await ammClient
+        .swapIx(amm, META_MINT, USDC_MINT, { buy: {} }, USDC(100), new BN(1))
+        .rpc();
 
-      const expectedOut = 900.818926;
+      const storedAmm = await ammClient.getAmm(amm);
+      const baseToSell = new BN(98_029).divn(2);
+      let sim = ammClient.simulateSwap(
+        baseToSell,
+        { sell: {} },
+        storedAmm.vBaseReserves,
+        storedAmm.vQuoteReserves
+      );
 
       let callbacks = expectError(
         "SwapSlippageExceeded",
@@ -261,83 +289,233 @@ console.log(bata)
       );
 
       await ammClient
-        .swap(amm, { sell: {} }, 1, expectedOut + 0.000001)
+        .swapIx(
+          amm,
+          META_MINT,
+          USDC_MINT,
+          { sell: {} },
+          baseToSell,
+          sim.expectedOut.addn(1)
+        )
+        .rpc()
         .then(callbacks[0], callbacks[1]);
 
-      await ammClient.swap(amm, { sell: {} }, 1, expectedOut);
-
+      await ammClient
+        .swapIx(
+          amm,
+          META_MINT,
+          USDC_MINT,
+          { sell: {} },
+          baseToSell,
+          sim.expectedOut
+        )
+        .rpc();
     });
 
-    it("swap base to quote and back, should not be profitable", async function () {
-      const permissionlessAmmStart = await ammClient.program.account.amm.fetch(
-        amm
-      );
-      const ammEnd = await ammClient.getAmm(amm);
+    it("swap quote to base and back, should not be profitable", async function () {
+      const ammStart = await ammClient.getAmm(amm);
 
-      let startingBaseSwapAmount = 1 * 10 ** 9;
+      let startingQuoteSwapAmount = USDC(1);
 
       await ammClient
         .swapIx(
           amm,
-          META,
-          USDC,
-          { sell: {} },
-          new BN(startingBaseSwapAmount),
+          META_MINT,
+          USDC_MINT,
+          { buy: {} },
+          startingQuoteSwapAmount,
           new BN(1)
         )
         .rpc();
 
       const ammMiddle = await ammClient.getAmm(amm);
-      let quoteReceived =
-        permissionlessAmmStart.vQuoteReserves.toNumber() -
-        ammMiddle.vQuoteReserves.toNumber();
+      let baseReceived = ammStart.vBaseReserves.sub(ammMiddle.vBaseReserves);
 
       await ammClient
-        .swapIx(amm, META, USDC, { buy: {} }, new BN(quoteReceived), new BN(1))
+        .swapIx(
+          amm,
+          META_MINT,
+          USDC_MINT,
+          { sell: {} },
+          baseReceived,
+          new BN(1)
+        )
         .rpc();
 
-      const permissionlessAmmEnd = await ammClient.program.account.amm.fetch(
-        amm
-      );
-      let baseReceived =
-        ammMiddle.vBaseReserves.toNumber() -
-        permissionlessAmmEnd.vBaseReserves.toNumber();
+      const ammEnd = await ammClient.getAmm(amm);
+      let quoteReceived = ammMiddle.vQuoteReserves.sub(ammEnd.vQuoteReserves);
 
-      assert.isBelow(baseReceived, startingBaseSwapAmount);
-      assert.isAbove(baseReceived, startingBaseSwapAmount * 0.98); // 1% swap fee both ways
+      assert.isBelow(
+        quoteReceived.toNumber(),
+        startingQuoteSwapAmount.toNumber()
+      );
+      assert.isAbove(
+        quoteReceived.toNumber(),
+        startingQuoteSwapAmount.toNumber() * 0.98
+      ); // 1% swap fee both ways
+
+      // the round trip leaves the price above where the curve started
+      assert.isTrue(
+        ammEnd.vQuoteReserves
+          .mul(ammArgs.initialVBaseReserves)
+          .gte(ammEnd.vBaseReserves.mul(ammArgs.initialVQuoteReserves))
+      );
     });
+  });
 
-    it("swap quote to base and back, should not be profitable", async function () {
-      const ammStart = await ammClient.getAmm(amm);
+  describe("#remove_liquidity", async function () {
+    it("pays an LP out without leaving sellers short", async function () {
+      await ammClient
+        .swapIx(amm, META_MINT, USDC_MINT, { buy: {} }, USDC(100), new BN(1))
+        .rpc();
+
+      await ammClient
+        .addLiquidityIx(
+          amm,
+          META_MINT,
+          USDC_MINT,
+          USDC(10),
+          META(1),
+          new BN(1)
+        )
+        .rpc();
+
+      const [lpMint] = getAmmLpMintAddr(ammClient.program.programId, amm);
+      const userLpAccount = await getAccount(
+        connection,
+        getAssociatedTokenAddressSync(
+          lpMint,
+          payer.publicKey,
+          false,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
 
-      let startingQuoteSwapAmount = 1 * 10 ** 6;
+      await ammClient
+        .removeLiquidityIx(
+          amm,
+          META_MINT,
+          USDC_MINT,
+          new BN(userLpAccount.amount.toString()),
+          new BN(0),
+          new BN(0)
+        )
+        .rpc();
+
+      // the trader can still sell everything they bought
+      const userMetaAccount = await getAccount(
+        connection,
+        getAssociatedTokenAddressSync(META_MINT, payer.publicKey)
+      );
 
       await ammClient
         .swapIx(
           amm,
-          META,
-          USDC,
-          { buy: {} },
-          new BN(startingQuoteSwapAmount),
+          META_MINT,
+          USDC_MINT,
+          { sell: {} },
+          new BN(userMetaAccount.amount.toString()),
           new BN(1)
         )
         .rpc();
+    });
+  });
 
-      const ammMiddle = await ammClient.getAmm(amm);
-      let baseReceived =
-        ammStart.vBaseReserves.toNumber() - ammMiddle.vBaseReserves.toNumber();
+  describe("#collect_fees", async function () {
+    it("sends the accrued fees to the fee recipient", async function () {
+      await ammClient
+        .swapIx(amm, META_MINT, USDC_MINT, { buy: {} }, USDC(100), new BN(1))
+        .rpc();
+
+      let storedAmm = await ammClient.getAmm(amm);
+      assert.equal(storedAmm.quoteFeesAccrued.toString(), USDC(1).toString());
 
       await ammClient
-        .swapIx(amm, META, USDC, { sell: {} }, new BN(baseReceived), new BN(1))
+        .collectFeesIx(amm, META_MINT, USDC_MINT, payer.publicKey)
         .rpc();
 
-      const ammEnd = await ammClient.getAmm(amm);
-      let quoteReceived =
-        ammMiddle.vQuoteReserves.toNumber() - ammEnd.vQuoteReserves.toNumber();
+      storedAmm = await ammClient.getAmm(amm);
+      assert.equal(storedAmm.baseFeesAccrued.toString(), "0");
+      assert.equal(storedAmm.quoteFeesAccrued.toString(), "0");
+    });
+  });
+
+  describe("#graduate", async function () {
+    it("graduates the virtual liquidity and lets the LP withdraw theirs", async function () {
+      await ammClient
+        .swapIx(amm, META_MINT, USDC_MINT, { buy: {} }, USDC(600), new BN(1))
+        .rpc();
+
+      await ammClient
+        .addLiquidityIx(
+          amm,
+          META_MINT,
+          USDC_MINT,
+          USDC(10),
+          META(1),
+          new BN(1)
+        )
+        .rpc();
+
+      let callbacks = expectError(
+        "MarketNotFinalized",
+        "graduated a market that was still trading"
+      );
+
+      await ammClient
+        .graduateToRecipientIx(amm, META_MINT, USDC_MINT, payer.publicKey)
+        .rpc()
+        .then(callbacks[0], callbacks[1]);
+
+      await ammClient
+        .settleMarketIx(amm, { finalized: { 0: { winning: {} } } })
+        .rpc();
+
+      await ammClient
+        .graduateToRecipientIx(amm, META_MINT, USDC_MINT, payer.publicKey)
+        .rpc();
+
+      let storedAmm = await ammClient.getAmm(amm);
+      assert.deepEqual(storedAmm.status, { graduated: {} });
+      assert.equal(storedAmm.virtualLpSupply.toString(), "0");
+
+      // the LP's share stayed behind
+      const [lpMint] = getAmmLpMintAddr(ammClient.program.programId, amm);
+      const userLpAccount = await getAccount(
+        connection,
+        getAssociatedTokenAddressSync(
+          lpMint,
+          payer.publicKey,
+          false,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      await ammClient
+        .removeLiquidityIx(
+          amm,
+          META_MINT,
+          USDC_MINT,
+          new BN(userLpAccount.amount.toString()),
+          new BN(1),
+          new BN(1)
+        )
+        .rpc();
 
-      assert.isBelow(quoteReceived, startingQuoteSwapAmount);
-      assert.isAbove(quoteReceived, startingQuoteSwapAmount * 0.98);
+      await ammClient
+        .collectFeesIx(amm, META_MINT, USDC_MINT, payer.publicKey)
+        .rpc();
+
+      await ammClient
+        .closeMarketIx(amm, META_MINT, USDC_MINT, payer.publicKey)
+        .rpc();
+
+      storedAmm = await ammClient.getAmm(amm);
+      assert.deepEqual(storedAmm.status, { closed: {} });
     });
   });
 });
-